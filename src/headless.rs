@@ -0,0 +1,381 @@
+use std::{
+    io::{self, BufRead, Write},
+    sync::{Arc, Mutex},
+};
+
+use actuire::logic::{
+    acquire_event::AcquireEvent,
+    acquire_game::{AcquireGame, AcquireGameCallback},
+    acquire_player_strategy::{AcquirePlayerStrategy, GreedyBot},
+    acquire_request::AcquireRequest,
+    acquire_response::{
+        AcquirePlayerResponse, AcquireResponse, BuyStockChoice, DisposeStockChoice,
+    },
+    hotel_data::Hotel,
+    tile::Tile,
+};
+
+// prints everything the game reports straight to stdout
+#[derive(Default)]
+pub struct StdoutMessages {
+    player_names: Mutex<Vec<String>>,
+}
+
+impl StdoutMessages {
+    pub fn set_player_names(&self, player_names: Vec<String>) {
+        *self.player_names.lock().unwrap() = player_names;
+    }
+}
+
+impl AcquireGameCallback for StdoutMessages {
+    fn send_message(&self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn on_event(&self, event: &AcquireEvent) {
+        println!("{}", event.describe(&self.player_names.lock().unwrap()));
+    }
+}
+
+// runs a game from commands read on stdin until stdin is closed or the game ends,
+// the last number_of_bots players are played by the computer
+pub fn run(
+    mut game: AcquireGame<StdoutMessages>,
+    messages: Arc<StdoutMessages>,
+    number_of_bots: usize,
+) -> io::Result<()> {
+    let number_of_players = game.players.len();
+    messages.set_player_names(game.players.iter().map(|p| p.name.clone()).collect());
+
+    let bots: Vec<Option<GreedyBot>> = (0..number_of_players)
+        .map(|player| {
+            (player + number_of_bots >= number_of_players).then(|| GreedyBot::new(player))
+        })
+        .collect();
+
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        while let Some((player, bot)) = game
+            .get_players_to_respond()
+            .into_iter()
+            .find_map(|player| bots[player].as_ref().map(|bot| (player, bot)))
+        {
+            let request = *game.get_current_request();
+            let response = AcquirePlayerResponse::new(bot.decide(&game, &request), player);
+            if let Err(error) = game.handle_player_response(response) {
+                println!("Error: {}", error);
+                break;
+            }
+        }
+
+        if game.is_game_over() {
+            let names: Vec<String> = game.players.iter().map(|p| p.name.clone()).collect();
+            for line in game.game_stats().describe(&names) {
+                println!("{}", line);
+            }
+            #[cfg(feature = "serde")]
+            println!("{}", game.results_json());
+            return Ok(());
+        }
+
+        print!("{}> ", get_prompt(&game));
+        io::stdout().flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(()),
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.trim().eq_ignore_ascii_case("undo") {
+            if let Err(error) = game.undo() {
+                println!("Error: {}", error);
+            }
+            continue;
+        }
+
+        let response = parse_response(&line, game.get_current_request());
+        // everyone shares the terminal, so a command is made by the player asked
+        let player = game.get_current_request().get_player();
+        if let Err(error) = response.and_then(|response| {
+            game.handle_player_response(AcquirePlayerResponse::new(response, player))
+                .map_err(|e| e.to_string())
+        }) {
+            println!("Error: {}", error);
+        }
+    }
+}
+
+fn get_prompt(game: &AcquireGame<StdoutMessages>) -> String {
+    let request = game.get_current_request();
+    let name = |player: usize| game.players[player].name.clone();
+    let hotels = || {
+        game.get_acceptable_hotels_for_response()
+            .iter()
+            .map(|hotel| hotel.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match *request {
+        AcquireRequest::PlayStartingTile(player) => {
+            format!("{}, draw a starting tile (start)", name(player))
+        }
+        AcquireRequest::PlayTile(player) => {
+            let tiles = game.players[player]
+                .tiles
+                .iter()
+                .map(|tile| tile.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!("{}, play a tile [{}] (tile <tile>)", name(player), tiles)
+        }
+        AcquireRequest::ChooseNewChain(player) => {
+            format!(
+                "{}, found a chain [{}] (chain <hotel>)",
+                name(player),
+                hotels()
+            )
+        }
+        AcquireRequest::ChooseMergerSurvivor(player) => {
+            format!(
+                "{}, choose the survivor [{}] (chain <hotel>)",
+                name(player),
+                hotels()
+            )
+        }
+        AcquireRequest::ChooseDefunctChainToResolve(player) => format!(
+            "{}, choose the defunct chain to resolve [{}] (chain <hotel>)",
+            name(player),
+            hotels()
+        ),
+        AcquireRequest::DisposeStock(player) => format!(
+            "{}, dispose of {} defunct shares (sell|keep|trade [all|<shares>] | split <keep> <sell> <trade>)",
+            name(player),
+            game.get_remaining_shares_to_dispose(player)
+        ),
+        AcquireRequest::BuyStock(player) => {
+            let hotels = game
+                .buyable_hotels(player)
+                .iter()
+                .map(|(hotel, price)| format!("{} ${}", hotel, price))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "{}, buy stock [{}] (buy <hotel> [shares] | pass)",
+                name(player),
+                hotels
+            )
+        }
+        AcquireRequest::EndGame(player) => {
+            format!("{}, end the game? (end | continue)", name(player))
+        }
+    }
+}
+
+// turns a command such as "tile 3-A" or "sell all" into a response, the current
+// request decides what a chosen hotel is for and whose shares are disposed of
+fn parse_response(line: &str, request: &AcquireRequest) -> Result<AcquireResponse, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let command = words
+        .first()
+        .map(|word| word.to_lowercase())
+        .unwrap_or_default();
+    let argument = words.get(1).copied();
+
+    let expect_argument =
+        |what: &str| argument.ok_or_else(|| format!("{} needs a {}", command, what));
+
+    match command.as_str() {
+        "start" => Ok(AcquireResponse::StartingTile),
+        "tile" => Ok(AcquireResponse::Tile(
+            expect_argument("tile")?.parse::<Tile>()?,
+        )),
+        "chain" => {
+            let hotel = expect_argument("hotel")?.parse::<Hotel>()?;
+            match request {
+                AcquireRequest::ChooseMergerSurvivor(_) => {
+                    Ok(AcquireResponse::MergerSurvivor(hotel))
+                }
+                AcquireRequest::ChooseDefunctChainToResolve(_) => {
+                    Ok(AcquireResponse::DefunctChainToResolve(hotel))
+                }
+                _ => Ok(AcquireResponse::NewChain(hotel)),
+            }
+        }
+        "buy" => {
+            let hotel = expect_argument("hotel")?.parse::<Hotel>()?;
+            match words.get(2) {
+                Some(shares) => {
+                    let shares = shares
+                        .parse::<u32>()
+                        .map_err(|_| format!("{} is not a number of shares", shares))?;
+                    Ok(AcquireResponse::BuyStock(BuyStockChoice::BuyN(
+                        hotel, shares,
+                    )))
+                }
+                None => Ok(AcquireResponse::BuyStock(BuyStockChoice::Buy(hotel))),
+            }
+        }
+        "pass" => Ok(AcquireResponse::BuyStock(BuyStockChoice::Pass)),
+        // disposal asks one player at a time, so the shares are always the asked player's
+        "sell" | "keep" | "trade" => {
+            let all = argument.is_some_and(|word| word.eq_ignore_ascii_case("all"));
+            if words.len() > 2 {
+                return Err(format!("{} takes all or a number of shares", command));
+            }
+            // "sell 4" sells 4 shares
+            let count = match argument {
+                Some(_) if !all => Some(
+                    argument
+                        .unwrap_or_default()
+                        .parse::<u32>()
+                        .map_err(|_| format!("{} needs a number of shares", command))?,
+                ),
+                _ => None,
+            };
+
+            let choice = match (command.as_str(), all, count) {
+                ("sell", false, Some(count)) => DisposeStockChoice::SellN(count),
+                ("trade", false, Some(count)) => DisposeStockChoice::TradeN(count),
+                ("keep", false, Some(_)) => {
+                    return Err("keep takes all or a single share".to_string())
+                }
+                ("sell", false, None) => DisposeStockChoice::Sell,
+                ("sell", true, _) => DisposeStockChoice::SellAll,
+                ("keep", false, None) => DisposeStockChoice::Keep,
+                ("keep", true, _) => DisposeStockChoice::KeepAll,
+                ("trade", false, None) => DisposeStockChoice::Trade,
+                _ => DisposeStockChoice::TradeAll,
+            };
+            Ok(AcquireResponse::DisposeStock(request.get_player(), choice))
+        }
+        // "split 2 1 4" keeps 2, sells 1 and trades 4 shares
+        "split" => {
+            let numbers = words[1..]
+                .iter()
+                .map(|word| word.parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+                .filter(|numbers| numbers.len() == 3)
+                .ok_or("split needs keep, sell and trade counts")?;
+            Ok(AcquireResponse::DisposeStock(
+                request.get_player(),
+                DisposeStockChoice::Split {
+                    keep: numbers[0],
+                    sell: numbers[1],
+                    trade: numbers[2],
+                },
+            ))
+        }
+        "end" => Ok(AcquireResponse::EndGame(true)),
+        "continue" => Ok(AcquireResponse::EndGame(false)),
+        _ => Err(format!("Unknown command: {}", line.trim())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response() {
+        let request = AcquireRequest::PlayTile(0);
+        assert_eq!(
+            parse_response("tile 3-A", &request),
+            Ok(AcquireResponse::Tile(Tile::from((0, 2))))
+        );
+        assert_eq!(
+            parse_response("buy tower", &request),
+            Ok(AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Tower)))
+        );
+        assert_eq!(
+            parse_response("buy tower 2", &request),
+            Ok(AcquireResponse::BuyStock(BuyStockChoice::BuyN(
+                Hotel::Tower,
+                2
+            )))
+        );
+        assert!(parse_response("buy tower two", &request).is_err());
+        assert_eq!(
+            parse_response("pass", &request),
+            Ok(AcquireResponse::BuyStock(BuyStockChoice::Pass))
+        );
+        assert!(parse_response("tile", &request).is_err());
+        assert!(parse_response("dance", &request).is_err());
+    }
+
+    #[test]
+    fn test_parse_disposal_is_for_the_asked_player() {
+        let request = AcquireRequest::DisposeStock(1);
+        assert_eq!(
+            parse_response("sell all", &request),
+            Ok(AcquireResponse::DisposeStock(
+                1,
+                DisposeStockChoice::SellAll
+            ))
+        );
+        assert_eq!(
+            parse_response("keep", &request),
+            Ok(AcquireResponse::DisposeStock(1, DisposeStockChoice::Keep))
+        );
+        assert_eq!(
+            parse_response("keep all", &request),
+            Ok(AcquireResponse::DisposeStock(
+                1,
+                DisposeStockChoice::KeepAll
+            ))
+        );
+        assert_eq!(
+            parse_response("sell 4", &request),
+            Ok(AcquireResponse::DisposeStock(
+                1,
+                DisposeStockChoice::SellN(4)
+            ))
+        );
+        assert_eq!(
+            parse_response("trade 2", &request),
+            Ok(AcquireResponse::DisposeStock(
+                1,
+                DisposeStockChoice::TradeN(2)
+            ))
+        );
+        assert_eq!(
+            parse_response("split 2 1 4", &request),
+            Ok(AcquireResponse::DisposeStock(
+                1,
+                DisposeStockChoice::Split {
+                    keep: 2,
+                    sell: 1,
+                    trade: 4
+                }
+            ))
+        );
+        assert!(parse_response("split 2 1 4 2", &request).is_err());
+        assert!(parse_response("split 2 1", &request).is_err());
+        assert!(parse_response("sell 4 2", &request).is_err());
+        assert!(parse_response("sell four", &request).is_err());
+        assert!(parse_response("keep 2", &request).is_err());
+    }
+
+    #[test]
+    fn test_parse_chain_depends_on_request() {
+        assert_eq!(
+            parse_response("chain Luxor", &AcquireRequest::ChooseNewChain(0)),
+            Ok(AcquireResponse::NewChain(Hotel::Luxor))
+        );
+        assert_eq!(
+            parse_response("chain Luxor", &AcquireRequest::ChooseMergerSurvivor(0)),
+            Ok(AcquireResponse::MergerSurvivor(Hotel::Luxor))
+        );
+        assert_eq!(
+            parse_response(
+                "chain Luxor",
+                &AcquireRequest::ChooseDefunctChainToResolve(0)
+            ),
+            Ok(AcquireResponse::DefunctChainToResolve(Hotel::Luxor))
+        );
+    }
+}
@@ -1,3 +1,6 @@
 pub const MAX_STOCK_PER_HOTEL: u32 = 25;
 pub const STOCK_TO_BUY_PER_TURN: u32 = 3;
-pub const MAX_NUMBER_OF_PLAYERS: u32 = 6;
+pub const STARTING_CASH: u32 = 6000;
+pub const MIN_NUMBER_OF_PLAYERS: usize = 2;
+pub const MAX_NUMBER_OF_PLAYERS: usize = 6;
+pub const TILES_PER_PLAYER: usize = 6;
@@ -0,0 +1,244 @@
+use std::fmt;
+
+use super::{
+    acquire_request::AcquireRequest, game_board::CellNotPlayableReason, hotel_data::Hotel,
+    tile::Tile,
+};
+
+// why a response was rejected. programs can match on the variant,
+// players are shown the Display form
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AcquireError {
+    GameOver,
+    NotYourTurn,
+    // a player tried to dispose of someone else's stock
+    NotYourStock,
+    // the response does not answer the request the game is waiting on
+    WrongPhase(AcquireRequest),
+    TileNotInHand {
+        player_name: String,
+        tile: Tile,
+    },
+    IllegalTile(CellNotPlayableReason),
+    InvalidHotelChoice(Hotel, HotelChoiceError),
+    InsufficientCash {
+        hotel: Hotel,
+        price: u32,
+    },
+    NoStockAvailable(Hotel),
+    // the survivor does not have enough shares left for the trade
+    NoStockToTrade(Hotel),
+    InvalidShareCount(ShareCountError),
+    // the rules forbid mixing selling and trading
+    SellAndTradeNotAllowed(SellAndTrade),
+    // every buy allowed this turn has been made
+    BuyLimitReached,
+    // the bank cannot give out that many shares
+    TooFewSharesAvailable {
+        hotel: Hotel,
+        available: u32,
+        requested: u32,
+    },
+    // the player cannot sell or return more shares than they hold
+    SharesNotHeld {
+        player_name: String,
+        hotel: Hotel,
+        shares: u32,
+    },
+    NothingToUndo,
+    NoSuchPlayer(usize),
+    AlreadyResigned {
+        player_name: String,
+    },
+    // players may not resign while starting tiles are still being drawn
+    NotStarted,
+    // no player is far enough ahead to concede to
+    NotDecided,
+    // only the players behind the leader may concede
+    LeaderCannotConcede,
+    // something the game should never have let happen, such as a broken stock count
+    Internal(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HotelChoiceError {
+    AlreadyOnBoard,
+    CannotSurvive,
+    NotDefunct,
+    // defunct chains are resolved from the largest to the smallest
+    LargerDefunctFirst,
+    NotFounded,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ShareCountError {
+    AlreadyDisposed,
+    MoreThanHeld,
+    NoneToDispose,
+    NoneToBuy,
+    // shares are traded in multiples of the trade ratio
+    NotAMultipleOf(u32),
+    FewerThan(u32),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SellAndTrade {
+    AtOnce,
+    SellAfterTrading,
+    TradeAfterSelling,
+}
+
+impl fmt::Display for AcquireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AcquireError::GameOver => write!(f, "The game is over"),
+            AcquireError::NotYourTurn => write!(f, "It is not your turn"),
+            AcquireError::NotYourStock => {
+                write!(f, "Players may only dispose of their own stock")
+            }
+            AcquireError::WrongPhase(request) => {
+                let request = match request {
+                    AcquireRequest::PlayStartingTile(_) => "starting tile",
+                    AcquireRequest::PlayTile(_) => "tile",
+                    AcquireRequest::ChooseNewChain(_) => "new chain",
+                    AcquireRequest::ChooseMergerSurvivor(_) => "merger survivor",
+                    AcquireRequest::ChooseDefunctChainToResolve(_) => "defunct chain",
+                    AcquireRequest::DisposeStock(_) => "dispose stock",
+                    AcquireRequest::BuyStock(_) => "buy stock",
+                    AcquireRequest::EndGame(_) => "end game",
+                };
+                write!(f, "Invalid response to {} request", request)
+            }
+            AcquireError::TileNotInHand { player_name, tile } => {
+                write!(f, "{} does not hold tile {}", player_name, tile)
+            }
+            AcquireError::IllegalTile(reason) => write!(f, "{}", reason.as_display_message()),
+            AcquireError::InvalidHotelChoice(hotel, problem) => match problem {
+                HotelChoiceError::AlreadyOnBoard => write!(f, "Hotel is already on the board"),
+                HotelChoiceError::CannotSurvive => write!(f, "That chain cannot be the survivor"),
+                HotelChoiceError::NotDefunct => {
+                    write!(f, "{} is not a defunct chain in this merger", hotel)
+                }
+                HotelChoiceError::LargerDefunctFirst => write!(
+                    f,
+                    "{} cannot be resolved until the larger defunct chains are",
+                    hotel
+                ),
+                HotelChoiceError::NotFounded => write!(
+                    f,
+                    "{} has not been founded, its stock cannot be bought",
+                    hotel
+                ),
+            },
+            AcquireError::InsufficientCash { hotel, price } => {
+                write!(f, "You cannot afford a share of {} at ${}", hotel, price)
+            }
+            AcquireError::NoStockAvailable(hotel) => {
+                write!(f, "There are no shares of {} left to buy", hotel)
+            }
+            AcquireError::NoStockToTrade(hotel) => {
+                write!(f, "Not enough stock available in {} to trade", hotel)
+            }
+            AcquireError::InvalidShareCount(problem) => match problem {
+                ShareCountError::AlreadyDisposed => {
+                    write!(f, "You have already disposed of all your shares")
+                }
+                ShareCountError::MoreThanHeld => {
+                    write!(f, "You cannot dispose of more shares than you have")
+                }
+                ShareCountError::NoneToDispose => write!(f, "You must dispose of at least 1 share"),
+                ShareCountError::NoneToBuy => write!(f, "You must buy at least 1 share"),
+                ShareCountError::NotAMultipleOf(ratio) => {
+                    write!(f, "You can only trade shares in multiples of {}", ratio)
+                }
+                ShareCountError::FewerThan(ratio) => {
+                    write!(f, "You cannot trade with fewer than {} shares", ratio)
+                }
+            },
+            AcquireError::SellAndTradeNotAllowed(problem) => match problem {
+                SellAndTrade::AtOnce => write!(f, "You cannot both sell and trade your shares"),
+                SellAndTrade::SellAfterTrading => {
+                    write!(f, "You cannot sell shares after trading them")
+                }
+                SellAndTrade::TradeAfterSelling => {
+                    write!(f, "You cannot trade shares after selling them")
+                }
+            },
+            AcquireError::BuyLimitReached => write!(f, "You cannot buy more stock this turn"),
+            AcquireError::TooFewSharesAvailable {
+                hotel,
+                available,
+                requested,
+            } => write!(
+                f,
+                "Only {} shares of {} are available, not {}",
+                available, hotel, requested
+            ),
+            AcquireError::SharesNotHeld {
+                player_name,
+                hotel,
+                shares,
+            } => write!(
+                f,
+                "{} does not hold {} shares of {}",
+                player_name, shares, hotel
+            ),
+            AcquireError::NothingToUndo => write!(
+                f,
+                "Only a tile placement or stock purchase this turn can be undone"
+            ),
+            AcquireError::NoSuchPlayer(player) => write!(f, "There is no player {}", player + 1),
+            AcquireError::AlreadyResigned { player_name } => {
+                write!(f, "{} has already resigned", player_name)
+            }
+            AcquireError::NotStarted => write!(
+                f,
+                "Players may only resign once every starting tile is drawn"
+            ),
+            AcquireError::NotDecided => write!(f, "The game is not decided yet"),
+            AcquireError::LeaderCannotConcede => {
+                write!(f, "The leader has no one to concede to")
+            }
+            AcquireError::Internal(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for AcquireError {}
+
+// the game's own bookkeeping reports its failures as strings
+impl From<String> for AcquireError {
+    fn from(message: String) -> Self {
+        AcquireError::Internal(message)
+    }
+}
+
+// callers that only show errors to players can keep working with strings
+impl From<AcquireError> for String {
+    fn from(error: AcquireError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_matches_the_messages_players_see() {
+        assert_eq!(AcquireError::NotYourTurn.to_string(), "It is not your turn");
+        assert_eq!(
+            AcquireError::WrongPhase(AcquireRequest::BuyStock(0)).to_string(),
+            "Invalid response to buy stock request"
+        );
+        assert_eq!(
+            AcquireError::InvalidHotelChoice(Hotel::Tower, HotelChoiceError::NotDefunct)
+                .to_string(),
+            "Tower is not a defunct chain in this merger"
+        );
+        assert_eq!(
+            AcquireError::InvalidShareCount(ShareCountError::NotAMultipleOf(2)).to_string(),
+            "You can only trade shares in multiples of 2"
+        );
+    }
+}
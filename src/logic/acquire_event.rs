@@ -0,0 +1,104 @@
+use super::{hotel_data::Hotel, tile::Tile};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PayoutKind {
+    Majority,
+    Minority,
+    // a tied majority or a sole stockholder shares both bonuses
+    MajorityAndMinority,
+}
+
+// This represents something that happened in the game
+// unlike the messages sent to the callback, these are meant to be read by programs
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AcquireEvent {
+    TilePlaced {
+        player: usize,
+        tile: Tile,
+    },
+    ChainFounded {
+        player: usize,
+        hotel: Hotel,
+    },
+    MergerStarted {
+        survivor: Hotel,
+        defunct: Hotel,
+    },
+    StockBought {
+        player: usize,
+        hotel: Hotel,
+        price: u32,
+    },
+    PayoutAwarded {
+        player: usize,
+        amount: u32,
+        kind: PayoutKind,
+    },
+    PlayerResigned {
+        player: usize,
+    },
+    GameEnded {
+        scores: Vec<u32>,
+    },
+}
+
+impl AcquireEvent {
+    // describes the event for display, players are referred to by their names
+    pub fn describe(&self, player_names: &[String]) -> String {
+        let name = |player: &usize| {
+            player_names
+                .get(*player)
+                .cloned()
+                .unwrap_or_else(|| format!("Player {}", player + 1))
+        };
+
+        match self {
+            AcquireEvent::TilePlaced { player, tile } => {
+                format!("{} placed tile {}", name(player), tile)
+            }
+            AcquireEvent::ChainFounded { player, hotel } => {
+                format!("{} has started a new chain in {}", name(player), hotel)
+            }
+            AcquireEvent::MergerStarted { survivor, defunct } => {
+                format!(
+                    "A merger of {} into {} will now commence",
+                    defunct, survivor
+                )
+            }
+            AcquireEvent::StockBought {
+                player,
+                hotel,
+                price,
+            } => format!(
+                "{} has bought stock in {} for ${}",
+                name(player),
+                hotel,
+                price
+            ),
+            AcquireEvent::PayoutAwarded {
+                player,
+                amount,
+                kind,
+            } => {
+                let kind = match kind {
+                    PayoutKind::Majority => "majority",
+                    PayoutKind::Minority => "minority",
+                    PayoutKind::MajorityAndMinority => "majority and minority",
+                };
+                format!("{} receives a {} payout of ${}", name(player), kind, amount)
+            }
+            AcquireEvent::PlayerResigned { player } => format!("{} has resigned", name(player)),
+            AcquireEvent::GameEnded { scores } => {
+                let scores = scores
+                    .iter()
+                    .enumerate()
+                    .map(|(player, score)| format!("{} ${}", name(&player), score))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("The game has ended! Final scores: {}", scores)
+            }
+        }
+    }
+}
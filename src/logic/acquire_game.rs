@@ -1,27 +1,55 @@
-use core::panic;
-use std::{collections::HashSet, sync::Arc, usize};
+use std::{collections::HashSet, sync::Arc};
+#[cfg(feature = "serde")]
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
-use rand::seq::IteratorRandom;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 
 use crate::logic::game_board::Cell;
 
 use super::{
-    acquire_constants::{MAX_NUMBER_OF_PLAYERS, MAX_STOCK_PER_HOTEL},
+    acquire_constants::{
+        MAX_NUMBER_OF_PLAYERS, MAX_STOCK_PER_HOTEL, MIN_NUMBER_OF_PLAYERS, STARTING_CASH,
+        TILES_PER_PLAYER,
+    },
+    acquire_error::{AcquireError, HotelChoiceError, SellAndTrade, ShareCountError},
+    acquire_event::{AcquireEvent, PayoutKind},
     acquire_game_state::AcquireGameState,
+    acquire_player_strategy::AcquirePlayerStrategy,
     acquire_request::AcquireRequest,
-    acquire_response::{AcquireResponse, BuyStockChoice, DisposeStockChoice},
-    game_board::{self, CellNotPlayableReason, GameBoard},
+    acquire_response::{
+        AcquirePlayerResponse, AcquireResponse, BuyStockChoice, DisposeStockChoice,
+    },
+    game_board::{self, GameBoard},
+    game_phase::GamePhase,
+    game_setup::GameSetup,
+    game_snapshot::GameSnapshot,
     game_states::{
         buy_stock_state::BuyStockState, dispose_stock_state::DisposeStockState,
         game_start_state::GameStartState, merge_state::MergerState,
     },
-    hotel_data::Hotel,
+    game_stats::{GameResults, GameStats, PlayerResult, PlayerStats},
+    hotel_data::{Hotel, CHAIN_LENGTH_BUCKETS},
+    merger_preview::MergerPreview,
     player::Player,
+    rule_set::RuleSet,
     tile::Tile,
+    tile_preview::TilePreview,
+    turn_record::TurnRecord,
 };
 
 pub trait AcquireGameCallback: Send + Sync {
-    fn send_message(&self, message: &str);
+    // human readable description of what is happening in the game
+    fn send_message(&self, _message: &str) {}
+
+    // structured description of what is happening in the game
+    fn on_event(&self, _event: &AcquireEvent) {}
+
+    // called whenever a response moves the game on to a different request
+    fn on_request_changed(&self, _old: &AcquireRequest, _new: &AcquireRequest) {}
 }
 
 pub struct AcquireGame<T: AcquireGameCallback> {
@@ -32,18 +60,94 @@ pub struct AcquireGame<T: AcquireGameCallback> {
     available_stock: [u32; Hotel::count()],
     current_request: AcquireRequest,
     current_state: AcquireGameState,
+    rules: RuleSet,
+    starting_cash: u32,
+    seed: u64,
+    rng: StdRng,
+    action_log: Vec<AcquirePlayerResponse>,
+    // the game as it was before the last undoable response
+    last_state: Option<UndoState>,
+    // what happened so far this turn, summarized into the turn history when it ends
+    turn_events: Vec<AcquireEvent>,
+    turn_history: Vec<TurnRecord>,
+    // each player's stock when the game ended, before it was sold
+    stocks_at_end: Option<Vec<[u32; Hotel::count()]>>,
+    // every applied response is appended here so a crashed game can be replayed
+    #[cfg(feature = "serde")]
+    event_log: Option<PathBuf>,
+}
+
+// everything a single response can change, kept so the response can be undone
+struct UndoState {
+    players: Vec<Player>,
+    board: GameBoard,
+    available_tiles: HashSet<Tile>,
+    available_stock: [u32; Hotel::count()],
+    current_request: AcquireRequest,
+    current_state: AcquireGameState,
+    rng: StdRng,
+    action_log_len: usize,
+    turn_events: Vec<AcquireEvent>,
 }
 
 impl<T: AcquireGameCallback> AcquireGame<T> {
+    // seeds the rng from entropy, the seed is kept so the game can still be reproduced
     pub fn new(number_of_players: usize, message_callback: Arc<T>) -> Self {
-        let mut available_tiles = HashSet::new();
+        Self::with_seed(number_of_players, message_callback, rand::random())
+    }
+
+    pub fn try_new(number_of_players: usize, message_callback: Arc<T>) -> Result<Self, String> {
+        Self::try_with_seed(number_of_players, message_callback, rand::random())
+    }
+
+    // skips drawing starting tiles, hands are dealt straight away and the first
+    // player is on the move, for setting up games in tests. without the starting
+    // tiles the game cannot be rebuilt by replaying its responses
+    pub fn new_with_first_player(
+        number_of_players: usize,
+        message_callback: Arc<T>,
+        first_player: usize,
+    ) -> Self {
+        assert!(
+            first_player < number_of_players,
+            "there is no player {} in a game of {}",
+            first_player + 1,
+            number_of_players
+        );
 
-        // add all tiles to the available tiles
-        for row in 0..game_board::BOARD_ROWS {
-            for col in 0..game_board::BOARD_COLS {
-                available_tiles.insert(Tile::from((row, col)));
+        let mut game = Self::new(number_of_players, message_callback);
+        for _ in 0..TILES_PER_PLAYER {
+            for player in 0..number_of_players {
+                game.give_player_tile(player);
             }
         }
+        game.message_callback.send_message(&format!(
+            "{} will start the game!",
+            game.players[first_player].name
+        ));
+        game.start_turn(first_player);
+        game
+    }
+
+    // panics when the number of players is not supported, use try_with_seed to handle that
+    pub fn with_seed(number_of_players: usize, message_callback: Arc<T>, seed: u64) -> Self {
+        Self::try_with_seed(number_of_players, message_callback, seed)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // all tile draws are made from the seeded rng, so games with the same seed
+    // and the same responses play out identically
+    pub fn try_with_seed(
+        number_of_players: usize,
+        message_callback: Arc<T>,
+        seed: u64,
+    ) -> Result<Self, String> {
+        if !(MIN_NUMBER_OF_PLAYERS..=MAX_NUMBER_OF_PLAYERS).contains(&number_of_players) {
+            return Err(format!(
+                "Acquire is played by {} to {} players, not {}",
+                MIN_NUMBER_OF_PLAYERS, MAX_NUMBER_OF_PLAYERS, number_of_players
+            ));
+        }
 
         let mut players = Vec::new();
         for i in 0..number_of_players {
@@ -51,10 +155,11 @@ impl<T: AcquireGameCallback> AcquireGame<T> {
         }
 
         let board = GameBoard::new();
+        let available_tiles: HashSet<Tile> = board.tiles().collect();
 
         message_callback.send_message("Welcome to Acquire!");
 
-        Self {
+        Ok(Self {
             players,
             message_callback,
             board,
@@ -62,741 +167,4312 @@ impl<T: AcquireGameCallback> AcquireGame<T> {
             available_stock: [MAX_STOCK_PER_HOTEL; Hotel::count()],
             current_request: AcquireRequest::PlayStartingTile(0),
             current_state: AcquireGameState::GameStart(GameStartState::new(number_of_players)),
-        }
+            rules: RuleSet::standard(),
+            starting_cash: STARTING_CASH,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            action_log: Vec::new(),
+            last_state: None,
+            turn_events: Vec::new(),
+            turn_history: Vec::new(),
+            stocks_at_end: None,
+            #[cfg(feature = "serde")]
+            event_log: None,
+        })
     }
 
-    pub fn get_current_stock_availability(&self, hotel: Hotel) -> u32 {
-        self.available_stock[hotel as usize]
+    // house rules may allow more or fewer buys than the standard three
+    pub fn with_buys_per_turn(self, buys_per_turn: u32) -> Result<Self, String> {
+        let rules = RuleSet {
+            buys_per_turn,
+            ..self.rules
+        };
+        self.with_rules(rules)
     }
 
-    pub fn get_current_stock_price(&self, hotel: Hotel) -> u32 {
-        hotel.get_stock_value(self.board.get_hotel_chain_size(hotel))
+    // plays the game by house rules instead of the standard ones
+    pub fn with_rules(mut self, rules: RuleSet) -> Result<Self, String> {
+        rules.validate()?;
+
+        self.board.safe_chain_size = rules.safe_chain_size;
+        self.rules = rules;
+        Ok(self)
     }
 
-    pub fn get_current_request(&self) -> &AcquireRequest {
-        &self.current_request
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
     }
 
-    pub fn handle_player_response(&mut self, response: AcquireResponse) -> Result<(), String> {
-        match self.current_request {
-            AcquireRequest::PlayStartingTile(player) => {
-                if let AcquireResponse::StartingTile = response {
-                    let tile = self.take_random_tile();
-                    self.handle_starting_tile_response(tile, player);
-                    Ok(())
-                } else {
-                    Err("Invalid response to starting tile request".to_string())
-                }
-            }
-            AcquireRequest::PlayTile(player) => {
-                if let AcquireResponse::Tile(tile) = response {
-                    self.handle_tile_response(tile, player)
-                } else {
-                    Err("Invalid response to tile request".to_string())
-                }
-            }
-            AcquireRequest::ChooseNewChain(player) => {
-                if let AcquireResponse::NewChain(hotel) = response {
-                    self.handle_new_chain_response(hotel, player)
-                } else {
-                    Err("Invalid response to new chain request".to_string())
-                }
-            }
-            AcquireRequest::ChooseMergerSurvivor(player) => {
-                if let AcquireResponse::MergerSurvivor(hotel) = response {
-                    self.handle_merger_survivor_response(hotel, player);
-                    Ok(())
-                } else {
-                    Err("Invalid response to merger survivor request".to_string())
-                }
-            }
-            AcquireRequest::ChooseDefunctChainToResolve(player) => {
-                if let AcquireResponse::DefunctChainToResolve(hotel) = response {
-                    self.handle_defunct_chain_response(hotel, player);
-                    Ok(())
-                } else {
-                    Err("Invalid response to defunct chain request".to_string())
-                }
-            }
-            AcquireRequest::DisposeStock => {
-                if let AcquireResponse::DisposeStock(player, choice) = response {
-                    self.handle_dispose_stock_response(choice, player)
-                } else {
-                    Err("Invalid response to dispose stock request".to_string())
-                }
-            }
-            AcquireRequest::BuyStock(_) => {
-                if let AcquireResponse::BuyStock(choice) = response {
-                    self.handle_buy_stock_response(choice)
-                } else {
-                    Err("Invalid response to buy stock request".to_string())
-                }
-            }
-            AcquireRequest::EndGame(player) => {
-                if let AcquireResponse::EndGame(quit) = response {
-                    self.handle_end_game_response(quit, player);
-                    Ok(())
-                } else {
-                    Err("Invalid response to end game request".to_string())
-                }
-            }
+    // variant games can be played on a board other than the standard 9 by 12,
+    // the board must hold a starting tile and a full hand for every player
+    pub fn with_board_size(mut self, rows: usize, cols: usize) -> Result<Self, String> {
+        if self.tiles_drawn() > 0 {
+            return Err("The board size cannot change once tiles are drawn".to_string());
         }
-    }
 
-    // when a player is asked to choose a hotel to start a new chain or resolve a conflict
-    // this function is called to get the hotels that are acceptable for the player to choose
-    pub fn get_acceptable_hotels_for_response(&self) -> Vec<Hotel> {
-        let board = &self.board;
-        match self.current_request {
-            AcquireRequest::ChooseNewChain(_) => self.board.get_inactive_hotels(),
-            AcquireRequest::ChooseMergerSurvivor(_) => self.board.acceptable_conflict_resolutions(),
-            AcquireRequest::ChooseDefunctChainToResolve(_) => {
-                if let AcquireGameState::Merger(merge_state) = &self.current_state {
-                    merge_state.get_largest_defunct_chains(board)
-                } else {
-                    Vec::new()
-                }
-            }
-            _ => Vec::new(),
+        let tiles_needed = self.players.len() * (TILES_PER_PLAYER + 1);
+        if rows * cols < tiles_needed {
+            return Err(format!(
+                "A {} by {} board is too small for {} players, it needs at least {} tiles",
+                rows,
+                cols,
+                self.players.len(),
+                tiles_needed
+            ));
         }
+
+        self.board = GameBoard::with_size(rows, cols);
+        self.board.safe_chain_size = self.rules.safe_chain_size;
+        self.available_tiles = self.board.tiles().collect();
+        Ok(self)
     }
 
-    fn end_turn(&mut self, player: usize) {
-        self.message_callback.send_message(&format!("{}'s turn has ended", self.players[player].name));
-        self.give_player_tile(player);
+    // names the first players in turn order, anyone left over keeps their default name
+    pub fn with_player_names(mut self, names: Vec<String>) -> Result<Self, String> {
+        if names.len() > self.players.len() {
+            return Err(format!(
+                "{} names were given for {} players",
+                names.len(),
+                self.players.len()
+            ));
+        }
 
-        let next_player = (player + 1) % self.players.len();
-        self.message_callback.send_message(&format!(
-            "It is now {}'s turn",
-            self.players[next_player].name
-        ));
+        for (player, name) in self.players.iter_mut().zip(names) {
+            player.name = name;
+        }
+        Ok(self)
+    }
 
-        let play_tile_state = AcquireGameState::PlayTile(next_player);
-        self.current_request = AcquireRequest::PlayTile(next_player);
-        self.current_state = play_tile_state;
+    // variant rules can start everyone with a different bankroll
+    pub fn with_starting_cash(mut self, starting_cash: u32) -> Self {
+        self.players = self
+            .players
+            .iter()
+            .map(|player| Player::with_cash(&player.name, starting_cash))
+            .collect();
+        self.starting_cash = starting_cash;
+        self
     }
 
-    pub fn get_number_of_tiles_left(&self) -> usize {
-        self.available_tiles.len()
+    // how the game was set up, enough to build it again before any response is applied
+    pub fn setup(&self) -> GameSetup {
+        GameSetup {
+            seed: self.seed,
+            number_of_players: self.players.len(),
+            names: self
+                .players
+                .iter()
+                .map(|player| player.name.clone())
+                .collect(),
+            starting_cash: self.starting_cash,
+            rules: self.rules,
+            board_rows: self.board.rows,
+            board_cols: self.board.cols,
+        }
     }
 
-    pub fn take_random_tile(&mut self) -> Tile {
-        let mut rng = rand::thread_rng();
-        let tile = self
-            .available_tiles
-            .iter()
-            .choose(&mut rng)
-            .unwrap()
-            .clone();
-        self.available_tiles.remove(&tile);
-        tile
+    // rebuilds a game by applying logged responses to a fresh game with the same setup
+    pub fn replay(
+        setup: &GameSetup,
+        responses: &[AcquirePlayerResponse],
+        message_callback: Arc<T>,
+    ) -> Result<Self, String> {
+        let mut game = setup.build(message_callback)?;
+        for player_response in responses {
+            game.handle_player_response(player_response.clone())?;
+        }
+        Ok(game)
     }
 
-    fn handle_starting_tile_response(&mut self, tile: Tile, player: usize) {
-        // confirm the tile is not in the available tiles
-        assert!(!self.available_tiles.contains(&tile));
-        // confirm that the tile is not already on the board
-        assert!(self.board.get_cell_state(tile.row, tile.col) == Cell::Empty);
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
 
-        if let AcquireGameState::GameStart(ref mut game_start_state) = self.current_state {
-            self.board.place_initial_tile(tile.row, tile.col);
-            self.message_callback.send_message(&format!(
-                "{} placed starting tile {}",
-                self.players[player].name, tile
-            ));
+    // the callback the game reports its messages and events to
+    pub fn message_callback(&self) -> Arc<T> {
+        self.message_callback.clone()
+    }
 
-            if game_start_state.player_played_tile(player, tile) {
-                self.handle_game_start_complete();
-            } else {
-                self.current_request =
-                    AcquireRequest::PlayStartingTile((player + 1) % self.players.len());
-            }
-        } else {
-            panic!("Cannot handle starting tile response without a game start state");
-        }
+    // every response that was successfully applied, in order
+    pub fn action_log(&self) -> &[AcquirePlayerResponse] {
+        &self.action_log
     }
 
-    fn handle_tile_response(
-        &mut self,
-        tile: Tile,
-        player: usize,
-    ) -> Result<(), String> {
-        // confirm the tile is not available
-        assert!(!self.available_tiles.contains(&tile));
-        // confirm that the tile is not already on the board
-        assert!(self.board.get_cell_state(tile.row, tile.col) == Cell::Empty);
-        // confirm that the player has the tile in their hand
-        assert!(self.players[player].tiles.contains(&tile));
+    pub fn get_current_stock_availability(&self, hotel: Hotel) -> u32 {
+        self.available_stock[hotel as usize]
+    }
 
-        // place tile on board
-        let place_tile_result = self.board.place_tile(tile.row, tile.col);
-        match place_tile_result {
-            game_board::PlaceTileResult::Success => {
-                // the with no chains created or mergers started, the player can buy stock
-                self.message_callback.send_message(&format!(
-                    "{} placed tile {}",
-                    self.players[player].name, tile
-                ));
-                self.start_buy_stock_phase(player);
-            }
+    pub fn get_current_stock_price(&self, hotel: Hotel) -> u32 {
+        hotel.get_stock_value(self.board.get_hotel_chain_size(hotel))
+    }
 
-            game_board::PlaceTileResult::ConflictCreated(conflict_type) => {
-                self.message_callback.send_message(&format!(
-                    "{} placed tile {}",
-                    self.players[player].name, tile
-                ));
-                match conflict_type {
-                    game_board::CellConflictType::NewChain => {
-                        self.message_callback.send_message(&format!(
-                            "A new chain has been started!",
-                        ));
-                        self.message_callback.send_message(&format!(
-                            "{} must choose a new chain to start",
-                            self.players[player].name
-                        ));
-                        self.current_request = AcquireRequest::ChooseNewChain(player);
-                    }
+    // the active chains with stock left that the player can afford, with their prices
+    pub fn buyable_hotels(&self, player: usize) -> Vec<(Hotel, u32)> {
+        self.board
+            .get_active_hotels()
+            .into_iter()
+            .filter(|hotel| self.available_stock[*hotel as usize] > 0)
+            .map(|hotel| (hotel, self.get_current_stock_price(hotel)))
+            .filter(|(_, price)| *price <= self.players[player].cash)
+            .collect()
+    }
 
-                    game_board::CellConflictType::Merge(_) => {
-                        // we need to check if this merger has a tie
-                        // in which case players must choose which chain to keep
+    pub fn player_shares(&self, player: usize, hotel: Hotel) -> u32 {
+        self.players[player].stocks[hotel as usize]
+    }
 
-                        // acceptable conflict resolutions in this case are hotels that could survive the merger
-                        let largest_chains_in_merger = self.board.acceptable_conflict_resolutions();
+    // the players who would collect the majority bonus if the hotel went defunct now
+    pub fn majority_holders(&self, hotel: Hotel) -> Vec<usize> {
+        self.preview_merger_payout(hotel).majority_holders
+    }
 
-                        if largest_chains_in_merger.len() == 1 {
-                            // there is no tie in this case; the largest chain is the one to survive
-                            // and we can start the merge phase
-                            self.start_merge_phase(player, largest_chains_in_merger[0]);
-                        } else {
-                            // there is a tie; players must choose which chain to keep before the merge phase
-                            self.message_callback.send_message(&format!(
-                                "A merge has been triggered, but there is a tie!",
-                            ));
-                            self.message_callback.send_message(&format!(
-                                "{} must choose a chain to keep",
-                                self.players[player].name
-                            ));
-                            self.current_request = AcquireRequest::ChooseMergerSurvivor(player);
-                        }
-                    }
-                }
-            }
-            game_board::PlaceTileResult::CellNotPlayable(not_playable_reason) => {
-                return Err(not_playable_reason.as_display_message());
-            }
-        }
+    // the players who would collect the minority bonus if the hotel went defunct now,
+    // empty when the majority is tied or there is only one shareholder
+    pub fn minority_holders(&self, hotel: Hotel) -> Vec<usize> {
+        self.preview_merger_payout(hotel).minority_holders
+    }
 
-        // remove tile from player's hand
-        self.players[player].tiles.retain(|t| t != &tile);
-        Ok(())
+    // who would be paid what if the hotel went defunct now, nothing is changed
+    pub fn preview_merger_payout(&self, defunct: Hotel) -> MergerPreview {
+        MergerPreview::new(
+            defunct,
+            self.board.get_hotel_chain_size(defunct),
+            &self.shares_by_player(defunct),
+        )
     }
 
-    fn handle_new_chain_response(&mut self, hotel: Hotel, player: usize) -> Result<(), String> {
-        // confirm that the hotel is not already on the board
-        if !self.board.get_inactive_hotels().contains(&hotel) {
-            return Err("Hotel is already on the board".to_string());
-        }
+    // cash plus the market value of every share the player holds
+    pub fn net_worth(&self, player: usize) -> u32 {
+        let stock_value: u32 = Hotel::iter()
+            .map(|hotel| {
+                self.players[player].stocks[hotel as usize] * self.get_current_stock_price(hotel)
+            })
+            .sum();
+        self.players[player].cash + stock_value
+    }
 
-        self.board.resolve_conflict(hotel);
+    // the shares of the hotel held by players rather than the bank
+    pub fn shares_outstanding(&self, hotel: Hotel) -> u32 {
+        MAX_STOCK_PER_HOTEL - self.available_stock[hotel as usize]
+    }
 
-        self.message_callback.send_message(&format!(
-            "{} has started a new chain in {}",
-            self.players[player].name, hotel
-        ));
+    // every share of every hotel is either in the bank or held by exactly one player
+    pub fn verify_stock_conservation(&self) -> bool {
+        Hotel::iter().all(|hotel| {
+            let held: u32 = self
+                .players
+                .iter()
+                .map(|player| player.stocks[hotel as usize])
+                .sum();
+            held + self.available_stock[hotel as usize] == MAX_STOCK_PER_HOTEL
+        })
+    }
 
-        if self.available_stock[hotel as usize] > 0 {
-            self.message_callback.send_message(&format!(
-                "{} will recieve founding bonus stock in {}",
-                self.players[player].name, hotel
-            ));
-            self.give_player_stock(hotel, player, 1);
-        } else {
-            self.message_callback.send_message(&format!(
-                "No {} stock is available. Founding bonus will not be given to {}",
-                self.players[player].name, hotel
-            ));
-        }
+    // the hotel's row of the price chart: for each chain length bucket the
+    // share price and the majority and minority bonuses
+    pub fn price_table(&self, hotel: Hotel) -> Vec<(&'static str, u32, u32, u32)> {
+        CHAIN_LENGTH_BUCKETS
+            .iter()
+            .map(|&(label, chain_length)| {
+                (
+                    label,
+                    hotel.get_stock_value(chain_length),
+                    hotel.get_majority_holder_bonus(chain_length),
+                    hotel.get_minority_holder_bonus(chain_length),
+                )
+            })
+            .collect()
+    }
 
-        self.start_buy_stock_phase(player);
-        Ok(())
+    // the players still in the game with the highest net worth, more than one when
+    // they are tied. a player who resigned keeps their cash but can no longer win
+    pub fn current_leader(&self) -> Vec<usize> {
+        let active_players: Vec<usize> = (0..self.players.len())
+            .filter(|player| !self.players[*player].resigned)
+            .collect();
+        let highest = active_players
+            .iter()
+            .map(|player| self.net_worth(*player))
+            .max()
+            .unwrap_or_default();
+        active_players
+            .into_iter()
+            .filter(|player| self.net_worth(*player) == highest)
+            .collect()
     }
 
-    fn handle_merger_survivor_response(&mut self, hotel: Hotel, player: usize) {
-        self.message_callback.send_message(&format!(
-            "{} has chosen {} to survive the merger",
-            self.players[player].name, hotel
-        ));
+    // the player no one else can catch any more, if there is one. this is an estimate
+    // rather than a search: every other player still in the game is assumed to end
+    // up with their cash, their own shares and all of the bank's shares, with every
+    // share at the highest price on the chart. bonuses still to be paid out are left
+    // out, and so is the leader's stock losing value. the leader's net worth must beat
+    // those best cases added together
+    pub fn is_mathematically_decided(&self) -> Option<usize> {
+        let &(_, largest_chain) = CHAIN_LENGTH_BUCKETS.last()?;
+        let most_worth = |stocks: &[u32; Hotel::count()]| -> u64 {
+            Hotel::iter()
+                .map(|hotel| {
+                    stocks[hotel as usize] as u64 * hotel.get_stock_value(largest_chain) as u64
+                })
+                .sum()
+        };
 
-        self.start_merge_phase(player, hotel);
+        let leader = *self.current_leader().first()?;
+        let best_cases: u64 = (0..self.players.len())
+            .filter(|player| *player != leader && !self.players[*player].resigned)
+            .map(|player| {
+                self.players[player].cash as u64 + most_worth(&self.players[player].stocks)
+            })
+            .sum();
+        let unowned_shares = most_worth(&self.available_stock);
+        (self.net_worth(leader) as u64 > best_cases + unowned_shares).then_some(leader)
     }
 
-    fn handle_defunct_chain_response(&mut self, hotel: Hotel, player: usize) {
+    // the player, still in the game and behind the leader, gives up for everyone
+    // else as well, the game ends as it would have at the end
+    pub fn concede_to_leader(&mut self, player: usize) -> Result<(), AcquireError> {
+        if self.is_game_over() {
+            return Err(AcquireError::GameOver);
+        }
+        if player >= self.players.len() {
+            return Err(AcquireError::NoSuchPlayer(player));
+        }
+        if self.players[player].resigned {
+            return Err(AcquireError::AlreadyResigned {
+                player_name: self.players[player].name.clone(),
+            });
+        }
+        let Some(leader) = self.is_mathematically_decided() else {
+            return Err(AcquireError::NotDecided);
+        };
+        if player == leader {
+            return Err(AcquireError::LeaderCannotConcede);
+        }
+        let old_request = self.current_request;
+
         self.message_callback.send_message(&format!(
-            "{} has chosen to resolve {}",
-            self.players[player].name, hotel
+            "No one can catch {}, the other players concede",
+            self.players[leader].name
         ));
-        self.handle_defunct_hotel(hotel);
+        self.end_game();
+        self.last_state = None;
+        self.log_response(AcquirePlayerResponse::new(AcquireResponse::Concede, player));
+
+        self.notify_request_changed(&old_request);
+        Ok(())
     }
 
-    fn handle_dispose_stock_response(
-        &mut self,
-        choice: DisposeStockChoice,
-        player: usize,
-    ) -> Result<(), String> {
-        if let AcquireGameState::DisposeStock(dispose_stock_state) = &mut self.current_state {
-            let defunct_chain = dispose_stock_state.defunct_chain;
-            let merge_maker = dispose_stock_state.merge_maker;
-            let merge_survivor = dispose_stock_state.surviving_chain;
+    pub fn get_current_request(&self) -> &AcquireRequest {
+        &self.current_request
+    }
 
-            let remaining_shares = dispose_stock_state.get_remaining_shares(player);
+    // a summary of every completed turn, oldest first
+    pub fn turn_history(&self) -> &[TurnRecord] {
+        &self.turn_history
+    }
 
-            if remaining_shares == 0 {
-                return Err("You have already disposed of all your shares".to_string());
-            }
+    pub fn current_phase(&self) -> GamePhase {
+        GamePhase::from(&self.current_state)
+    }
 
-            let shares_to_handle = match choice {
-                DisposeStockChoice::Keep => 1,
-                DisposeStockChoice::Sell => 1,
-                DisposeStockChoice::Trade => 2,
-                DisposeStockChoice::SellAll => remaining_shares,
-                DisposeStockChoice::KeepAll => remaining_shares,
-                // can only trade even number of shares
-                DisposeStockChoice::TradeAll => (remaining_shares / 2) * 2,
-            };
+    // how many more shares the current buyer may take this turn, None outside the buy phase
+    pub fn buys_remaining(&self) -> Option<u32> {
+        match &self.current_state {
+            AcquireGameState::BuyStock(buy_stock_state) => Some(buy_stock_state.buys_remaining),
+            _ => None,
+        }
+    }
 
-            if shares_to_handle > remaining_shares {
-                return Err("You cannot dispose of more shares than you have".to_string());
+    // the player whose tile set off the merger being resolved, None outside of a merger
+    pub fn merge_maker(&self) -> Option<usize> {
+        match &self.current_state {
+            AcquireGameState::Merger(merge_state) => Some(merge_state.merge_maker),
+            AcquireGameState::DisposeStock(dispose_state) => {
+                Some(dispose_state.merger_state.merge_maker)
             }
+            _ => None,
+        }
+    }
+
+    // the tiles in the player's hand they could play right now, in hand order
+    pub fn playable_tiles(&self, player: usize) -> Vec<Tile> {
+        self.players[player]
+            .tiles
+            .iter()
+            .copied()
+            .filter(|tile| self.is_tile_playable(tile))
+            .collect()
+    }
 
-            if shares_to_handle == 0 {
-                return Err("You cannot trade with only 1 share".to_string());
+    // a teaching aid: the first of the player's tiles that grows a chain they lead,
+    // then one that founds a chain, then one left independent. mergers are suggested
+    // only when the player collects a bonus from them, and when nothing better is left
+    pub fn suggest_tile(&self, player: usize) -> Option<Tile> {
+        let score = |tile: &Tile| match self.preview_tile(*tile) {
+            TilePreview::GrowsChain(hotel) if self.majority_holders(hotel).contains(&player) => 5,
+            TilePreview::Merges { defunct, .. }
+                if defunct.iter().any(|hotel| {
+                    self.majority_holders(*hotel).contains(&player)
+                        || self.minority_holders(*hotel).contains(&player)
+                }) =>
+            {
+                4
             }
+            TilePreview::FoundsChain => 3,
+            TilePreview::Independent => 2,
+            TilePreview::GrowsChain(_) => 1,
+            _ => 0,
+        };
 
-            let next_phase;
-            match choice {
-                DisposeStockChoice::Keep | DisposeStockChoice::KeepAll => {
-                    self.message_callback.send_message(&format!(
-                        "{} has chosen to keep {} stock in {}",
-                        self.players[player].name,
-                        shares_to_handle,
-                        dispose_stock_state.defunct_chain
+        // the earliest tile in hand wins a tie
+        self.playable_tiles(player)
+            .into_iter()
+            .rev()
+            .max_by_key(score)
+    }
+
+    // describes what playing the tile would do without touching the board
+    pub fn preview_tile(&self, tile: Tile) -> TilePreview {
+        if let Err(reason) = self.board.is_cell_playable(tile.row, tile.col) {
+            return TilePreview::Illegal(reason);
+        }
+
+        if self.board.would_cell_start_new_chain(tile.row, tile.col) {
+            return TilePreview::FoundsChain;
+        }
+
+        let adjacent_hotels = self.board.get_adjacent_hotels(tile.row, tile.col);
+        match adjacent_hotels.len() {
+            0 => TilePreview::Independent,
+            1 => TilePreview::GrowsChain(adjacent_hotels[0]),
+            _ => {
+                let largest_chain_size = adjacent_hotels
+                    .iter()
+                    .map(|hotel| self.board.get_hotel_chain_size(*hotel))
+                    .max()
+                    .unwrap_or_default();
+                let (survivor_candidates, defunct) =
+                    adjacent_hotels.into_iter().partition(|hotel| {
+                        self.board.get_hotel_chain_size(*hotel) == largest_chain_size
+                    });
+                TilePreview::Merges {
+                    survivor_candidates,
+                    defunct,
+                }
+            }
+        }
+    }
+
+    // a recap of the game so far, biggest payouts come from the turn history
+    pub fn game_stats(&self) -> GameStats {
+        let current_turn = TurnRecord::from_events(0, 0, &self.turn_events);
+        let mut biggest_payouts = vec![0; self.players.len()];
+        for (player, amount) in self
+            .turn_history
+            .iter()
+            .chain(std::iter::once(&current_turn))
+            .flat_map(|record| &record.payouts)
+        {
+            biggest_payouts[*player] = biggest_payouts[*player].max(*amount);
+        }
+
+        let mut shares_held = [0; Hotel::count()];
+        let mut chain_sizes = [0; Hotel::count()];
+        for hotel in Hotel::iter() {
+            shares_held[hotel as usize] = (0..self.players.len())
+                .map(|player| self.final_stocks(player)[hotel as usize])
+                .sum();
+            chain_sizes[hotel as usize] = self.board.get_hotel_chain_size(hotel);
+        }
+
+        GameStats {
+            players: (0..self.players.len())
+                .map(|player| PlayerStats {
+                    cash: self.players[player].cash,
+                    net_worth: self.net_worth(player),
+                    biggest_payout: biggest_payouts[player],
+                })
+                .collect(),
+            shares_held,
+            chain_sizes,
+        }
+    }
+
+    // the final standings, meant to be exported once the game is over
+    pub fn game_results(&self) -> GameResults {
+        GameResults {
+            seed: self.seed,
+            players: (0..self.players.len())
+                .map(|player| PlayerResult {
+                    name: self.players[player].name.clone(),
+                    cash: self.players[player].cash,
+                    net_worth: self.net_worth(player),
+                    stocks: self.final_stocks(player),
+                })
+                .collect(),
+            winners: self.current_leader(),
+        }
+    }
+
+    // plays the game out with a strategy for each player and nothing else, for
+    // simulating many games quickly. a strategy making an illegal move is a bug in
+    // the strategy, so it panics
+    pub fn run_to_completion(
+        &mut self,
+        strategies: &[Box<dyn AcquirePlayerStrategy<T>>],
+    ) -> GameResults {
+        assert_eq!(
+            strategies.len(),
+            self.players.len(),
+            "every player needs a strategy"
+        );
+
+        while !self.is_game_over() {
+            let request = self.current_request;
+            let player = request.get_player();
+            let response = strategies[player].decide(self, &request);
+            if let Err(error) =
+                self.handle_player_response(AcquirePlayerResponse::new(response.clone(), player))
+            {
+                panic!(
+                    "{:?} from player {} was rejected: {}",
+                    response,
+                    player + 1,
+                    error
+                );
+            }
+        }
+
+        self.game_results()
+    }
+
+    // the player's stock, or what they held before it was sold at the end of the game
+    fn final_stocks(&self, player: usize) -> [u32; Hotel::count()] {
+        match &self.stocks_at_end {
+            Some(stocks_at_end) => stocks_at_end[player],
+            None => self.players[player].stocks,
+        }
+    }
+
+    pub fn snapshot(&self) -> GameSnapshot {
+        let mut chain_sizes = [0; Hotel::count()];
+        for hotel in Hotel::iter() {
+            chain_sizes[hotel as usize] = self.board.get_hotel_chain_size(hotel);
+        }
+
+        GameSnapshot {
+            cells: self.board.cells.clone(),
+            players: self.players.clone(),
+            available_stock: self.available_stock,
+            chain_sizes,
+            current_request: self.current_request,
+        }
+    }
+
+    // a playable game at the position a snapshot shows, which may have been written
+    // by hand to reproduce a bug. merger requests cannot be restored because the
+    // snapshot does not say how far the merger has got. every way the snapshot
+    // contradicts itself is listed when it cannot be used
+    pub fn from_snapshot(
+        snapshot: GameSnapshot,
+        message_callback: Arc<T>,
+    ) -> Result<Self, Vec<String>> {
+        let mut violations = Vec::new();
+
+        let number_of_players = snapshot.players.len();
+        let player = snapshot.current_request.get_player();
+        if player >= number_of_players {
+            violations.push(format!(
+                "The request is for player {} but there are only {} players",
+                player + 1,
+                number_of_players
+            ));
+        }
+        let current_state = match snapshot.current_request {
+            AcquireRequest::PlayTile(player) | AcquireRequest::ChooseNewChain(player) => {
+                Some(AcquireGameState::PlayTile(player))
+            }
+            AcquireRequest::BuyStock(player) => Some(AcquireGameState::BuyStock(
+                BuyStockState::new(player, RuleSet::standard().buys_per_turn),
+            )),
+            AcquireRequest::EndGame(player) => Some(AcquireGameState::EndGame(player)),
+            request => {
+                violations.push(format!("{:?} cannot be restored from a snapshot", request));
+                None
+            }
+        };
+
+        for hotel in Hotel::iter() {
+            let held: u32 = snapshot
+                .players
+                .iter()
+                .map(|player| player.stocks[hotel as usize])
+                .sum();
+            let available = snapshot.available_stock[hotel as usize];
+            if held + available != MAX_STOCK_PER_HOTEL {
+                violations.push(format!(
+                    "{} has {} shares held and {} available, not {} in total",
+                    hotel, held, available, MAX_STOCK_PER_HOTEL
+                ));
+            }
+        }
+
+        let board = match GameBoard::from_cells(snapshot.cells) {
+            Ok(board) => Some(board),
+            Err(e) => {
+                violations.push(e);
+                None
+            }
+        };
+
+        let mut available_tiles = HashSet::new();
+        if let Some(board) = &board {
+            for hotel in Hotel::iter() {
+                let size = board.get_hotel_chain_size(hotel);
+                if size != snapshot.chain_sizes[hotel as usize] {
+                    violations.push(format!(
+                        "{} is {} tiles long on the board, not {}",
+                        hotel, size, snapshot.chain_sizes[hotel as usize]
                     ));
-                    next_phase = dispose_stock_state.player_handled_stock(player, 1);
                 }
-                DisposeStockChoice::Sell | DisposeStockChoice::SellAll => {
-                    self.message_callback.send_message(&format!(
-                        "{} has chosen to sell {} stock in {}",
-                        self.players[player].name,
-                        shares_to_handle,
-                        dispose_stock_state.defunct_chain
+            }
+
+            available_tiles = board
+                .tiles()
+                .filter(|tile| board.get_cell_state(tile.row, tile.col) == Cell::Empty)
+                .collect();
+            for player in &snapshot.players {
+                for tile in &player.tiles {
+                    if !board.contains(tile) {
+                        violations.push(format!(
+                            "{} holds {}, which is off the board",
+                            player.name, tile
+                        ));
+                    } else if !available_tiles.remove(tile) {
+                        violations.push(format!(
+                            "{} holds {}, which is already on the board or in another hand",
+                            player.name, tile
+                        ));
+                    }
+                }
+                if player.tiles.len() > TILES_PER_PLAYER {
+                    violations.push(format!(
+                        "{} holds {} tiles, more than {}",
+                        player.name,
+                        player.tiles.len(),
+                        TILES_PER_PLAYER
                     ));
+                }
+            }
+        }
 
-                    next_phase = dispose_stock_state.player_handled_stock(player, shares_to_handle);
-                    self.sell_off_players_stock(defunct_chain, player, shares_to_handle);
+        let mut game =
+            match Self::try_with_seed(number_of_players, message_callback, rand::random()) {
+                Ok(game) => game,
+                Err(e) => {
+                    violations.push(e);
+                    return Err(violations);
                 }
-                DisposeStockChoice::Trade | DisposeStockChoice::TradeAll => {
-                    // check if there is enough stock available to trade
-                    let stock_to_receive = shares_to_handle / 2;
-                    if self.available_stock[merge_survivor as usize] < stock_to_receive {
-                        return Err(format!(
-                            "Not enough stock available in {} to trade",
-                            merge_survivor
-                        ));
+            };
+        let (Some(board), Some(current_state)) = (board, current_state) else {
+            return Err(violations);
+        };
+        if !violations.is_empty() {
+            return Err(violations);
+        }
+
+        game.players = snapshot.players;
+        game.board = board;
+        game.available_tiles = available_tiles;
+        game.available_stock = snapshot.available_stock;
+        game.current_request = snapshot.current_request;
+        game.current_state = current_state;
+        Ok(game)
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.current_state == AcquireGameState::GameOver
+    }
+
+    // the player the game is waiting on, disposal asks one player at a time as well.
+    // empty once the game is over
+    pub fn get_players_to_respond(&self) -> Vec<usize> {
+        if self.is_game_over() {
+            return Vec::new();
+        }
+
+        vec![self.current_request.get_player()]
+    }
+
+    // shares of the defunct chain the player still has to sell, trade or keep
+    pub fn get_remaining_shares_to_dispose(&self, player: usize) -> u32 {
+        match &self.current_state {
+            AcquireGameState::DisposeStock(dispose_stock_state) => {
+                dispose_stock_state.get_remaining_shares(player)
+            }
+            _ => 0,
+        }
+    }
+
+    // only the player a request is for may respond to it
+    pub fn handle_player_response(
+        &mut self,
+        player_response: AcquirePlayerResponse,
+    ) -> Result<(), AcquireError> {
+        if self.is_game_over() {
+            return Err(AcquireError::GameOver);
+        }
+
+        let player = player_response.player;
+        match player_response.response {
+            AcquireResponse::Resign => return self.resign_player(player),
+            AcquireResponse::Concede => return self.concede_to_leader(player),
+            _ => {}
+        }
+        if self.current_request.get_player() != player {
+            return Err(AcquireError::NotYourTurn);
+        }
+        if matches!(player_response.response, AcquireResponse::DisposeStock(owner, _) if owner != player)
+        {
+            return Err(AcquireError::NotYourStock);
+        }
+        let logged_response = player_response.clone();
+        let response = player_response.response;
+
+        let undo_state = self.undo_state();
+        let turns_before = self.turn_history.len();
+        let starts_merger = match &response {
+            AcquireResponse::Tile(tile) => {
+                self.board.get_adjacent_hotels(tile.row, tile.col).len() > 1
+            }
+            _ => false,
+        };
+
+        self.apply_player_response(response)?;
+        self.log_response(logged_response);
+        self.notify_request_changed(&undo_state.current_request);
+
+        // only placing a tile or buying stock can be undone, and only while the
+        // player's turn continues, mergers pay out bonuses and are never undone.
+        // every other player may be skipped, so the same player buying again can
+        // still be a new turn
+        let undoable = match (undo_state.current_request, self.current_request) {
+            (AcquireRequest::PlayTile(before), AcquireRequest::BuyStock(after))
+            | (AcquireRequest::PlayTile(before), AcquireRequest::ChooseNewChain(after))
+            | (AcquireRequest::BuyStock(before), AcquireRequest::BuyStock(after)) => {
+                before == after && !starts_merger && self.turn_history.len() == turns_before
+            }
+            _ => false,
+        };
+        self.last_state = undoable.then_some(undo_state);
+        Ok(())
+    }
+
+    fn log_response(&mut self, response: AcquirePlayerResponse) {
+        #[cfg(feature = "serde")]
+        if let Err(e) = self.append_event(&response) {
+            self.message_callback
+                .send_message(&format!("Could not write to the event log: {}", e));
+        }
+        self.action_log.push(response);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.last_state.is_some()
+    }
+
+    // restores the game to how it was before the last response
+    pub fn undo(&mut self) -> Result<(), AcquireError> {
+        let undo_state = self.last_state.take().ok_or(AcquireError::NothingToUndo)?;
+        let old_request = self.current_request;
+        self.restore_state(undo_state);
+
+        // the undone response is already in the event log, so it is written again
+        #[cfg(feature = "serde")]
+        if let Err(e) = self.write_event_log() {
+            self.message_callback
+                .send_message(&format!("Could not write to the event log: {}", e));
+        }
+
+        self.message_callback.send_message("Undid last action");
+        self.notify_request_changed(&old_request);
+        Ok(())
+    }
+
+    // the player leaves the game: their stock is sold at the current price, their
+    // tiles are taken out of play and their turns are skipped from now on
+    pub fn resign_player(&mut self, player: usize) -> Result<(), AcquireError> {
+        if self.is_game_over() {
+            return Err(AcquireError::GameOver);
+        }
+        if player >= self.players.len() {
+            return Err(AcquireError::NoSuchPlayer(player));
+        }
+        if self.players[player].resigned {
+            return Err(AcquireError::AlreadyResigned {
+                player_name: self.players[player].name.clone(),
+            });
+        }
+        if matches!(self.current_state, AcquireGameState::GameStart(_)) {
+            return Err(AcquireError::NotStarted);
+        }
+        let old_request = self.current_request;
+
+        // a choice made for the player can still be refused after earlier ones were
+        // applied, the game is then put back as it was before the resignation
+        let state_before = self.undo_state();
+        if let Err(e) = self.remove_player(player) {
+            self.restore_state(state_before);
+            return Err(e);
+        }
+        self.record_event(AcquireEvent::PlayerResigned { player });
+        self.last_state = None;
+        self.log_response(AcquirePlayerResponse::new(AcquireResponse::Resign, player));
+
+        if self.players.iter().filter(|p| !p.resigned).count() <= 1 {
+            self.message_callback
+                .send_message("Only one player is left in the game");
+            self.end_game();
+        } else if self.current_request.get_player() == player {
+            self.end_turn(player);
+        }
+
+        self.notify_request_changed(&old_request);
+        Ok(())
+    }
+
+    fn remove_player(&mut self, player: usize) -> Result<(), AcquireError> {
+        // a choice the player still owes is made for them so the turn can carry on
+        while let Some(response) = self.response_for_resigning_player(player) {
+            self.apply_player_response(response)?;
+        }
+
+        self.players[player].resigned = true;
+        self.players[player].tiles.clear();
+        for hotel in Hotel::iter() {
+            let shares = self.players[player].stocks[hotel as usize];
+            if shares > 0 {
+                self.sell_off_players_stock(hotel, player, shares)?;
+            }
+        }
+        // any defunct shares they were still to dispose of have just been sold
+        if let AcquireGameState::DisposeStock(dispose_stock_state) = &mut self.current_state {
+            let remaining_shares = dispose_stock_state.get_remaining_shares(player);
+            dispose_stock_state.player_handled_stock(player, remaining_shares);
+        }
+        Ok(())
+    }
+
+    fn response_for_resigning_player(&self, player: usize) -> Option<AcquireResponse> {
+        if self.current_request.get_player() != player {
+            return None;
+        }
+        let first_hotel = self.get_acceptable_hotels_for_response().first().copied();
+        match self.current_request {
+            AcquireRequest::ChooseNewChain(_) => first_hotel.map(AcquireResponse::NewChain),
+            AcquireRequest::ChooseMergerSurvivor(_) => {
+                first_hotel.map(AcquireResponse::MergerSurvivor)
+            }
+            AcquireRequest::ChooseDefunctChainToResolve(_) => {
+                first_hotel.map(AcquireResponse::DefunctChainToResolve)
+            }
+            AcquireRequest::DisposeStock(_) => Some(AcquireResponse::DisposeStock(
+                player,
+                DisposeStockChoice::SellAll,
+            )),
+            _ => None,
+        }
+    }
+
+    // reports the event and keeps it for the summary of the current turn
+    fn record_event(&mut self, event: AcquireEvent) {
+        self.message_callback.on_event(&event);
+        self.turn_events.push(event);
+    }
+
+    fn notify_request_changed(&self, old_request: &AcquireRequest) {
+        if *old_request != self.current_request {
+            self.message_callback
+                .on_request_changed(old_request, &self.current_request);
+        }
+    }
+
+    fn undo_state(&self) -> UndoState {
+        UndoState {
+            players: self.players.clone(),
+            board: self.board.clone(),
+            available_tiles: self.available_tiles.clone(),
+            available_stock: self.available_stock,
+            current_request: self.current_request,
+            current_state: self.current_state.clone(),
+            rng: self.rng.clone(),
+            action_log_len: self.action_log.len(),
+            turn_events: self.turn_events.clone(),
+        }
+    }
+
+    fn restore_state(&mut self, state: UndoState) {
+        self.players = state.players;
+        self.board = state.board;
+        self.available_tiles = state.available_tiles;
+        self.available_stock = state.available_stock;
+        self.current_request = state.current_request;
+        self.current_state = state.current_state;
+        self.rng = state.rng;
+        self.action_log.truncate(state.action_log_len);
+        self.turn_events = state.turn_events;
+    }
+
+    fn apply_player_response(&mut self, response: AcquireResponse) -> Result<(), AcquireError> {
+        match self.current_request {
+            AcquireRequest::PlayStartingTile(player) => {
+                if let AcquireResponse::StartingTile = response {
+                    let tile = self.take_random_tile();
+                    self.handle_starting_tile_response(tile, player);
+                    Ok(())
+                } else {
+                    Err(AcquireError::WrongPhase(self.current_request))
+                }
+            }
+            AcquireRequest::PlayTile(player) => {
+                if let AcquireResponse::Tile(tile) = response {
+                    self.handle_tile_response(tile, player)
+                } else {
+                    Err(AcquireError::WrongPhase(self.current_request))
+                }
+            }
+            AcquireRequest::ChooseNewChain(player) => {
+                if let AcquireResponse::NewChain(hotel) = response {
+                    self.handle_new_chain_response(hotel, player)
+                } else {
+                    Err(AcquireError::WrongPhase(self.current_request))
+                }
+            }
+            AcquireRequest::ChooseMergerSurvivor(player) => {
+                if let AcquireResponse::MergerSurvivor(hotel) = response {
+                    self.handle_merger_survivor_response(hotel, player)
+                } else {
+                    Err(AcquireError::WrongPhase(self.current_request))
+                }
+            }
+            AcquireRequest::ChooseDefunctChainToResolve(player) => {
+                if let AcquireResponse::DefunctChainToResolve(hotel) = response {
+                    self.handle_defunct_chain_response(hotel, player)
+                } else {
+                    Err(AcquireError::WrongPhase(self.current_request))
+                }
+            }
+            AcquireRequest::DisposeStock(_) => {
+                if let AcquireResponse::DisposeStock(player, choice) = response {
+                    self.handle_dispose_stock_response(choice, player)
+                } else {
+                    Err(AcquireError::WrongPhase(self.current_request))
+                }
+            }
+            AcquireRequest::BuyStock(_) => {
+                if let AcquireResponse::BuyStock(choice) = response {
+                    self.handle_buy_stock_response(choice)
+                } else {
+                    Err(AcquireError::WrongPhase(self.current_request))
+                }
+            }
+            AcquireRequest::EndGame(player) => {
+                if let AcquireResponse::EndGame(quit) = response {
+                    self.handle_end_game_response(quit, player);
+                    Ok(())
+                } else {
+                    Err(AcquireError::WrongPhase(self.current_request))
+                }
+            }
+        }
+    }
+
+    // every response the game would accept to the current request, from the player asked.
+    // a disposal is offered as each named choice and each split, SellN and TradeN
+    // are left out as every amount they cover is also a split
+    pub fn legal_responses(&self) -> Vec<AcquireResponse> {
+        if self.is_game_over() {
+            return Vec::new();
+        }
+
+        match self.current_request {
+            AcquireRequest::PlayStartingTile(_) => vec![AcquireResponse::StartingTile],
+            AcquireRequest::PlayTile(player) => self
+                .playable_tiles(player)
+                .into_iter()
+                .map(AcquireResponse::Tile)
+                .collect(),
+            AcquireRequest::ChooseNewChain(_) => self
+                .get_acceptable_hotels_for_response()
+                .into_iter()
+                .map(AcquireResponse::NewChain)
+                .collect(),
+            AcquireRequest::ChooseMergerSurvivor(_) => self
+                .get_acceptable_hotels_for_response()
+                .into_iter()
+                .map(AcquireResponse::MergerSurvivor)
+                .collect(),
+            AcquireRequest::ChooseDefunctChainToResolve(_) => self
+                .get_acceptable_hotels_for_response()
+                .into_iter()
+                .map(AcquireResponse::DefunctChainToResolve)
+                .collect(),
+            AcquireRequest::DisposeStock(player) => {
+                let remaining_shares = match &self.current_state {
+                    AcquireGameState::DisposeStock(dispose_stock_state) => {
+                        dispose_stock_state.get_remaining_shares(player)
+                    }
+                    _ => 0,
+                };
+
+                let mut choices = vec![
+                    DisposeStockChoice::Keep,
+                    DisposeStockChoice::Sell,
+                    DisposeStockChoice::Trade,
+                    DisposeStockChoice::KeepAll,
+                    DisposeStockChoice::SellAll,
+                    DisposeStockChoice::TradeAll,
+                ];
+                for keep in 0..=remaining_shares {
+                    for sell in 0..=remaining_shares - keep {
+                        for trade in 0..=remaining_shares - keep - sell {
+                            choices.push(DisposeStockChoice::Split { keep, sell, trade });
+                        }
                     }
+                }
+
+                choices
+                    .into_iter()
+                    .filter(|choice| self.check_disposal(*choice, player).is_ok())
+                    .map(|choice| AcquireResponse::DisposeStock(player, choice))
+                    .collect()
+            }
+            AcquireRequest::BuyStock(player) => self
+                .buyable_hotels(player)
+                .into_iter()
+                .map(|(hotel, _)| AcquireResponse::BuyStock(BuyStockChoice::Buy(hotel)))
+                .chain([AcquireResponse::BuyStock(BuyStockChoice::Pass)])
+                .collect(),
+            AcquireRequest::EndGame(_) => {
+                vec![
+                    AcquireResponse::EndGame(true),
+                    AcquireResponse::EndGame(false),
+                ]
+            }
+        }
+    }
+
+    // when a player is asked to choose a hotel to start a new chain or resolve a conflict
+    // this function is called to get the hotels that are acceptable for the player to choose
+    pub fn get_acceptable_hotels_for_response(&self) -> Vec<Hotel> {
+        let board = &self.board;
+        match self.current_request {
+            AcquireRequest::ChooseNewChain(_) => self.board.get_inactive_hotels(),
+            AcquireRequest::ChooseMergerSurvivor(_) => self.board.acceptable_conflict_resolutions(),
+            AcquireRequest::ChooseDefunctChainToResolve(_) => {
+                if let AcquireGameState::Merger(merge_state) = &self.current_state {
+                    merge_state.get_largest_defunct_chains(board)
+                } else {
+                    Vec::new()
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn end_turn(&mut self, player: usize) {
+        let turn_events = std::mem::take(&mut self.turn_events);
+        self.turn_history.push(TurnRecord::from_events(
+            self.turn_history.len() + 1,
+            player,
+            &turn_events,
+        ));
+
+        // once the end conditions are met the player may end the game before passing the turn
+        if self.board.can_game_end() && !self.players[player].resigned {
+            self.message_callback.send_message(&format!(
+                "{} may now end the game",
+                self.players[player].name
+            ));
+            self.current_request = AcquireRequest::EndGame(player);
+            self.current_state = AcquireGameState::EndGame(player);
+            return;
+        }
+
+        self.pass_turn(player);
+    }
+
+    fn pass_turn(&mut self, player: usize) {
+        self.message_callback
+            .send_message(&format!("{}'s turn has ended", self.players[player].name));
+        if self.players[player].tiles.len() < TILES_PER_PLAYER && !self.players[player].resigned {
+            self.give_player_tile(player);
+        }
+
+        if !self.can_any_player_play() {
+            self.message_callback
+                .send_message("No player can place a tile");
+            self.end_game();
+            return;
+        }
+
+        let number_of_players = self.players.len();
+        let next_player = (1..=number_of_players)
+            .map(|offset| (player + offset) % number_of_players)
+            .find(|next_player| !self.players[*next_player].resigned)
+            .unwrap_or(player);
+        self.message_callback.send_message(&format!(
+            "It is now {}'s turn",
+            self.players[next_player].name
+        ));
+
+        self.start_turn(next_player);
+    }
+
+    fn start_turn(&mut self, player: usize) {
+        self.replace_dead_tiles(player);
+
+        // a player without a playable tile skips straight to buying stock
+        if !self.can_player_play(player) {
+            self.message_callback.send_message(&format!(
+                "{} has no playable tiles",
+                self.players[player].name
+            ));
+            self.start_buy_stock_phase(player);
+            return;
+        }
+
+        self.current_request = AcquireRequest::PlayTile(player);
+        self.current_state = AcquireGameState::PlayTile(player);
+    }
+
+    fn is_tile_playable(&self, tile: &Tile) -> bool {
+        self.board.is_cell_playable(tile.row, tile.col).is_ok()
+    }
+
+    // a tile that would merge two safe chains can never be played
+    fn is_tile_dead(&self, tile: &Tile) -> bool {
+        self.board
+            .is_cell_playable(tile.row, tile.col)
+            .is_err_and(|reason| reason.is_permanent())
+    }
+
+    fn can_player_play(&self, player: usize) -> bool {
+        !self.playable_tiles(player).is_empty()
+    }
+
+    // dead tiles can still be swapped for new ones while the bag has tiles left
+    fn can_any_player_play(&self) -> bool {
+        (0..self.players.len()).any(|player| self.can_player_play(player))
+            || (!self.available_tiles.is_empty()
+                && self
+                    .players
+                    .iter()
+                    .any(|player| player.tiles.iter().any(|tile| self.is_tile_dead(tile))))
+    }
+
+    // dead tiles are discarded and replaced with tiles from the bag
+    fn replace_dead_tiles(&mut self, player: usize) {
+        while !self.available_tiles.is_empty() {
+            let dead_tile = match self.players[player]
+                .tiles
+                .iter()
+                .find(|tile| self.is_tile_dead(tile))
+            {
+                Some(tile) => *tile,
+                None => return,
+            };
+
+            self.players[player].tiles.retain(|tile| *tile != dead_tile);
+            self.message_callback.send_message(&format!(
+                "{} discarded the dead tile {}",
+                self.players[player].name, dead_tile
+            ));
+            self.give_player_tile(player);
+        }
+    }
+
+    // final bonuses are paid for every chain on the board and all stock is sold back
+    fn end_game(&mut self) {
+        self.message_callback.send_message("The game is over!");
+
+        self.stocks_at_end = Some(self.players.iter().map(|player| player.stocks).collect());
+
+        for hotel in self.board.get_active_hotels() {
+            self.pay_out_defunct_chain(hotel);
+            for player in 0..self.players.len() {
+                let shares = self.players[player].stocks[hotel as usize];
+                if shares > 0 {
+                    self.sell_off_players_stock(hotel, player, shares)
+                        .expect("a player can always sell every share they hold");
+                }
+            }
+        }
+
+        let scores = self.players.iter().map(|player| player.cash).collect();
+        self.record_event(AcquireEvent::GameEnded { scores });
+        self.current_state = AcquireGameState::GameOver;
+    }
+
+    pub fn get_number_of_tiles_left(&self) -> usize {
+        self.available_tiles.len()
+    }
+
+    // false once the tile has been drawn, an empty cell whose tile is not in the bag
+    // is in someone's hand, or was discarded as dead
+    pub fn is_tile_in_bag(&self, tile: Tile) -> bool {
+        self.available_tiles.contains(&tile)
+    }
+
+    // tiles taken from the bag so far, whether they are on the board or in a hand
+    pub fn tiles_drawn(&self) -> usize {
+        self.board.rows * self.board.cols - self.available_tiles.len()
+    }
+
+    pub fn take_random_tile(&mut self) -> Tile {
+        // the set's iteration order is not stable between games, so sort before drawing
+        let mut tiles: Vec<Tile> = self.available_tiles.iter().copied().collect();
+        tiles.sort();
+
+        let tile = *tiles.choose(&mut self.rng).unwrap();
+        self.available_tiles.remove(&tile);
+        tile
+    }
+
+    fn handle_starting_tile_response(&mut self, tile: Tile, player: usize) {
+        // confirm the tile is not in the available tiles
+        assert!(!self.available_tiles.contains(&tile));
+        // confirm that the tile is not already on the board
+        assert!(self.board.get_cell_state(tile.row, tile.col) == Cell::Empty);
+
+        if let AcquireGameState::GameStart(ref mut game_start_state) = self.current_state {
+            self.board.place_initial_tile(tile.row, tile.col);
+            self.message_callback.send_message(&format!(
+                "{} placed starting tile {}",
+                self.players[player].name, tile
+            ));
+
+            if game_start_state.player_played_tile(player, tile) {
+                self.handle_game_start_complete();
+            } else {
+                self.current_request =
+                    AcquireRequest::PlayStartingTile((player + 1) % self.players.len());
+            }
+        } else {
+            panic!("Cannot handle starting tile response without a game start state");
+        }
+    }
+
+    fn handle_tile_response(&mut self, tile: Tile, player: usize) -> Result<(), AcquireError> {
+        // confirm that the player has the tile in their hand
+        if !self.players[player].tiles.contains(&tile) {
+            return Err(AcquireError::TileNotInHand {
+                player_name: self.players[player].name.clone(),
+                tile,
+            });
+        }
+        // a tile in hand should never still be in the bag or on the board
+        debug_assert!(!self.available_tiles.contains(&tile));
+        debug_assert!(self.board.get_cell_state(tile.row, tile.col) == Cell::Empty);
+
+        // place tile on board
+        let place_tile_result = self.board.place_tile(tile.row, tile.col);
+        match place_tile_result {
+            game_board::PlaceTileResult::Success => {
+                // the with no chains created or mergers started, the player can buy stock
+                self.record_event(AcquireEvent::TilePlaced { player, tile });
+                self.start_buy_stock_phase(player);
+            }
+
+            game_board::PlaceTileResult::ConflictCreated(conflict_type) => {
+                self.record_event(AcquireEvent::TilePlaced { player, tile });
+                match conflict_type {
+                    game_board::CellConflictType::NewChain => {
+                        self.message_callback
+                            .send_message("A new chain has been started!");
+                        self.message_callback.send_message(&format!(
+                            "{} must choose a new chain to start",
+                            self.players[player].name
+                        ));
+                        self.current_request = AcquireRequest::ChooseNewChain(player);
+                    }
+
+                    game_board::CellConflictType::Merge(_) => {
+                        // we need to check if this merger has a tie
+                        // in which case players must choose which chain to keep
+
+                        // acceptable conflict resolutions in this case are hotels that could survive the merger
+                        let largest_chains_in_merger = self.board.acceptable_conflict_resolutions();
+
+                        if largest_chains_in_merger.len() == 1 {
+                            // there is no tie in this case; the largest chain is the one to survive
+                            // and we can start the merge phase
+                            self.start_merge_phase(player, largest_chains_in_merger[0]);
+                        } else {
+                            // there is a tie; players must choose which chain to keep before the merge phase
+                            self.message_callback
+                                .send_message("A merge has been triggered, but there is a tie!");
+                            self.message_callback.send_message(&format!(
+                                "{} must choose a chain to keep",
+                                self.players[player].name
+                            ));
+                            self.current_request = AcquireRequest::ChooseMergerSurvivor(player);
+                        }
+                    }
+                }
+            }
+            game_board::PlaceTileResult::CellNotPlayable(not_playable_reason) => {
+                return Err(AcquireError::IllegalTile(not_playable_reason));
+            }
+        }
+
+        // remove tile from player's hand
+        self.players[player].tiles.retain(|t| t != &tile);
+        Ok(())
+    }
+
+    fn handle_new_chain_response(
+        &mut self,
+        hotel: Hotel,
+        player: usize,
+    ) -> Result<(), AcquireError> {
+        // confirm that the hotel is not already on the board
+        if !self.board.get_inactive_hotels().contains(&hotel) {
+            return Err(AcquireError::InvalidHotelChoice(
+                hotel,
+                HotelChoiceError::AlreadyOnBoard,
+            ));
+        }
+
+        self.board
+            .resolve_conflict(hotel)
+            .map_err(|e| AcquireError::Internal(e.to_string()))?;
+
+        self.record_event(AcquireEvent::ChainFounded { player, hotel });
+
+        // the founding share is only a bonus, the chain is founded even when none are left
+        if self.give_player_stock(hotel, player, 1).is_err() {
+            self.message_callback.send_message(&format!(
+                "No {} stock is available; {} receives no founding share.",
+                hotel, self.players[player].name
+            ));
+        }
+
+        self.start_buy_stock_phase(player);
+        Ok(())
+    }
+
+    fn handle_merger_survivor_response(
+        &mut self,
+        hotel: Hotel,
+        player: usize,
+    ) -> Result<(), AcquireError> {
+        // only one of the largest chains in the merger may survive
+        if !self
+            .board
+            .acceptable_conflict_resolutions()
+            .contains(&hotel)
+        {
+            return Err(AcquireError::InvalidHotelChoice(
+                hotel,
+                HotelChoiceError::CannotSurvive,
+            ));
+        }
+
+        self.message_callback.send_message(&format!(
+            "{} has chosen {} to survive the merger",
+            self.players[player].name, hotel
+        ));
+
+        self.start_merge_phase(player, hotel);
+        Ok(())
+    }
+
+    fn handle_defunct_chain_response(
+        &mut self,
+        hotel: Hotel,
+        player: usize,
+    ) -> Result<(), AcquireError> {
+        if let AcquireGameState::Merger(merge_state) = &self.current_state {
+            if !merge_state.defunct_hotels_remaining.contains(&hotel) {
+                return Err(AcquireError::InvalidHotelChoice(
+                    hotel,
+                    HotelChoiceError::NotDefunct,
+                ));
+            }
+
+            // defunct chains are resolved from the largest to the smallest
+            if !merge_state
+                .get_largest_defunct_chains(&self.board)
+                .contains(&hotel)
+            {
+                return Err(AcquireError::InvalidHotelChoice(
+                    hotel,
+                    HotelChoiceError::LargerDefunctFirst,
+                ));
+            }
+        }
+
+        self.message_callback.send_message(&format!(
+            "{} has chosen to resolve {}",
+            self.players[player].name, hotel
+        ));
+        self.handle_defunct_hotel(hotel);
+        Ok(())
+    }
+
+    // how many shares the choice keeps, sells and trades, or why the player may not make it
+    fn check_disposal(
+        &self,
+        choice: DisposeStockChoice,
+        player: usize,
+    ) -> Result<(u32, u32, u32), AcquireError> {
+        let AcquireGameState::DisposeStock(dispose_stock_state) = &self.current_state else {
+            panic!("Cannot handle dispose stock response without a dispose stock state");
+        };
+        let merge_survivor = dispose_stock_state.merger_state.surviving_hotel;
+
+        let remaining_shares = dispose_stock_state.get_remaining_shares(player);
+        let has_sold = dispose_stock_state.has_sold(player);
+        let has_traded = dispose_stock_state.has_traded(player);
+        let trade_ratio = self.rules.trade_ratio;
+
+        if remaining_shares == 0 {
+            return Err(AcquireError::InvalidShareCount(
+                ShareCountError::AlreadyDisposed,
+            ));
+        }
+
+        // every choice comes down to how many shares are kept, sold and traded
+        let (keep, sell, trade) = match choice {
+            DisposeStockChoice::Keep => (1, 0, 0),
+            DisposeStockChoice::Sell => (0, 1, 0),
+            DisposeStockChoice::Trade => (0, 0, trade_ratio),
+            DisposeStockChoice::SellAll => (0, remaining_shares, 0),
+            DisposeStockChoice::KeepAll => (remaining_shares, 0, 0),
+            // shares are only traded in whole multiples of the trade ratio
+            DisposeStockChoice::TradeAll => (0, 0, (remaining_shares / trade_ratio) * trade_ratio),
+            DisposeStockChoice::SellN(shares) => (0, shares, 0),
+            DisposeStockChoice::TradeN(shares) => (0, 0, shares),
+            DisposeStockChoice::Split { keep, sell, trade } => (keep, sell, trade),
+        };
+        let shares_to_handle = keep + sell + trade;
+
+        if shares_to_handle > remaining_shares {
+            return Err(AcquireError::InvalidShareCount(
+                ShareCountError::MoreThanHeld,
+            ));
+        }
+
+        match choice {
+            DisposeStockChoice::SellN(0)
+            | DisposeStockChoice::TradeN(0)
+            | DisposeStockChoice::Split {
+                keep: 0,
+                sell: 0,
+                trade: 0,
+            } => {
+                return Err(AcquireError::InvalidShareCount(
+                    ShareCountError::NoneToDispose,
+                ));
+            }
+            _ if trade % trade_ratio != 0 => {
+                return Err(AcquireError::InvalidShareCount(
+                    ShareCountError::NotAMultipleOf(trade_ratio),
+                ));
+            }
+            _ if shares_to_handle == 0 => {
+                return Err(AcquireError::InvalidShareCount(ShareCountError::FewerThan(
+                    trade_ratio,
+                )));
+            }
+            _ => {}
+        }
+
+        if !self.rules.allow_sell_and_trade {
+            if sell > 0 && trade > 0 {
+                return Err(AcquireError::SellAndTradeNotAllowed(SellAndTrade::AtOnce));
+            }
+            if sell > 0 && has_traded {
+                return Err(AcquireError::SellAndTradeNotAllowed(
+                    SellAndTrade::SellAfterTrading,
+                ));
+            }
+            if trade > 0 && has_sold {
+                return Err(AcquireError::SellAndTradeNotAllowed(
+                    SellAndTrade::TradeAfterSelling,
+                ));
+            }
+        }
+
+        // check if there is enough stock available to trade before anything
+        // changes hands, so a split is applied in full or not at all
+        let stock_to_receive = trade / trade_ratio;
+        if self.available_stock[merge_survivor as usize] < stock_to_receive {
+            return Err(AcquireError::NoStockToTrade(merge_survivor));
+        }
+
+        Ok((keep, sell, trade))
+    }
+
+    fn handle_dispose_stock_response(
+        &mut self,
+        choice: DisposeStockChoice,
+        player: usize,
+    ) -> Result<(), AcquireError> {
+        let (keep, sell, trade) = self.check_disposal(choice, player)?;
+        let shares_to_handle = keep + sell + trade;
+        let stock_to_receive = trade / self.rules.trade_ratio;
+
+        let AcquireGameState::DisposeStock(dispose_stock_state) = &self.current_state else {
+            panic!("Cannot handle dispose stock response without a dispose stock state");
+        };
+        let defunct_chain = dispose_stock_state.defunct_chain;
+        let merge_state = dispose_stock_state.merger_state.clone();
+        let merge_survivor = merge_state.surviving_hotel;
+
+        // the shares change hands before they are counted as disposed of,
+        // so a failed sale or trade leaves the player to choose again
+        if keep > 0 {
+            self.message_callback.send_message(&format!(
+                "{} has chosen to keep {} stock in {}",
+                self.players[player].name, keep, defunct_chain
+            ));
+        }
+        if sell > 0 {
+            self.message_callback.send_message(&format!(
+                "{} has chosen to sell {} stock in {}",
+                self.players[player].name, sell, defunct_chain
+            ));
+            self.sell_off_players_stock(defunct_chain, player, sell)?;
+        }
+        if trade > 0 {
+            self.take_back_players_stock(defunct_chain, player, trade)?;
+            self.give_player_stock(merge_survivor, player, stock_to_receive)?;
+        }
+
+        let next_disposer = match &mut self.current_state {
+            AcquireGameState::DisposeStock(dispose_stock_state) => {
+                dispose_stock_state.player_handled_stock(player, shares_to_handle);
+                if sell > 0 {
+                    dispose_stock_state.record_sale(player);
+                }
+                if trade > 0 {
+                    dispose_stock_state.record_trade(player);
+                }
+                dispose_stock_state.current_disposer()
+            }
+            _ => None,
+        };
+
+        if let Some(next_disposer) = next_disposer {
+            self.current_request = AcquireRequest::DisposeStock(next_disposer);
+        } else {
+            self.message_callback.send_message(&format!(
+                "All players have disposed of their stock in {}",
+                defunct_chain
+            ));
+            self.finish_stock_disposal(merge_state, defunct_chain);
+        }
+
+        Ok(())
+    }
+
+    fn handle_buy_stock_response(&mut self, choice: BuyStockChoice) -> Result<(), AcquireError> {
+        let buyable_hotels = match &self.current_state {
+            AcquireGameState::BuyStock(buy_stock_state) => {
+                self.buyable_hotels(buy_stock_state.player)
+            }
+            _ => Vec::new(),
+        };
+
+        if let AcquireGameState::BuyStock(buy_stock_state) = &mut self.current_state {
+            let player = buy_stock_state.player;
+            let mut end_phase = false;
+            match choice {
+                BuyStockChoice::Pass => {
+                    self.message_callback.send_message(&format!(
+                        "{} has chosen to pass on buying stock",
+                        self.players[player].name
+                    ));
+
+                    end_phase = true;
+                }
+                BuyStockChoice::Buy(hotel) | BuyStockChoice::BuyN(hotel, _) => {
+                    let requested = match choice {
+                        BuyStockChoice::BuyN(_, shares) => shares,
+                        _ => 1,
+                    };
+                    if requested == 0 {
+                        return Err(AcquireError::InvalidShareCount(ShareCountError::NoneToBuy));
+                    }
+
+                    let Some(&(_, stock_value)) = buyable_hotels.iter().find(|(h, _)| *h == hotel)
+                    else {
+                        // each reason gets its own message so the player knows what to pick instead
+                        if !self.board.get_active_hotels().contains(&hotel) {
+                            return Err(AcquireError::InvalidHotelChoice(
+                                hotel,
+                                HotelChoiceError::NotFounded,
+                            ));
+                        }
+                        if self.available_stock[hotel as usize] == 0 {
+                            return Err(AcquireError::NoStockAvailable(hotel));
+                        }
+                        return Err(AcquireError::InsufficientCash {
+                            hotel,
+                            price: hotel.get_stock_value(self.board.get_hotel_chain_size(hotel)),
+                        });
+                    };
+
+                    // a repeated submission can arrive after the last buy of the turn
+                    if buy_stock_state.buys_remaining == 0 {
+                        return Err(AcquireError::BuyLimitReached);
+                    }
+
+                    // buy as many of the requested shares as the turn, the bank and
+                    // the player's cash allow
+                    let shares = requested
+                        .min(buy_stock_state.buys_remaining)
+                        .min(self.available_stock[hotel as usize])
+                        .min(self.players[player].cash / stock_value);
+
+                    for _ in 0..shares {
+                        // the buy stock state is still borrowed, so the event is recorded by hand
+                        let event = AcquireEvent::StockBought {
+                            player,
+                            hotel,
+                            price: stock_value,
+                        };
+                        self.message_callback.on_event(&event);
+                        self.turn_events.push(event);
+                        end_phase = buy_stock_state.player_has_bought_stock()?;
+                    }
+                    self.give_player_stock(hotel, player, shares)?;
+                    self.players[player].cash -= stock_value * shares;
+
+                    if shares < requested {
+                        self.message_callback.send_message(&format!(
+                            "{} could only buy {} of the {} shares of {} requested",
+                            self.players[player].name, shares, requested, hotel
+                        ));
+                    }
+                }
+            }
+            if end_phase {
+                self.end_turn(player);
+            }
+        } else {
+            panic!("Cannot handle buy stock response without a buy stock state");
+        }
+
+        Ok(())
+    }
+
+    fn handle_end_game_response(&mut self, quit: bool, player: usize) {
+        if quit {
+            self.message_callback
+                .send_message(&format!("{} has ended the game", self.players[player].name));
+            self.end_game();
+        } else {
+            self.pass_turn(player);
+        }
+    }
+
+    /// when a defunct chain is chosen to be resolved
+    /// this function is called to pay out the defunct
+    // and begin the stock disposal process
+    fn pay_out_defunct_chain(&mut self, defunct_hotel: Hotel) {
+        let preview = self.preview_merger_payout(defunct_hotel);
+        let majority_indices = &preview.majority_holders;
+        let minority_indices = &preview.minority_holders;
+
+        if majority_indices.is_empty() {
+            self.message_callback.send_message(&format!(
+                "No one holds stock in {}, no bonuses are paid",
+                defunct_hotel
+            ));
+            return;
+        }
+
+        if majority_indices.len() > 1 {
+            // tied majority: the majority and minority bonuses are combined
+            // and split evenly among the tied players, no minority is paid
+            self.message_callback.send_message(
+                "The following players are tied for the majority in the defunct chain:",
+            );
+            for &index in majority_indices {
+                self.message_callback
+                    .send_message(&self.players[index].name.to_string());
+            }
+            self.award_payouts(&preview, PayoutKind::MajorityAndMinority);
+        } else if minority_indices.is_empty() {
+            // a single shareholder collects both bonuses
+            self.message_callback.send_message(&format!(
+                "{} is the only stockholder in the defunct chain and receives both bonuses",
+                self.players[majority_indices[0]].name,
+            ));
+            self.award_payouts(&preview, PayoutKind::MajorityAndMinority);
+        } else {
+            // a single majority holder collects the majority bonus
+            self.message_callback.send_message(&format!(
+                "{} has the majority in the defunct chain",
+                self.players[majority_indices[0]].name,
+            ));
+            self.award_payouts(&preview, PayoutKind::Majority);
+
+            // the minority bonus is split among everyone tied for second
+            if minority_indices.len() == 1 {
+                self.message_callback.send_message(&format!(
+                    "{} has the minority in the defunct chain",
+                    self.players[minority_indices[0]].name,
+                ));
+            } else {
+                self.message_callback.send_message(
+                    "The following players are tied for the minority in the defunct chain:",
+                );
+                for &index in minority_indices {
+                    self.message_callback
+                        .send_message(&self.players[index].name.to_string());
+                }
+            }
+            self.award_payouts(&preview, PayoutKind::Minority);
+        }
+    }
+
+    fn shares_by_player(&self, hotel: Hotel) -> Vec<u32> {
+        self.players
+            .iter()
+            .map(|player| player.stocks[hotel as usize])
+            .collect()
+    }
+
+    // pays out the previewed bonuses of one kind
+    fn award_payouts(&mut self, preview: &MergerPreview, kind: PayoutKind) {
+        for &(player, amount, _) in preview.payouts.iter().filter(|payout| payout.2 == kind) {
+            self.players[player].cash += amount;
+            self.record_event(AcquireEvent::PayoutAwarded {
+                player,
+                amount,
+                kind,
+            });
+        }
+    }
+
+    fn sell_off_players_stock(
+        &mut self,
+        hotel: Hotel,
+        player: usize,
+        shares: u32,
+    ) -> Result<(), AcquireError> {
+        if self.players[player].stocks[hotel as usize] < shares {
+            return Err(AcquireError::SharesNotHeld {
+                player_name: self.players[player].name.clone(),
+                hotel,
+                shares,
+            });
+        }
+
+        let stock_value = hotel.get_stock_value(self.board.get_hotel_chain_size(hotel));
+        let payout = stock_value * shares;
+        self.players[player].cash += payout;
+        self.available_stock[hotel as usize] += shares;
+        self.players[player].stocks[hotel as usize] -= shares;
+
+        self.message_callback.send_message(&format!(
+            "{} has sold {} stock in {} for ${}",
+            self.players[player].name, shares, hotel, payout
+        ));
+        debug_assert!(self.verify_stock_conservation());
+        Ok(())
+    }
+
+    fn give_player_stock(
+        &mut self,
+        hotel: Hotel,
+        player: usize,
+        shares: u32,
+    ) -> Result<(), AcquireError> {
+        if self.available_stock[hotel as usize] < shares {
+            return Err(AcquireError::TooFewSharesAvailable {
+                hotel,
+                available: self.available_stock[hotel as usize],
+                requested: shares,
+            });
+        }
+
+        self.message_callback.send_message(&format!(
+            "{} has received {} stock in {}",
+            self.players[player].name, shares, hotel
+        ));
+
+        self.players[player].stocks[hotel as usize] += shares;
+        self.available_stock[hotel as usize] -= shares;
+        debug_assert!(self.verify_stock_conservation());
+        Ok(())
+    }
+
+    fn take_back_players_stock(
+        &mut self,
+        hotel: Hotel,
+        player: usize,
+        shares: u32,
+    ) -> Result<(), AcquireError> {
+        if self.players[player].stocks[hotel as usize] < shares {
+            return Err(AcquireError::SharesNotHeld {
+                player_name: self.players[player].name.clone(),
+                hotel,
+                shares,
+            });
+        }
+
+        self.players[player].stocks[hotel as usize] -= shares;
+        self.available_stock[hotel as usize] += shares;
+        debug_assert!(self.verify_stock_conservation());
+        Ok(())
+    }
+
+    fn start_buy_stock_phase(&mut self, player: usize) {
+        // a merge maker who resigned during the merger has nothing left to buy with
+        if self.players[player].resigned {
+            self.end_turn(player);
+            return;
+        }
+
+        let active_hotels = self.board.get_active_hotels();
+        if active_hotels.is_empty() {
+            self.message_callback
+                .send_message("There are no active chains to buy stock in");
+            self.end_turn(player);
+            return;
+        }
+
+        if active_hotels
+            .iter()
+            .all(|hotel| self.available_stock[*hotel as usize] == 0)
+        {
+            self.message_callback
+                .send_message("There is no stock available to buy");
+            self.end_turn(player);
+            return;
+        }
+
+        if self.buyable_hotels(player).is_empty() {
+            self.message_callback.send_message(&format!(
+                "{} can't afford any available stock",
+                self.players[player].name
+            ));
+            self.end_turn(player);
+            return;
+        }
+
+        let buy_stock_state = BuyStockState::new(player, self.rules.buys_per_turn);
+
+        self.current_request = AcquireRequest::BuyStock(player);
+        self.current_state = AcquireGameState::BuyStock(buy_stock_state);
+    }
+
+    fn start_merge_phase(&mut self, merge_maker: usize, merge_survivor: Hotel) {
+        let merge_state = MergerState::new(merge_maker, merge_survivor, &self.board);
+
+        self.message_callback.send_message(&format!(
+            "{} has triggered a merge!",
+            self.players[merge_maker].name
+        ));
+
+        self.message_callback.send_message(&format!(
+            "The following hotel(s) will be merged into {}:",
+            merge_survivor
+        ));
+
+        for hotel in merge_state.defunct_hotels_remaining.iter() {
+            self.message_callback.send_message(&format!("{}", hotel));
+        }
+
+        self.current_state = AcquireGameState::Merger(merge_state);
+        self.determine_next_defunct_hotel();
+    }
+
+    fn determine_next_defunct_hotel(&mut self) {
+        if let AcquireGameState::Merger(merge_state) = &self.current_state {
+            let merge_maker = merge_state.merge_maker;
+
+            match merge_state.get_next_defunct_hotel(&self.board) {
+                Some(defunct_hotel) => {
+                    self.handle_defunct_hotel(defunct_hotel);
+                }
+                None => {
+                    self.message_callback.send_message(&format!(
+                        "There is a tie in the defunct chains! {} must choose which chain to resolve first",
+                        self.players[merge_maker].name
+                    ));
+
+                    self.current_request = AcquireRequest::ChooseDefunctChainToResolve(merge_maker);
+                }
+            }
+        } else {
+            panic!("Cannot determine next defunct hotel without a merge state");
+        }
+    }
+
+    fn continue_merge_phase(&mut self, merge_state: MergerState) {
+        let merge_maker = merge_state.merge_maker;
+        let merge_survivor = merge_state.surviving_hotel;
+
+        if merge_state.defunct_hotels_remaining.is_empty() {
+            self.message_callback
+                .send_message("All defunct chains have been merged");
+
+            self.message_callback
+                .send_message(&format!("The merger into {} is complete!", merge_survivor));
+
+            let _ = self.board.resolve_conflict(merge_survivor);
+
+            self.message_callback.send_message(&format!(
+                "{} can now buy stock",
+                self.players[merge_maker].name
+            ));
+            self.start_buy_stock_phase(merge_maker);
+        } else {
+            self.message_callback
+                .send_message(&format!("Continuing the mergo into {}", merge_survivor));
+
+            self.current_state = AcquireGameState::Merger(merge_state);
+            self.determine_next_defunct_hotel();
+        }
+    }
+
+    fn handle_defunct_hotel(&mut self, defunct_hotel: Hotel) {
+        if let AcquireGameState::Merger(merge_state) = &self.current_state {
+            let mut merge_state = merge_state.clone();
+            merge_state.defunct_hotel_resolved(defunct_hotel);
+            let merge_survivor = merge_state.surviving_hotel;
+
+            self.record_event(AcquireEvent::MergerStarted {
+                survivor: merge_survivor,
+                defunct: defunct_hotel,
+            });
+
+            // pay out the owners of the defunct chain
+            self.pay_out_defunct_chain(defunct_hotel);
+
+            // begin the stock disposal phase
+            self.begin_stock_disposal(merge_state, defunct_hotel);
+        } else {
+            panic!("Cannot handle defunct hotel without a merge state");
+        }
+    }
+
+    fn begin_stock_disposal(&mut self, merge_state: MergerState, defunct_hotel: Hotel) {
+        let mut remaining_shares_per_player = Vec::new();
+        for player in &self.players {
+            remaining_shares_per_player.push(player.stocks[defunct_hotel as usize]);
+        }
+
+        // with no stock to dispose of there is no one to ask
+        if remaining_shares_per_player
+            .iter()
+            .all(|shares| *shares == 0)
+        {
+            self.finish_stock_disposal(merge_state, defunct_hotel);
+            return;
+        }
+
+        let dispose_stock_state =
+            DisposeStockState::new(merge_state, defunct_hotel, remaining_shares_per_player);
+        let first_disposer = dispose_stock_state
+            .current_disposer()
+            .expect("a player with stock should be asked to dispose of it");
+
+        self.current_state = AcquireGameState::DisposeStock(dispose_stock_state);
+        self.current_request = AcquireRequest::DisposeStock(first_disposer);
+
+        self.message_callback.send_message(&format!(
+            "Players must now dispose of their stock in {}",
+            defunct_hotel
+        ));
+    }
+
+    fn finish_stock_disposal(&mut self, merge_state: MergerState, defunct_hotel: Hotel) {
+        // All hotel tiles of the old chain are removed from the board and replaced with the new chain
+        self.board
+            .replace_defunct_hotel_with_surviving_hotel(defunct_hotel, merge_state.surviving_hotel);
+        self.continue_merge_phase(merge_state);
+    }
+
+    fn handle_game_start_complete(&mut self) {
+        if let AcquireGameState::GameStart(game_start_state) = &self.current_state {
+            self.message_callback
+                .send_message("All players have placed their starting tiles.");
+
+            for (player, tile) in game_start_state.sorted_starting_tiles() {
+                self.message_callback
+                    .send_message(&format!("{} drew {}", self.players[player].name, tile));
+            }
+
+            let player_with_winning_tile = game_start_state.player_with_winning_tile;
+            let winning_tile = game_start_state.winning_tile;
+            self.message_callback.send_message(&format!(
+                "{} has the winning tile {} and will start the game!",
+                self.players[player_with_winning_tile].name, winning_tile
+            ));
+
+            // play continues around the table from the winning player
+            let number_of_players = self.players.len();
+            let turn_order = (0..number_of_players)
+                .map(|offset| {
+                    let player = (player_with_winning_tile + offset) % number_of_players;
+                    self.players[player].name.clone()
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.message_callback
+                .send_message(&format!("Turn order: {}", turn_order));
+
+            // give each player 6 tiles
+            for _ in 0..TILES_PER_PLAYER {
+                for player in 0..self.players.len() {
+                    self.give_player_tile(player);
+                }
+            }
+
+            self.start_turn(player_with_winning_tile);
+        } else {
+            panic!("Cannot handle game start complete without a game start state");
+        }
+    }
+
+    fn give_player_tile(&mut self, player: usize) {
+        // once the bag is empty hands are no longer refilled
+        if self.available_tiles.is_empty() {
+            return;
+        }
+
+        let tile = self.take_random_tile();
+        self.players[player].tiles.push(tile);
+    }
+
+    pub fn get_current_chain_size(&self, hotel: Hotel) -> usize {
+        self.board.get_hotel_chain_size(hotel)
+    }
+}
+
+// everything needed to restore a game in progress, the message callback is supplied on load
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct AcquireGameSave {
+    players: Vec<Player>,
+    board: GameBoard,
+    available_tiles: HashSet<Tile>,
+    available_stock: [u32; Hotel::count()],
+    current_request: AcquireRequest,
+    current_state: AcquireGameState,
+    rules: RuleSet,
+    starting_cash: u32,
+    seed: u64,
+    turn_events: Vec<AcquireEvent>,
+    turn_history: Vec<TurnRecord>,
+    stocks_at_end: Option<Vec<[u32; Hotel::count()]>>,
+    action_log: Vec<AcquirePlayerResponse>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: AcquireGameCallback> AcquireGame<T> {
+    // the game's results as pretty printed JSON
+    pub fn results_json(&self) -> String {
+        serde_json::to_string_pretty(&self.game_results())
+            .expect("the results can always be serialized")
+    }
+
+    pub fn save_to_path(&self, path: &Path) -> io::Result<()> {
+        let save = AcquireGameSave {
+            players: self.players.clone(),
+            board: self.board.clone(),
+            available_tiles: self.available_tiles.clone(),
+            available_stock: self.available_stock,
+            current_request: self.current_request,
+            current_state: self.current_state.clone(),
+            rules: self.rules,
+            starting_cash: self.starting_cash,
+            seed: self.seed,
+            turn_events: self.turn_events.clone(),
+            turn_history: self.turn_history.clone(),
+            stocks_at_end: self.stocks_at_end.clone(),
+            action_log: self.action_log.clone(),
+        };
+
+        let json = serde_json::to_string_pretty(&save)?;
+        fs::write(path, json)
+    }
+
+    pub fn load_from_path(path: &Path, message_callback: Arc<T>) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        let save: AcquireGameSave = serde_json::from_str(&json)?;
+
+        // every tile missing from the bag was drawn, so the draws carry on where they left off
+        let bag_size = save.board.rows * save.board.cols;
+        let draws = bag_size
+            .checked_sub(save.available_tiles.len())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "The save has more tiles in the bag than the board has cells",
+                )
+            })?;
+
+        Ok(Self {
+            players: save.players,
+            message_callback,
+            board: save.board,
+            available_tiles: save.available_tiles,
+            available_stock: save.available_stock,
+            current_request: save.current_request,
+            current_state: save.current_state,
+            rules: save.rules,
+            starting_cash: save.starting_cash,
+            seed: save.seed,
+            rng: rng_after_draws(save.seed, bag_size, draws),
+            action_log: save.action_log,
+            last_state: None,
+            turn_events: save.turn_events,
+            turn_history: save.turn_history,
+            stocks_at_end: save.stocks_at_end,
+            event_log: None,
+        })
+    }
+
+    // logs every response applied from now on to path, starting with those applied so far
+    pub fn with_event_log(mut self, path: &Path) -> io::Result<Self> {
+        self.event_log = Some(path.to_path_buf());
+        self.write_event_log()?;
+        Ok(self)
+    }
+
+    // writes the game's setup as a header line then the whole action log, replacing
+    // anything already at the path
+    fn write_event_log(&self) -> io::Result<()> {
+        let Some(path) = &self.event_log else {
+            return Ok(());
+        };
+
+        let mut lines = vec![serde_json::to_string(&self.setup())?];
+        for response in &self.action_log {
+            lines.push(serde_json::to_string(response)?);
+        }
+        fs::write(path, lines.join("\n") + "\n")
+    }
+
+    // adds a single applied response to the end of the event log, if there is one
+    pub fn append_event(&self, response: &AcquirePlayerResponse) -> io::Result<()> {
+        let Some(path) = &self.event_log else {
+            return Ok(());
+        };
+
+        let mut file = OpenOptions::new().append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(response)?)
+    }
+
+    // rebuilds a game from an event log by replaying its responses, the game
+    // carries on logging to the same file
+    pub fn from_event_log(path: &Path, message_callback: Arc<T>) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let log = fs::read_to_string(path)?;
+        let mut lines = log.lines().enumerate().filter(|(_, line)| !line.is_empty());
+        let setup: GameSetup = match lines.next() {
+            Some((_, line)) => serde_json::from_str(line)?,
+            None => return Err(invalid("The event log is empty".to_string())),
+        };
+
+        let mut game = setup.build(message_callback).map_err(invalid)?;
+        for (index, line) in lines {
+            // the game rejects a response logged for the wrong player
+            let entry: AcquirePlayerResponse = serde_json::from_str(line)?;
+            game.handle_player_response(entry)
+                .map_err(|e| invalid(format!("Line {} of the event log: {}", index + 1, e)))?;
+        }
+
+        game.event_log = Some(path.to_path_buf());
+        Ok(game)
+    }
+}
+
+// the rng for the seed as it is after the given number of tiles were drawn from a full bag.
+// each draw chooses from a bag one tile smaller than the last, and choosing from a slice
+// uses the rng the same way whatever the slice holds, so the tiles themselves are not needed
+#[cfg(feature = "serde")]
+fn rng_after_draws(seed: u64, bag_size: usize, draws: usize) -> StdRng {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let bag = vec![(); bag_size];
+    for drawn in 0..draws.min(bag_size) {
+        bag[..bag_size - drawn].choose(&mut rng);
+    }
+    rng
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::logic::game_board::CellNotPlayableReason;
+
+    #[derive(Default)]
+    struct TestCallback {
+        messages: Mutex<Vec<String>>,
+        events: Mutex<Vec<AcquireEvent>>,
+        request_changes: Mutex<Vec<(AcquireRequest, AcquireRequest)>>,
+    }
+
+    impl AcquireGameCallback for TestCallback {
+        fn send_message(&self, message: &str) {
+            self.messages.lock().unwrap().push(message.to_string());
+        }
+
+        fn on_event(&self, event: &AcquireEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+
+        fn on_request_changed(&self, old: &AcquireRequest, new: &AcquireRequest) {
+            self.request_changes.lock().unwrap().push((*old, *new));
+        }
+    }
+
+    // builds a game with a two tile Luxor chain on the board
+    // and gives each player the given number of Luxor shares
+    fn game_with_luxor_holdings(shares: &[u32]) -> AcquireGame<TestCallback> {
+        let mut game = AcquireGame::new(shares.len(), Arc::new(TestCallback::default()));
+        for col in 0..2 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Luxor));
+            game.available_tiles.remove(&Tile::from((0, col)));
+        }
+        for (player, &count) in shares.iter().enumerate() {
+            game.players[player].stocks[Hotel::Luxor as usize] = count;
+        }
+        game
+    }
+
+    // responds as the player whose stock is being disposed of, or otherwise as
+    // the player the current request is for
+    fn respond(
+        game: &mut AcquireGame<TestCallback>,
+        response: AcquireResponse,
+    ) -> Result<(), String> {
+        let player = match &response {
+            AcquireResponse::DisposeStock(player, _) => *player,
+            _ => game.current_request.get_player(),
+        };
+        game.handle_player_response(AcquirePlayerResponse::new(response, player))
+            .map_err(|e| e.to_string())
+    }
+
+    fn cash(game: &AcquireGame<TestCallback>) -> Vec<u32> {
+        game.players.iter().map(|player| player.cash).collect()
+    }
+
+    #[test]
+    fn test_pay_out_single_majority_single_minority() {
+        let mut game = game_with_luxor_holdings(&[5, 3, 0]);
+        game.pay_out_defunct_chain(Hotel::Luxor);
+        assert_eq!(cash(&game), vec![8000, 7000, 6000]);
+    }
+
+    #[test]
+    fn test_preview_merger_payout_matches_the_pay_out() {
+        let mut game = game_with_luxor_holdings(&[5, 3, 3, 0]);
+        let preview = game.preview_merger_payout(Hotel::Luxor);
+        assert_eq!(cash(&game), vec![6000; 4]);
+        assert_eq!(preview.majority_holders, vec![0]);
+        assert_eq!(preview.minority_holders, vec![1, 2]);
+
+        game.pay_out_defunct_chain(Hotel::Luxor);
+        let paid: Vec<_> = (0..4)
+            .map(|player| 6000 + preview.payout_for(player))
+            .collect();
+        assert_eq!(cash(&game), paid);
+    }
+
+    #[test]
+    fn test_pay_out_single_majority_tied_minority() {
+        let mut game = game_with_luxor_holdings(&[5, 3, 3, 3]);
+        game.pay_out_defunct_chain(Hotel::Luxor);
+        // 1000 split three ways is 333.33, rounded up to 400
+        assert_eq!(cash(&game), vec![8000, 6400, 6400, 6400]);
+    }
+
+    #[test]
+    fn test_pay_out_tied_majority() {
+        let mut game = game_with_luxor_holdings(&[4, 4, 2]);
+        game.pay_out_defunct_chain(Hotel::Luxor);
+        // majority and minority are combined and split, the minority holder gets nothing
+        assert_eq!(cash(&game), vec![7500, 7500, 6000]);
+    }
+
+    #[test]
+    fn test_pay_out_max_players() {
+        let mut game = game_with_luxor_holdings(&[1, 5, 2, 2, 2, 2]);
+        assert_eq!(game.players.len(), MAX_NUMBER_OF_PLAYERS);
+        game.pay_out_defunct_chain(Hotel::Luxor);
+        // 1000 split four ways is 250, rounded up to 300
+        assert_eq!(cash(&game), vec![6000, 8000, 6300, 6300, 6300, 6300]);
+    }
+
+    #[test]
+    fn test_pay_out_single_shareholder() {
+        let mut game = game_with_luxor_holdings(&[0, 6, 0]);
+        game.pay_out_defunct_chain(Hotel::Luxor);
+        assert_eq!(cash(&game), vec![6000, 9000, 6000]);
+
+        let messages = game.message_callback.messages.lock().unwrap();
+        assert!(messages
+            .iter()
+            .any(|message| message.contains("Player 2 is the only stockholder")));
+    }
+
+    #[test]
+    fn test_shareholder_queries() {
+        let game = game_with_luxor_holdings(&[5, 3, 3, 0]);
+        assert_eq!(game.player_shares(1, Hotel::Luxor), 3);
+        assert_eq!(game.majority_holders(Hotel::Luxor), vec![0]);
+        assert_eq!(game.minority_holders(Hotel::Luxor), vec![1, 2]);
+
+        let game = game_with_luxor_holdings(&[4, 4, 2]);
+        assert_eq!(game.majority_holders(Hotel::Luxor), vec![0, 1]);
+        assert_eq!(game.minority_holders(Hotel::Luxor), Vec::<usize>::new());
+
+        let game = game_with_luxor_holdings(&[0, 0]);
+        assert_eq!(game.majority_holders(Hotel::Luxor), Vec::<usize>::new());
+        assert_eq!(game.minority_holders(Hotel::Luxor), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_pay_out_no_shareholders() {
+        let mut game = game_with_luxor_holdings(&[0, 0]);
+        game.pay_out_defunct_chain(Hotel::Luxor);
+        assert_eq!(cash(&game), vec![6000, 6000]);
+    }
+
+    // builds a two player game where player 1 has just merged a three tile Luxor
+    // chain with a two tile Tower chain, leaving player 2 to dispose of Tower stock
+    fn game_in_dispose_phase() -> AcquireGame<TestCallback> {
+        game_in_dispose_phase_with_shares(4)
+    }
+
+    // as above with player 1 holding the given number of Tower shares
+    fn game_in_dispose_phase_with_shares(shares: u32) -> AcquireGame<TestCallback> {
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        for col in 0..3 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Luxor));
+            game.available_tiles.remove(&Tile::from((0, col)));
+        }
+        for col in 4..6 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Tower));
+            game.available_tiles.remove(&Tile::from((0, col)));
+        }
+        game.players[1].stocks[Hotel::Tower as usize] = shares;
+        game.available_stock[Hotel::Tower as usize] -= shares;
+
+        let tile = Tile::from((0, 3));
+        game.available_tiles.remove(&tile);
+        game.players[0].tiles.push(tile);
+        game.current_state = AcquireGameState::PlayTile(0);
+        game.current_request = AcquireRequest::PlayTile(0);
+
+        respond(&mut game, AcquireResponse::Tile(tile)).unwrap();
+        game
+    }
+
+    // every hotel is a two tile chain and player 1 holds most of the stock: the bank
+    // has 5 shares of each hotel left, player 2 has $2,000 and 3 Tower shares and
+    // player 3 $1,500 and 2 Imperial shares. at the highest prices that is $47,400,
+    // player 1's shares are worth $40,600 at today's prices
+    fn game_owned_by_a_leader(leader_cash: u32) -> AcquireGame<TestCallback> {
+        let mut game = AcquireGame::new(3, Arc::new(TestCallback::default()));
+        for hotel in Hotel::iter() {
+            for col in 0..2 {
+                let tile = Tile::from((hotel as usize, col));
+                game.board.set_cell(tile.row, tile.col, Cell::Hotel(hotel));
+                game.available_tiles.remove(&tile);
+                for player in &mut game.players {
+                    player.tiles.retain(|held| *held != tile);
+                }
+            }
+            game.available_stock[hotel as usize] = 5;
+            game.players[0].stocks[hotel as usize] = MAX_STOCK_PER_HOTEL - 5;
+        }
+        game.players[0].cash = leader_cash;
+        game.players[1].cash = 2_000;
+        game.players[2].cash = 1_500;
+        for (player, hotel, shares) in [(1, Hotel::Tower, 3), (2, Hotel::Imperial, 2)] {
+            game.players[0].stocks[hotel as usize] -= shares;
+            game.players[player].stocks[hotel as usize] = shares;
+        }
+        assert!(game.verify_stock_conservation());
+        game
+    }
+
+    #[test]
+    fn test_is_mathematically_decided() {
+        let game = game_owned_by_a_leader(6_801);
+        assert_eq!(game.net_worth(0), 47_401);
+        assert_eq!(game.is_mathematically_decided(), Some(0));
+
+        // drawing level with the other players' best cases is not enough
+        let mut game = game_owned_by_a_leader(6_800);
+        assert_eq!(game.is_mathematically_decided(), None);
+        assert_eq!(game.concede_to_leader(1), Err(AcquireError::NotDecided));
+
+        // a new game is anyone's
+        let game = AcquireGame::new(3, Arc::new(TestCallback::default()));
+        assert_eq!(game.is_mathematically_decided(), None);
+    }
+
+    #[test]
+    fn test_resigned_player_is_no_threat() {
+        let mut game = game_owned_by_a_leader(6_800);
+        game.players[1].cash = 100_000;
+        assert_eq!(game.is_mathematically_decided(), None);
+
+        // even as the player with the most cash
+        game.players[1].resigned = true;
+        assert_eq!(game.current_leader(), vec![0]);
+        assert_eq!(game.is_mathematically_decided(), Some(0));
+    }
+
+    #[test]
+    fn test_only_players_behind_the_leader_concede() {
+        let mut game = game_owned_by_a_leader(6_801);
+        let concede = |game: &mut AcquireGame<TestCallback>, player: usize| {
+            game.handle_player_response(AcquirePlayerResponse::new(
+                AcquireResponse::Concede,
+                player,
+            ))
+        };
+
+        assert_eq!(
+            concede(&mut game, 0),
+            Err(AcquireError::LeaderCannotConcede)
+        );
+        assert_eq!(concede(&mut game, 3), Err(AcquireError::NoSuchPlayer(3)));
+        game.players[2].resigned = true;
+        assert!(matches!(
+            concede(&mut game, 2),
+            Err(AcquireError::AlreadyResigned { .. })
+        ));
+        assert!(!game.is_game_over());
+
+        // anyone still behind may concede, whether it is their turn or not
+        assert_eq!(concede(&mut game, 1), Ok(()));
+        assert!(game.is_game_over());
+        assert_eq!(
+            game.action_log().last(),
+            Some(&AcquirePlayerResponse::new(AcquireResponse::Concede, 1))
+        );
+    }
+
+    #[test]
+    fn test_is_tile_in_bag() {
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        let drawn = game.take_random_tile();
+        assert!(!game.is_tile_in_bag(drawn));
+        let left_in_bag = game.board.tiles().filter(|tile| game.is_tile_in_bag(*tile));
+        assert_eq!(left_in_bag.count(), game.get_number_of_tiles_left());
+    }
+
+    #[test]
+    fn test_merge_maker() {
+        let mut game = game_in_dispose_phase();
+        assert_eq!(game.merge_maker(), Some(0));
+
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::KeepAll),
+        )
+        .unwrap();
+        assert_eq!(game.merge_maker(), None);
+    }
+
+    #[test]
+    fn test_give_player_stock_fails_when_not_enough_available() {
+        let mut game = game_in_dispose_phase();
+        game.available_stock[Hotel::Luxor as usize] = 2;
+        game.players[1].stocks[Hotel::Luxor as usize] = MAX_STOCK_PER_HOTEL - 2;
+
+        assert_eq!(
+            game.give_player_stock(Hotel::Luxor, 0, 3),
+            Err(AcquireError::TooFewSharesAvailable {
+                hotel: Hotel::Luxor,
+                available: 2,
+                requested: 3
+            })
+        );
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 0);
+        assert_eq!(game.get_current_stock_availability(Hotel::Luxor), 2);
+
+        assert_eq!(game.give_player_stock(Hotel::Luxor, 0, 2), Ok(()));
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 2);
+        assert_eq!(game.get_current_stock_availability(Hotel::Luxor), 0);
+    }
+
+    #[test]
+    fn test_failed_sale_leaves_the_shares_to_dispose_of() {
+        let mut game = game_in_dispose_phase();
+        // the player lost track of two of the shares the merger expects them to hold
+        game.players[1].stocks[Hotel::Tower as usize] = 2;
+        game.available_stock[Hotel::Tower as usize] += 2;
+
+        let response = AcquirePlayerResponse::new(
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::SellAll),
+            1,
+        );
+        assert_eq!(
+            game.handle_player_response(response),
+            Err(AcquireError::SharesNotHeld {
+                player_name: "Player 2".to_string(),
+                hotel: Hotel::Tower,
+                shares: 4
+            })
+        );
+        assert_eq!(cash(&game), vec![6000, 9000]);
+        if let AcquireGameState::DisposeStock(dispose_stock_state) = &game.current_state {
+            assert_eq!(dispose_stock_state.get_remaining_shares(1), 4);
+        } else {
+            panic!("Expected the game to still be disposing of stock");
+        }
+        assert!(game.verify_stock_conservation());
+    }
+
+    #[test]
+    fn test_merge_pays_out_and_starts_disposal() {
+        let game = game_in_dispose_phase();
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(1));
+        assert_eq!(cash(&game), vec![6000, 9000]);
+
+        if let AcquireGameState::DisposeStock(dispose_stock_state) = &game.current_state {
+            assert_eq!(dispose_stock_state.defunct_chain, Hotel::Tower);
+            assert_eq!(
+                dispose_stock_state.merger_state.surviving_hotel,
+                Hotel::Luxor
+            );
+            assert_eq!(dispose_stock_state.get_remaining_shares(1), 4);
+        } else {
+            panic!("Expected a dispose stock state");
+        }
+    }
+
+    #[test]
+    fn test_sell_and_trade_an_exact_number_of_shares() {
+        let mut game = game_in_dispose_phase();
+        let dispose = |choice| AcquireResponse::DisposeStock(1, choice);
+        assert!(respond(&mut game, dispose(DisposeStockChoice::SellN(0))).is_err());
+        assert!(respond(&mut game, dispose(DisposeStockChoice::SellN(5))).is_err());
+        assert!(respond(&mut game, dispose(DisposeStockChoice::TradeN(3))).is_err());
+        assert_eq!(game.get_remaining_shares_to_dispose(1), 4);
+
+        respond(&mut game, dispose(DisposeStockChoice::SellN(1))).unwrap();
+        assert_eq!(game.get_remaining_shares_to_dispose(1), 3);
+        assert_eq!(game.players[1].cash, 9200);
+
+        respond(&mut game, dispose(DisposeStockChoice::TradeN(2))).unwrap();
+        assert_eq!(game.get_remaining_shares_to_dispose(1), 1);
+        assert_eq!(game.player_shares(1, Hotel::Tower), 1);
+        assert_eq!(game.player_shares(1, Hotel::Luxor), 1);
+    }
+
+    #[test]
+    fn test_house_rules_change_trading() {
+        let rules = RuleSet {
+            trade_ratio: 3,
+            allow_sell_and_trade: false,
+            ..RuleSet::standard()
+        };
+        let mut game = game_in_dispose_phase().with_rules(rules).unwrap();
+        let dispose = |choice| AcquireResponse::DisposeStock(1, choice);
+        assert!(respond(&mut game, dispose(DisposeStockChoice::TradeN(2))).is_err());
+
+        respond(&mut game, dispose(DisposeStockChoice::TradeAll)).unwrap();
+        assert_eq!(game.get_remaining_shares_to_dispose(1), 1);
+        assert_eq!(game.player_shares(1, Hotel::Luxor), 1);
+        assert_eq!(game.player_shares(1, Hotel::Tower), 1);
+
+        assert_eq!(
+            respond(&mut game, dispose(DisposeStockChoice::Sell)),
+            Err("You cannot sell shares after trading them".to_string())
+        );
+        respond(&mut game, dispose(DisposeStockChoice::Keep)).unwrap();
+        assert_eq!(game.player_shares(1, Hotel::Tower), 1);
+        assert_eq!(*game.get_current_request(), AcquireRequest::BuyStock(0));
+    }
+
+    #[test]
+    fn test_house_rules_change_safe_chain_size() {
+        let rules = RuleSet {
+            safe_chain_size: 3,
+            ..RuleSet::standard()
+        };
+        assert!(AcquireGame::new(2, Arc::new(TestCallback::default()))
+            .with_rules(RuleSet {
+                trade_ratio: 0,
+                ..rules
+            })
+            .is_err());
+
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()))
+            .with_board_size(6, 6)
+            .and_then(|game| game.with_rules(rules))
+            .unwrap();
+        for col in 0..3 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Luxor));
+        }
+        assert!(game.board.is_chain_safe(Hotel::Luxor));
+        assert!(game.board.can_game_end());
+        assert_eq!(game.rules(), &rules);
+    }
+
+    #[test]
+    fn test_split_disposal_settles_everything_at_once() {
+        let mut game = game_in_dispose_phase_with_shares(7);
+        let split = |keep, sell, trade| {
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::Split { keep, sell, trade })
+        };
+        assert!(respond(&mut game, split(0, 0, 0)).is_err());
+        assert!(respond(&mut game, split(2, 2, 4)).is_err());
+        assert!(respond(&mut game, split(1, 3, 3)).is_err());
+        assert_eq!(game.get_remaining_shares_to_dispose(1), 7);
+        assert_eq!(game.players[1].cash, 9000);
+
+        respond(&mut game, split(1, 2, 4)).unwrap();
+        assert_eq!(game.players[1].cash, 9400);
+        assert_eq!(game.player_shares(1, Hotel::Tower), 1);
+        assert_eq!(game.player_shares(1, Hotel::Luxor), 2);
+        assert_eq!(game.get_current_stock_availability(Hotel::Tower), 24);
+        assert_eq!(game.get_current_stock_availability(Hotel::Luxor), 23);
+        assert_eq!(*game.get_current_request(), AcquireRequest::BuyStock(0));
+    }
+
+    #[test]
+    fn test_from_snapshot() {
+        let game = game_in_dispose_phase();
+        assert!(
+            AcquireGame::from_snapshot(game.snapshot(), Arc::new(TestCallback::default())).is_err()
+        );
+
+        let mut game = game_with_chains_around_tile(&[(Hotel::Luxor, (0, 1), 3)]);
+        game.players[1].stocks[Hotel::Luxor as usize] = 2;
+        game.available_stock[Hotel::Luxor as usize] -= 2;
+        let mut restored =
+            AcquireGame::from_snapshot(game.snapshot(), Arc::new(TestCallback::default())).unwrap();
+        assert_eq!(restored.snapshot(), game.snapshot());
+        assert_eq!(restored.available_tiles, game.available_tiles);
+        respond(&mut restored, AcquireResponse::Tile(Tile::from((4, 4)))).unwrap();
+        assert_eq!(restored.get_current_chain_size(Hotel::Luxor), 4);
+
+        let mut snapshot = game.snapshot();
+        snapshot.available_stock[Hotel::Tower as usize] -= 1;
+        snapshot.players[1].tiles.push(Tile::from((4, 5)));
+        snapshot.players[1].tiles.push(Tile::from((4, 4)));
+        let violations = AcquireGame::from_snapshot(snapshot, Arc::new(TestCallback::default()))
+            .err()
+            .unwrap();
+        assert_eq!(violations.len(), 3);
+    }
+
+    // places the given chains on the board and gives player 0 the tile at (4, 4)
+    // which touches every one of them, each chain is a straight line of tiles
+    // leading away from that tile
+    fn game_with_chains_around_tile(
+        chains: &[(Hotel, (isize, isize), usize)],
+    ) -> AcquireGame<TestCallback> {
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        for &(hotel, (row_step, col_step), size) in chains {
+            for step in 1..=size as isize {
+                let tile = Tile::from((
+                    (4 + row_step * step) as usize,
+                    (4 + col_step * step) as usize,
+                ));
+                game.board.set_cell(tile.row, tile.col, Cell::Hotel(hotel));
+                game.available_tiles.remove(&tile);
+            }
+        }
+
+        let tile = Tile::from((4, 4));
+        game.available_tiles.remove(&tile);
+        game.players[0].tiles.push(tile);
+        game.current_state = AcquireGameState::PlayTile(0);
+        game.current_request = AcquireRequest::PlayTile(0);
+        game
+    }
+
+    fn merged_defunct_chains(game: &AcquireGame<TestCallback>) -> Vec<Hotel> {
+        game.message_callback
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                AcquireEvent::MergerStarted { defunct, .. } => Some(*defunct),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_turn_history_summarizes_completed_turns() {
+        let mut game = game_with_chains_around_tile(&[(Hotel::Luxor, (0, -1), 2)]);
+        respond(&mut game, AcquireResponse::Tile(Tile::from((4, 4)))).unwrap();
+        respond(
+            &mut game,
+            AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Luxor)),
+        )
+        .unwrap();
+        respond(
+            &mut game,
+            AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Luxor)),
+        )
+        .unwrap();
+        assert!(game.turn_history().is_empty());
+
+        // an undone purchase is left out of the summary
+        game.undo().unwrap();
+        respond(&mut game, AcquireResponse::BuyStock(BuyStockChoice::Pass)).unwrap();
+
+        assert_eq!(
+            game.turn_history(),
+            &[TurnRecord {
+                turn: 1,
+                player: 0,
+                tile: Some(Tile::from((4, 4))),
+                chain_founded: None,
+                mergers: Vec::new(),
+                stock_bought: vec![(Hotel::Luxor, 1)],
+                payouts: Vec::new(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_founding_bonus_share() {
+        let found_chain = |hotel: Hotel, available_stock: u32| {
+            let mut game = game_with_chains_around_tile(&[]);
+            game.board.set_cell(4, 5, Cell::Independent);
+            game.available_stock[hotel as usize] = available_stock;
+            game.players[1].stocks[hotel as usize] = MAX_STOCK_PER_HOTEL - available_stock;
+
+            respond(&mut game, AcquireResponse::Tile(Tile::from((4, 4)))).unwrap();
+            respond(&mut game, AcquireResponse::NewChain(hotel)).unwrap();
+            game
+        };
+
+        let game = found_chain(Hotel::Luxor, MAX_STOCK_PER_HOTEL);
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 1);
+        assert_eq!(
+            game.get_current_stock_availability(Hotel::Luxor),
+            MAX_STOCK_PER_HOTEL - 1
+        );
+
+        let game = found_chain(Hotel::Tower, 0);
+        assert_eq!(game.player_shares(0, Hotel::Tower), 0);
+        assert_eq!(game.get_current_stock_availability(Hotel::Tower), 0);
+        assert!(game.message_callback.messages.lock().unwrap().contains(
+            &"No Tower stock is available; Player 1 receives no founding share.".to_string()
+        ));
+    }
+
+    #[test]
+    fn test_preview_tile() {
+        let mut game = game_with_chains_around_tile(&[
+            (Hotel::Luxor, (0, -1), 4),
+            (Hotel::Tower, (-1, 0), 4),
+            (Hotel::American, (0, 1), 2),
+        ]);
+        let board = game.board.clone();
+
+        assert_eq!(
+            game.preview_tile(Tile::from((4, 4))),
+            TilePreview::Merges {
+                survivor_candidates: vec![Hotel::Tower, Hotel::Luxor],
+                defunct: vec![Hotel::American],
+            }
+        );
+        assert_eq!(
+            game.preview_tile(Tile::from((5, 3))),
+            TilePreview::GrowsChain(Hotel::Luxor)
+        );
+        assert_eq!(
+            game.preview_tile(Tile::from((8, 11))),
+            TilePreview::Independent
+        );
+        assert_eq!(
+            game.preview_tile(Tile::from((4, 3))),
+            TilePreview::Illegal(CellNotPlayableReason::CellIsNotEmpty)
+        );
+        // previewing never changes the board
+        assert_eq!(game.board, board);
+
+        game.board.set_cell(8, 10, Cell::Independent);
+        assert_eq!(
+            game.preview_tile(Tile::from((8, 11))),
+            TilePreview::FoundsChain
+        );
+    }
+
+    #[test]
+    fn test_three_way_merge_resolves_defuncts_in_size_order() {
+        let mut game = game_with_chains_around_tile(&[
+            (Hotel::Luxor, (0, -1), 4),
+            (Hotel::Tower, (-1, 0), 4),
+            (Hotel::American, (1, 0), 3),
+        ]);
+        // a fifth Luxor tile makes it the unique largest chain
+        game.board.set_cell(3, 0, Cell::Hotel(Hotel::Luxor));
+        for hotel in [Hotel::Tower, Hotel::American] {
+            game.players[1].stocks[hotel as usize] = 2;
+            game.available_stock[hotel as usize] -= 2;
+        }
+
+        respond(&mut game, AcquireResponse::Tile(Tile::from((4, 4)))).unwrap();
+        assert_eq!(merged_defunct_chains(&game), vec![Hotel::Tower]);
+
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::SellAll),
+        )
+        .unwrap();
+        assert_eq!(
+            merged_defunct_chains(&game),
+            vec![Hotel::Tower, Hotel::American]
+        );
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(1));
+
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::SellAll),
+        )
+        .unwrap();
+        assert_eq!(
+            merged_defunct_chains(&game),
+            vec![Hotel::Tower, Hotel::American]
+        );
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+        assert_eq!(game.board.get_hotel_chain_size(Hotel::Luxor), 13);
+        assert_eq!(game.board.get_conflict_on_board(), None);
+    }
+
+    #[test]
+    fn test_illegal_merger_survivor_is_rejected() {
+        let mut game = game_with_chains_around_tile(&[
+            (Hotel::Luxor, (0, -1), 4),
+            (Hotel::Tower, (-1, 0), 4),
+            (Hotel::American, (1, 0), 3),
+        ]);
+        respond(&mut game, AcquireResponse::Tile(Tile::from((4, 4)))).unwrap();
+        assert_eq!(
+            game.current_request,
+            AcquireRequest::ChooseMergerSurvivor(0)
+        );
+
+        let board = game.board.clone();
+        for hotel in [Hotel::American, Hotel::Festival] {
+            assert_eq!(
+                respond(&mut game, AcquireResponse::MergerSurvivor(hotel)),
+                Err("That chain cannot be the survivor".to_string())
+            );
+        }
+        assert_eq!(game.board, board);
+        assert_eq!(
+            game.current_request,
+            AcquireRequest::ChooseMergerSurvivor(0)
+        );
+    }
+
+    #[test]
+    fn test_smaller_defunct_chain_cannot_be_resolved_first() {
+        let mut game = game_with_chains_around_tile(&[
+            (Hotel::Luxor, (0, -1), 4),
+            (Hotel::Tower, (-1, 0), 3),
+            (Hotel::American, (1, 0), 3),
+            (Hotel::Festival, (0, 1), 2),
+        ]);
+        respond(&mut game, AcquireResponse::Tile(Tile::from((4, 4)))).unwrap();
+        assert_eq!(
+            game.current_request,
+            AcquireRequest::ChooseDefunctChainToResolve(0)
+        );
+
+        assert_eq!(
+            respond(
+                &mut game,
+                AcquireResponse::DefunctChainToResolve(Hotel::Festival)
+            ),
+            Err("Festival cannot be resolved until the larger defunct chains are".to_string())
+        );
+        assert_eq!(
+            respond(
+                &mut game,
+                AcquireResponse::DefunctChainToResolve(Hotel::Imperial)
+            ),
+            Err("Imperial is not a defunct chain in this merger".to_string())
+        );
+        assert_eq!(merged_defunct_chains(&game), Vec::new());
+
+        respond(
+            &mut game,
+            AcquireResponse::DefunctChainToResolve(Hotel::American),
+        )
+        .unwrap();
+        assert_eq!(
+            merged_defunct_chains(&game),
+            vec![Hotel::American, Hotel::Tower, Hotel::Festival]
+        );
+    }
+
+    #[test]
+    fn test_four_way_merge_with_ties() {
+        let mut game = game_with_chains_around_tile(&[
+            (Hotel::Luxor, (0, -1), 4),
+            (Hotel::Tower, (-1, 0), 4),
+            (Hotel::American, (1, 0), 3),
+            (Hotel::Festival, (0, 1), 3),
+        ]);
+        game.players[1].stocks[Hotel::Festival as usize] = 3;
+        game.available_stock[Hotel::Festival as usize] -= 3;
+
+        respond(&mut game, AcquireResponse::Tile(Tile::from((4, 4)))).unwrap();
+        assert_eq!(
+            game.current_request,
+            AcquireRequest::ChooseMergerSurvivor(0)
+        );
+        assert_eq!(
+            game.get_acceptable_hotels_for_response(),
+            vec![Hotel::Tower, Hotel::Luxor]
+        );
+
+        // Luxor is the largest defunct chain, then American and Festival are tied
+        respond(&mut game, AcquireResponse::MergerSurvivor(Hotel::Tower)).unwrap();
+        assert_eq!(merged_defunct_chains(&game), vec![Hotel::Luxor]);
+        assert_eq!(
+            game.current_request,
+            AcquireRequest::ChooseDefunctChainToResolve(0)
+        );
+        assert_eq!(
+            game.get_acceptable_hotels_for_response(),
+            vec![Hotel::American, Hotel::Festival]
+        );
+        assert!(respond(
+            &mut game,
+            AcquireResponse::DefunctChainToResolve(Hotel::Luxor)
+        )
+        .is_err());
+
+        respond(
+            &mut game,
+            AcquireResponse::DefunctChainToResolve(Hotel::Festival),
+        )
+        .unwrap();
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(1));
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::KeepAll),
+        )
+        .unwrap();
+
+        assert_eq!(
+            merged_defunct_chains(&game),
+            vec![Hotel::Luxor, Hotel::Festival, Hotel::American]
+        );
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+        assert_eq!(game.board.get_active_hotels(), vec![Hotel::Tower]);
+        assert_eq!(game.board.get_hotel_chain_size(Hotel::Tower), 15);
+        assert_eq!(game.board.get_conflict_on_board(), None);
+    }
+
+    #[test]
+    fn test_merge_emits_events() {
+        let game = game_in_dispose_phase();
+        let events = game.message_callback.events.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![
+                AcquireEvent::TilePlaced {
+                    player: 0,
+                    tile: Tile::from((0, 3)),
+                },
+                AcquireEvent::MergerStarted {
+                    survivor: Hotel::Luxor,
+                    defunct: Hotel::Tower,
+                },
+                AcquireEvent::PayoutAwarded {
+                    player: 1,
+                    amount: 3000,
+                    kind: PayoutKind::MajorityAndMinority,
+                },
+            ]
+        );
+    }
+
+    // picks a random response that the game will accept for its current request
+    fn random_response(game: &AcquireGame<TestCallback>, rng: &mut StdRng) -> AcquireResponse {
+        match game.current_request {
+            AcquireRequest::PlayStartingTile(_) => AcquireResponse::StartingTile,
+            AcquireRequest::PlayTile(player) => {
+                let playable_tiles: Vec<Tile> = game.players[player]
+                    .tiles
+                    .iter()
+                    .copied()
+                    .filter(|tile| game.board.is_cell_playable(tile.row, tile.col).is_ok())
+                    .collect();
+                AcquireResponse::Tile(*playable_tiles.choose(rng).unwrap())
+            }
+            AcquireRequest::ChooseNewChain(_) => AcquireResponse::NewChain(
+                *game
+                    .get_acceptable_hotels_for_response()
+                    .choose(rng)
+                    .unwrap(),
+            ),
+            AcquireRequest::ChooseMergerSurvivor(_) => AcquireResponse::MergerSurvivor(
+                *game
+                    .get_acceptable_hotels_for_response()
+                    .choose(rng)
+                    .unwrap(),
+            ),
+            AcquireRequest::ChooseDefunctChainToResolve(_) => {
+                AcquireResponse::DefunctChainToResolve(
+                    *game
+                        .get_acceptable_hotels_for_response()
+                        .choose(rng)
+                        .unwrap(),
+                )
+            }
+            AcquireRequest::DisposeStock(player) => {
+                AcquireResponse::DisposeStock(player, DisposeStockChoice::SellAll)
+            }
+            AcquireRequest::BuyStock(player) => {
+                let affordable_hotels: Vec<Hotel> = game
+                    .board
+                    .get_active_hotels()
+                    .into_iter()
+                    .filter(|hotel| {
+                        game.available_stock[*hotel as usize] > 0
+                            && game.get_current_stock_price(*hotel) <= game.players[player].cash
+                    })
+                    .collect();
+                match affordable_hotels.choose(rng) {
+                    Some(hotel) => AcquireResponse::BuyStock(BuyStockChoice::Buy(*hotel)),
+                    None => AcquireResponse::BuyStock(BuyStockChoice::Pass),
+                }
+            }
+            AcquireRequest::EndGame(_) => AcquireResponse::EndGame(true),
+        }
+    }
+
+    #[test]
+    fn test_replay_action_log() {
+        let mut game = AcquireGame::with_seed(3, Arc::new(TestCallback::default()), 7);
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..10 {
+            let response = random_response(&game, &mut rng);
+            respond(&mut game, response).unwrap();
+        }
+        assert_eq!(game.action_log().len(), 10);
+
+        let replayed = AcquireGame::replay(
+            &game.setup(),
+            game.action_log(),
+            Arc::new(TestCallback::default()),
+        )
+        .unwrap();
+
+        assert_eq!(replayed.board, game.board);
+        assert_eq!(replayed.players, game.players);
+        assert_eq!(replayed.available_tiles, game.available_tiles);
+        assert_eq!(replayed.current_request, game.current_request);
+        assert_eq!(replayed.action_log(), game.action_log());
+    }
+
+    #[test]
+    fn test_same_seed_draws_same_starting_tiles() {
+        let mut first = AcquireGame::with_seed(4, Arc::new(TestCallback::default()), 42);
+        let mut second = AcquireGame::with_seed(4, Arc::new(TestCallback::default()), 42);
+        for _ in 0..4 {
+            respond(&mut first, AcquireResponse::StartingTile).unwrap();
+            respond(&mut second, AcquireResponse::StartingTile).unwrap();
+        }
+
+        assert_eq!(first.board, second.board);
+        assert_eq!(first.current_request, second.current_request);
+        for (first_player, second_player) in first.players.iter().zip(second.players.iter()) {
+            assert_eq!(first_player.tiles, second_player.tiles);
+        }
+    }
+
+    #[test]
+    fn test_unseeded_game_is_reproduced_from_its_seed() {
+        let mut unseeded = AcquireGame::new(3, Arc::new(TestCallback::default()));
+        let mut seeded =
+            AcquireGame::with_seed(3, Arc::new(TestCallback::default()), unseeded.seed());
+        for _ in 0..3 {
+            respond(&mut unseeded, AcquireResponse::StartingTile).unwrap();
+            respond(&mut seeded, AcquireResponse::StartingTile).unwrap();
+        }
+        assert_eq!(unseeded.snapshot(), seeded.snapshot());
+
+        // the draws come from the game's own rng, not the thread's
+        let tiles: Vec<Tile> = (0..5).map(|_| seeded.take_random_tile()).collect();
+        let mut again = AcquireGame::with_seed(3, Arc::new(TestCallback::default()), seeded.seed());
+        for _ in 0..3 {
+            respond(&mut again, AcquireResponse::StartingTile).unwrap();
+        }
+        assert_eq!(
+            (0..5).map(|_| again.take_random_tile()).collect::<Vec<_>>(),
+            tiles
+        );
+    }
+
+    #[test]
+    fn test_playing_tile_not_in_hand_is_rejected() {
+        let mut game = AcquireGame::with_seed(2, Arc::new(TestCallback::default()), 3);
+        for _ in 0..2 {
+            respond(&mut game, AcquireResponse::StartingTile).unwrap();
+        }
+        let AcquireRequest::PlayTile(player) = game.current_request else {
+            panic!("expected a tile request");
+        };
+        let tile = *game.available_tiles.iter().next().unwrap();
+
+        assert!(respond(&mut game, AcquireResponse::Tile(tile)).is_err());
+        assert_eq!(game.current_request, AcquireRequest::PlayTile(player));
+        assert!(game.action_log().len() == 2);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let game = game_in_dispose_phase();
+        let snapshot = game.snapshot();
+
+        assert_eq!(snapshot.cells, game.board.cells);
+        assert_eq!(snapshot.players, game.players);
+        assert_eq!(snapshot.current_request, AcquireRequest::DisposeStock(1));
+        assert_eq!(snapshot.chain_sizes[Hotel::Luxor as usize], 3);
+        assert_eq!(snapshot.chain_sizes[Hotel::Tower as usize], 2);
+        assert_eq!(snapshot.chain_sizes[Hotel::American as usize], 0);
+        assert_eq!(
+            snapshot.available_stock[Hotel::Tower as usize],
+            game.get_current_stock_availability(Hotel::Tower)
+        );
+    }
+
+    #[test]
+    fn test_game_stats() {
+        let game = game_in_dispose_phase();
+        let stats = game.game_stats();
+        assert_eq!(stats.players[0].biggest_payout, 0);
+        // the only Tower stockholder is paid both bonuses at once
+        assert_eq!(stats.players[1].biggest_payout, 3000);
+        assert_eq!(stats.players[1].net_worth, game.net_worth(1));
+        assert_eq!(stats.shares_held[Hotel::Tower as usize], 4);
+        assert_eq!(stats.chain_sizes[Hotel::Luxor as usize], 3);
+
+        // once the game is over every share has been sold, but the recap keeps them
+        let mut game = game_in_dispose_phase();
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::KeepAll),
+        )
+        .unwrap();
+        game.end_game();
+        let stats = game.game_stats();
+        assert_eq!(stats.shares_held[Hotel::Tower as usize], 4);
+        for (player, player_stats) in stats.players.iter().enumerate() {
+            assert_eq!(player_stats.cash, game.players[player].cash);
+            assert_eq!(player_stats.net_worth, player_stats.cash);
+        }
+        assert_eq!(stats.players[1].biggest_payout, 3000);
+    }
+
+    #[test]
+    fn test_game_results() {
+        let mut game = game_in_dispose_phase();
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::KeepAll),
+        )
+        .unwrap();
+        game.players[0].stocks[Hotel::Luxor as usize] = 2;
+        game.available_stock[Hotel::Luxor as usize] -= 2;
+        game.end_game();
+
+        let results = game.game_results();
+        assert_eq!(results.players[0].stocks[Hotel::Luxor as usize], 2);
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 0);
+        assert_eq!(results.players[1].cash, game.players[1].cash);
+        // the only Luxor stockholder collects both of its bonuses at the end
+        assert_eq!(results.winners, vec![0]);
+
+        #[cfg(feature = "serde")]
+        assert_eq!(
+            serde_json::from_str::<GameResults>(&game.results_json()).unwrap(),
+            results
+        );
+    }
+
+    #[test]
+    fn test_net_worth() {
+        let mut game = game_in_dispose_phase();
+        game.players[0].stocks[Hotel::Luxor as usize] = 2;
+
+        // Luxor is 3 tiles at $300 a share, Tower is 2 tiles at $200 a share
+        assert_eq!(game.net_worth(0), 6000 + 2 * 300);
+        assert_eq!(game.net_worth(1), 9000 + 4 * 200);
+    }
+
+    #[test]
+    fn test_stock_conservation() {
+        let mut game = game_in_dispose_phase();
+        assert!(game.verify_stock_conservation());
+        assert_eq!(
+            game.shares_outstanding(Hotel::Tower),
+            (0..game.players.len())
+                .map(|player| game.player_shares(player, Hotel::Tower))
+                .sum::<u32>()
+        );
+
+        // a share that appears out of nowhere is caught
+        game.players[0].stocks[Hotel::Luxor as usize] += 1;
+        assert!(!game.verify_stock_conservation());
+    }
+
+    #[test]
+    fn test_price_table() {
+        let game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        let table = game.price_table(Hotel::American);
+        assert_eq!(table.len(), 9);
+        assert_eq!(table[0], ("2", 300, 3000, 1500));
+        assert_eq!(table[4], ("6-10", 700, 7000, 3500));
+        assert_eq!(table[8], ("41+", 1100, 11000, 5500));
+    }
+
+    #[test]
+    fn test_current_leader() {
+        let mut game = game_in_dispose_phase();
+        assert_eq!(game.current_leader(), vec![1]);
+
+        game.players[0].cash = 9000 + 4 * 200;
+        assert_eq!(game.current_leader(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_with_board_size() {
+        let game = AcquireGame::new(2, Arc::new(TestCallback::default()))
+            .with_board_size(3, 5)
+            .unwrap();
+        assert_eq!(game.board.rows, 3);
+        assert_eq!(game.get_number_of_tiles_left(), 15);
+        assert_eq!(game.tiles_drawn(), 0);
+
+        let error = AcquireGame::new(3, Arc::new(TestCallback::default()))
+            .with_board_size(4, 5)
+            .err();
+        assert_eq!(
+            error,
+            Some(
+                "A 4 by 5 board is too small for 3 players, it needs at least 21 tiles".to_string()
+            )
+        );
+
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        respond(&mut game, AcquireResponse::StartingTile).unwrap();
+        assert!(game.with_board_size(3, 5).is_err());
+    }
+
+    #[test]
+    fn test_buys_per_turn() {
+        assert!(AcquireGame::new(2, Arc::new(TestCallback::default()))
+            .with_buys_per_turn(0)
+            .is_err());
+
+        let mut game = game_in_dispose_phase().with_buys_per_turn(1).unwrap();
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::SellAll),
+        )
+        .unwrap();
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+
+        // a single buy ends the turn
+        respond(
+            &mut game,
+            AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Luxor)),
+        )
+        .unwrap();
+        assert_eq!(game.get_players_to_respond(), vec![1]);
+    }
+
+    #[test]
+    fn test_starting_cash() {
+        let game = AcquireGame::new(3, Arc::new(TestCallback::default()));
+        assert_eq!(cash(&game), vec![6000, 6000, 6000]);
+
+        let game = game.with_starting_cash(8000);
+        assert_eq!(cash(&game), vec![8000, 8000, 8000]);
+        assert_eq!(game.players[2].name, "Player 3");
+    }
+
+    #[test]
+    fn test_player_names() {
+        let game = AcquireGame::new(3, Arc::new(TestCallback::default()))
+            .with_player_names(vec!["Alice".to_string(), "Bob".to_string()])
+            .unwrap()
+            .with_starting_cash(8000);
+        let names: Vec<&str> = game.players.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Alice", "Bob", "Player 3"]);
+
+        let names = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        assert!(AcquireGame::new(2, Arc::new(TestCallback::default()))
+            .with_player_names(names)
+            .is_err());
+    }
+
+    #[test]
+    fn test_number_of_players_is_validated() {
+        for number_of_players in [0, 1, 7] {
+            assert!(
+                AcquireGame::try_new(number_of_players, Arc::new(TestCallback::default())).is_err()
+            );
+        }
+        for number_of_players in 2..=6 {
+            let game =
+                AcquireGame::try_new(number_of_players, Arc::new(TestCallback::default())).unwrap();
+            assert_eq!(game.players.len(), number_of_players);
+        }
+    }
+
+    #[test]
+    fn test_tiles_drawn() {
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        assert_eq!(game.tiles_drawn(), 0);
+
+        respond(&mut game, AcquireResponse::StartingTile).unwrap();
+        respond(&mut game, AcquireResponse::StartingTile).unwrap();
+        // both starting tiles and two full hands
+        assert_eq!(game.tiles_drawn(), 2 + 2 * TILES_PER_PLAYER);
+        assert_eq!(
+            game.tiles_drawn() + game.get_number_of_tiles_left(),
+            game_board::BOARD_ROWS * game_board::BOARD_COLS
+        );
+    }
+
+    #[test]
+    fn test_legal_responses() {
+        let mut game = game_with_luxor_holdings(&[0, 0]);
+        game.players[0].cash = 200;
+        game.current_state = AcquireGameState::BuyStock(BuyStockState::new(0, 3));
+        game.current_request = AcquireRequest::BuyStock(0);
+        assert_eq!(
+            game.legal_responses(),
+            vec![
+                AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Luxor)),
+                AcquireResponse::BuyStock(BuyStockChoice::Pass),
+            ]
+        );
+
+        let game = game_in_dispose_phase();
+        let player = game.current_request.get_player();
+        let responses = game.legal_responses();
+        assert!(responses.contains(&AcquireResponse::DisposeStock(
+            player,
+            DisposeStockChoice::KeepAll
+        )));
+        assert!(responses.iter().all(
+            |response| matches!(response, AcquireResponse::DisposeStock(p, _) if *p == player)
+        ));
+    }
+
+    #[test]
+    fn test_random_legal_responses_are_always_accepted() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for seed in 0..2000 {
+            let number_of_players = 2 + seed as usize % 5;
+            let mut game =
+                AcquireGame::with_seed(number_of_players, Arc::new(TestCallback::default()), seed);
+
+            let mut responses = 0;
+            while !game.is_game_over() {
+                let response = game
+                    .legal_responses()
+                    .choose(&mut rng)
+                    .cloned()
+                    .unwrap_or_else(|| panic!("seed {} has no legal response", seed));
+                let player = match &response {
+                    AcquireResponse::DisposeStock(player, _) => *player,
+                    _ => game.current_request.get_player(),
+                };
+                if let Err(error) = game
+                    .handle_player_response(AcquirePlayerResponse::new(response.clone(), player))
+                {
+                    panic!("seed {} rejected {:?}: {}", seed, response, error);
+                }
+
+                responses += 1;
+                assert!(responses < 10_000, "seed {} did not finish", seed);
+            }
+            assert!(game.verify_stock_conservation(), "seed {}", seed);
+            assert!(game.legal_responses().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_response_from_the_wrong_player_is_rejected() {
+        let mut game = AcquireGame::with_seed(2, Arc::new(TestCallback::default()), 3);
+        assert_eq!(
+            game.handle_player_response(AcquirePlayerResponse::new(
+                AcquireResponse::StartingTile,
+                1
+            )),
+            Err(AcquireError::NotYourTurn)
+        );
+        assert!(game.action_log().is_empty());
+
+        // only the player asked may dispose of stock, and only their own
+        let mut game = game_in_dispose_phase();
+        let keep_all = |player| AcquireResponse::DisposeStock(player, DisposeStockChoice::KeepAll);
+        assert_eq!(
+            game.handle_player_response(AcquirePlayerResponse::new(keep_all(1), 0)),
+            Err(AcquireError::NotYourTurn)
+        );
+        assert_eq!(
+            game.handle_player_response(AcquirePlayerResponse::new(keep_all(0), 1)),
+            Err(AcquireError::NotYourStock)
+        );
+        assert_eq!(
+            game.handle_player_response(AcquirePlayerResponse::new(keep_all(1), 1)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_disposal_follows_turn_order_from_the_merge_maker() {
+        let mut game = AcquireGame::new(3, Arc::new(TestCallback::default()));
+        for col in 0..3 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Luxor));
+            game.available_tiles.remove(&Tile::from((0, col)));
+        }
+        for col in 4..6 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Tower));
+            game.available_tiles.remove(&Tile::from((0, col)));
+        }
+        for player in [0, 2] {
+            game.players[player].stocks[Hotel::Tower as usize] = 2;
+            game.available_stock[Hotel::Tower as usize] -= 2;
+        }
+
+        // the second player merges without any Tower stock of their own
+        let tile = Tile::from((0, 3));
+        game.available_tiles.remove(&tile);
+        game.players[1].tiles.push(tile);
+        game.current_state = AcquireGameState::PlayTile(1);
+        game.current_request = AcquireRequest::PlayTile(1);
+        respond(&mut game, AcquireResponse::Tile(tile)).unwrap();
+
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(2));
+        assert_eq!(game.get_players_to_respond(), vec![2]);
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(2, DisposeStockChoice::Keep),
+        )
+        .unwrap();
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(2));
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(2, DisposeStockChoice::SellAll),
+        )
+        .unwrap();
+
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(0));
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(0, DisposeStockChoice::KeepAll),
+        )
+        .unwrap();
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(1));
+    }
+
+    #[test]
+    fn test_undo_tile_and_buy() {
+        let mut game = game_with_luxor_holdings(&[0, 0]);
+        let tile = Tile::from((5, 5));
+        game.available_tiles.remove(&tile);
+        game.players[0].tiles.push(tile);
+        game.current_state = AcquireGameState::PlayTile(0);
+        game.current_request = AcquireRequest::PlayTile(0);
+        assert!(!game.can_undo());
+
+        respond(&mut game, AcquireResponse::Tile(tile)).unwrap();
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+        game.undo().unwrap();
+        assert_eq!(game.current_request, AcquireRequest::PlayTile(0));
+        assert_eq!(game.board.get_cell_state(5, 5), Cell::Empty);
+        assert!(game.players[0].tiles.contains(&tile));
+        assert!(game.action_log().is_empty());
+        assert!(game.undo().is_err());
+
+        respond(&mut game, AcquireResponse::Tile(tile)).unwrap();
+        respond(
+            &mut game,
+            AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Luxor)),
+        )
+        .unwrap();
+        game.undo().unwrap();
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 0);
+        assert_eq!(game.players[0].cash, 6000);
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+
+        // passing ends the turn, which cannot be undone
+        respond(&mut game, AcquireResponse::BuyStock(BuyStockChoice::Pass)).unwrap();
+        assert!(!game.can_undo());
+    }
+
+    #[test]
+    fn test_request_changes_are_reported() {
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        respond(&mut game, AcquireResponse::StartingTile).unwrap();
+        assert!(respond(&mut game, AcquireResponse::EndGame(true)).is_err());
+
+        let changes = game.message_callback.request_changes.lock().unwrap();
+        assert_eq!(
+            *changes,
+            vec![(
+                AcquireRequest::PlayStartingTile(0),
+                AcquireRequest::PlayStartingTile(1)
+            )]
+        );
+    }
+
+    #[test]
+    fn test_current_phase() {
+        let game = AcquireGame::new(3, Arc::new(TestCallback::default()));
+        assert_eq!(
+            game.current_phase(),
+            GamePhase::GameStart {
+                players_remaining: 3
+            }
+        );
+
+        let game = game_in_dispose_phase();
+        assert_eq!(
+            game.current_phase(),
+            GamePhase::DisposeStock {
+                merge_maker: 0,
+                survivor: Hotel::Luxor,
+                defunct: Hotel::Tower,
+                defunct_remaining: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_merger_cannot_be_undone() {
+        let mut game = game_in_dispose_phase();
+        assert_eq!(game.undo(), Err(AcquireError::NothingToUndo));
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(1));
+    }
+
+    #[test]
+    fn test_buyable_hotels() {
+        let mut game = game_with_luxor_holdings(&[0, 0]);
+        game.board.set_cell(2, 0, Cell::Hotel(Hotel::Continental));
+        game.board.set_cell(2, 1, Cell::Hotel(Hotel::Continental));
+        assert_eq!(
+            game.buyable_hotels(0),
+            vec![(Hotel::Luxor, 200), (Hotel::Continental, 400)]
+        );
+
+        game.players[0].cash = 300;
+        game.available_stock[Hotel::Luxor as usize] = 0;
+        assert_eq!(game.buyable_hotels(0), Vec::new());
+
+        game.current_state = AcquireGameState::BuyStock(BuyStockState::new(0, 3));
+        game.current_request = AcquireRequest::BuyStock(0);
+        assert_eq!(
+            respond(
+                &mut game,
+                AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Luxor))
+            ),
+            Err("There are no shares of Luxor left to buy".to_string())
+        );
+        assert_eq!(
+            respond(
+                &mut game,
+                AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Continental))
+            ),
+            Err("You cannot afford a share of Continental at $400".to_string())
+        );
+        assert_eq!(
+            respond(
+                &mut game,
+                AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Tower))
+            ),
+            Err("Tower has not been founded, its stock cannot be bought".to_string())
+        );
+    }
 
-                    next_phase = dispose_stock_state.player_handled_stock(player, shares_to_handle);
-                    self.give_player_stock(merge_survivor, player, stock_to_receive);
-                    self.take_back_players_stock(defunct_chain, player, shares_to_handle);
-                }
-            }
+    #[test]
+    fn test_buying_past_the_last_buy_is_rejected() {
+        let mut game = game_with_luxor_holdings(&[0, 0]);
+        assert_eq!(game.buys_remaining(), None);
 
-            if next_phase {
-                self.message_callback.send_message(&format!(
-                    "All players have disposed of their stock in {}",
-                    defunct_chain
-                ));
+        game.current_state = AcquireGameState::BuyStock(BuyStockState::new(0, 2));
+        game.current_request = AcquireRequest::BuyStock(0);
+        assert_eq!(game.buys_remaining(), Some(2));
+        respond(
+            &mut game,
+            AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Luxor)),
+        )
+        .unwrap();
+        assert_eq!(game.buys_remaining(), Some(1));
 
-                // All hotel tiles of the old chain are removed from the board and replaced with the new chain
-                self.board
-                    .replace_defunct_hotel_with_surviving_hotel(defunct_chain, merge_survivor);
-                self.continue_merge_phase(merge_maker, merge_survivor);
-            }
-        } else {
-            panic!("Cannot handle dispose stock response without a dispose stock state");
-        }
+        // a state with no buys left must refuse rather than panic
+        game.current_state = AcquireGameState::BuyStock(BuyStockState::new(0, 0));
+        let cash_before = cash(&game);
+        assert!(respond(
+            &mut game,
+            AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Luxor))
+        )
+        .is_err());
+        assert_eq!(cash(&game), cash_before);
 
-        Ok(())
+        let mut state = BuyStockState::new(0, 1);
+        assert_eq!(state.player_has_bought_stock(), Ok(true));
+        assert_eq!(
+            state.player_has_bought_stock(),
+            Err(AcquireError::BuyLimitReached)
+        );
     }
 
-    fn handle_buy_stock_response(&mut self, choice: BuyStockChoice) -> Result<(), String> {
-        if let AcquireGameState::BuyStock(buy_stock_state) = &mut self.current_state {
-            let player = buy_stock_state.player;
-            let end_phase;
-            match choice {
-                BuyStockChoice::Pass => {
-                    self.message_callback.send_message(&format!(
-                        "{} has chosen to pass on buying stock",
-                        self.players[player].name
-                    ));
+    #[test]
+    fn test_errors_can_be_matched_by_kind() {
+        let mut game = game_with_luxor_holdings(&[0, 0]);
+        game.players[0].cash = 100;
+        game.current_state = AcquireGameState::BuyStock(BuyStockState::new(0, 3));
+        game.current_request = AcquireRequest::BuyStock(0);
 
-                    end_phase = true;
-                }
-                BuyStockChoice::Buy(hotel) => {
-                    if self.available_stock[hotel as usize] == 0 {
-                        return Err(format!("No {} stock available to buy", hotel).to_string());
-                    }
+        let buy = |hotel| {
+            AcquirePlayerResponse::new(AcquireResponse::BuyStock(BuyStockChoice::Buy(hotel)), 0)
+        };
+        assert_eq!(
+            game.handle_player_response(buy(Hotel::Luxor)),
+            Err(AcquireError::InsufficientCash {
+                hotel: Hotel::Luxor,
+                price: 200
+            })
+        );
+        assert_eq!(
+            game.handle_player_response(buy(Hotel::Tower)),
+            Err(AcquireError::InvalidHotelChoice(
+                Hotel::Tower,
+                HotelChoiceError::NotFounded
+            ))
+        );
+        assert_eq!(
+            game.handle_player_response(AcquirePlayerResponse::new(
+                AcquireResponse::Tile(Tile::from((5, 5))),
+                0
+            )),
+            Err(AcquireError::WrongPhase(AcquireRequest::BuyStock(0)))
+        );
+    }
 
-                    let stock_value = hotel.get_stock_value(self.board.get_hotel_chain_size(hotel));
+    #[test]
+    fn test_unfounded_chain_is_reported_before_missing_stock() {
+        let mut game = game_with_luxor_holdings(&[0, 0]);
+        // every share of a chain that was taken over is still held
+        game.players[1].stocks[Hotel::Tower as usize] = MAX_STOCK_PER_HOTEL;
+        game.available_stock[Hotel::Tower as usize] = 0;
+        game.current_state = AcquireGameState::BuyStock(BuyStockState::new(0, 3));
+        game.current_request = AcquireRequest::BuyStock(0);
 
-                    if stock_value > self.players[player].cash {
-                        return Err("You do not have enough cash to buy stock".to_string());
-                    }
+        assert_eq!(
+            respond(
+                &mut game,
+                AcquireResponse::BuyStock(BuyStockChoice::BuyN(Hotel::Tower, 2))
+            ),
+            Err("Tower has not been founded, its stock cannot be bought".to_string())
+        );
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+    }
 
-                    if !self.board.get_active_hotels().contains(&hotel) {
-                        return Err("You cannot buy stock in an inactive chain".to_string());
-                    }
+    #[test]
+    fn test_turn_ends_when_no_stock_is_affordable() {
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        for col in 0..2 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Continental));
+            game.available_tiles.remove(&Tile::from((0, col)));
+        }
+        // a share of Continental at size 2 costs $400
+        game.players[0].cash = 300;
 
-                    self.message_callback.send_message(&format!(
-                        "{} has chosen to buy stock in {}",
-                        self.players[player].name, hotel
-                    ));
+        let tile = Tile::from((8, 11));
+        game.available_tiles.remove(&tile);
+        game.players[0].tiles.push(tile);
+        game.current_state = AcquireGameState::PlayTile(0);
+        game.current_request = AcquireRequest::PlayTile(0);
 
-                    end_phase = buy_stock_state.player_has_bought_stock();
-                    self.players[player].cash -= stock_value;
-                    self.give_player_stock(hotel, player, 1);
-                }
-            }
-            if end_phase {
-                self.end_turn(player);
-            }
-        } else {
-            panic!("Cannot handle buy stock response without a buy stock state");
+        respond(&mut game, AcquireResponse::Tile(tile)).unwrap();
+        assert_eq!(game.current_request.get_player(), 1);
+        assert!(game
+            .message_callback
+            .messages
+            .lock()
+            .unwrap()
+            .contains(&"Player 1 can't afford any available stock".to_string()));
+        let request_changes = game.message_callback.request_changes.lock().unwrap();
+        assert!(request_changes
+            .iter()
+            .all(|(_, new)| *new != AcquireRequest::BuyStock(0)));
+    }
+
+    #[test]
+    fn test_end_game_is_offered_once_every_chain_is_safe() {
+        let mut game = AcquireGame::new_with_first_player(2, Arc::new(TestCallback::default()), 0);
+        for col in 0..11 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Luxor));
+            game.available_tiles.remove(&Tile::from((0, col)));
         }
+        // with no cash the buy phase is skipped
+        game.players[0].cash = 0;
 
-        Ok(())
+        let tile = Tile::from((8, 11));
+        game.available_tiles.remove(&tile);
+        game.players[0].tiles.push(tile);
+        game.current_state = AcquireGameState::PlayTile(0);
+        game.current_request = AcquireRequest::PlayTile(0);
+
+        respond(&mut game, AcquireResponse::Tile(tile)).unwrap();
+        assert_eq!(game.current_request, AcquireRequest::EndGame(0));
+
+        // declining passes the turn as usual
+        respond(&mut game, AcquireResponse::EndGame(false)).unwrap();
+        assert_eq!(game.current_request.get_player(), 1);
+        assert!(!game.is_game_over());
     }
 
-    fn handle_end_game_response(&self, quit: bool, player: usize) {}
+    #[test]
+    fn test_ending_the_game_pays_bonuses_and_buys_back_stock() {
+        let mut game = game_with_luxor_holdings(&[3, 1]);
+        game.available_stock[Hotel::Luxor as usize] -= 4;
+        game.current_state = AcquireGameState::EndGame(0);
+        game.current_request = AcquireRequest::EndGame(0);
 
-    /// when a defunct chain is chosen to be resolved
-    /// this function is called to pay out the defunct
-    // and begin the stock disposal process
-    fn pay_out_defunct_chain(&mut self, defunct_hotel: Hotel) {
-        let defunct_chain_size = self.board.get_hotel_chain_size(defunct_hotel);
-        let majority_payout = defunct_hotel.get_majority_holder_bonus(defunct_chain_size);
-        let minority_payout = defunct_hotel.get_minority_holder_bonus(defunct_chain_size);
+        respond(&mut game, AcquireResponse::EndGame(true)).unwrap();
 
-        let mut majority_indices: [isize; 6] = [-1; 6];
-        let mut minority_indices: [isize; 6] = [-1; 6];
-        let mut max_shares = 0;
-        let mut second_max_shares = 0;
+        // bonuses of $2000 and $1000, then every share sold back at $200
+        assert_eq!(cash(&game), vec![8600, 7200]);
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 0);
+        assert_eq!(game.player_shares(1, Hotel::Luxor), 0);
+        assert_eq!(
+            game.available_stock[Hotel::Luxor as usize],
+            MAX_STOCK_PER_HOTEL
+        );
 
-        // Find the highest and second-highest share counts
-        for player in &self.players {
-            let shares = player.stocks[defunct_hotel as usize];
-            if shares > max_shares {
-                second_max_shares = max_shares;
-                max_shares = shares;
-            } else if shares > second_max_shares && shares < max_shares {
-                second_max_shares = shares;
-            }
-        }
+        assert!(game.is_game_over());
+        let response = AcquirePlayerResponse::new(AcquireResponse::EndGame(true), 0);
+        assert_eq!(
+            game.handle_player_response(response),
+            Err(AcquireError::GameOver)
+        );
+    }
 
-        // Classify stockholders into majority and minority, using indices
-        let mut maj_count = 0;
-        let mut min_count = 0;
-        for (index, player) in self.players.iter().enumerate() {
-            let shares = player.stocks[defunct_hotel as usize];
-            if shares == max_shares {
-                majority_indices[maj_count] = index as isize;
-                maj_count += 1;
-            } else if shares == second_max_shares {
-                minority_indices[min_count] = index as isize;
-                min_count += 1;
-            }
+    // Luxor and Tower are both safe, so a tile between them is dead
+    fn game_with_a_dead_tile_in_hand(player: usize) -> AcquireGame<TestCallback> {
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        for col in 0..11 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Luxor));
+            game.board.set_cell(2, col, Cell::Hotel(Hotel::Tower));
+            game.available_tiles.remove(&Tile::from((0, col)));
+            game.available_tiles.remove(&Tile::from((2, col)));
+        }
+        // keep the other dead tiles out of the bag so a replacement is always live
+        for col in 0..11 {
+            game.available_tiles.remove(&Tile::from((1, col)));
         }
+        game.players[player].tiles = vec![Tile::from((1, 0))];
+        game
+    }
 
-        // Distribute payouts to majority stockholders
-        let mut total_majority_payout = majority_payout as u32;
-        let mut total_minority_payout = minority_payout as u32;
+    #[test]
+    fn test_dead_tiles_are_replaced_at_the_start_of_a_turn() {
+        let mut game = game_with_a_dead_tile_in_hand(1);
+        let tiles_in_bag = game.available_tiles.len();
 
-        // if there are no minority stockholders or multiple majority stockholders, the minority payout is 0
-        if min_count == 0 || maj_count > 1 {
-            total_majority_payout += minority_payout as u32;
-            total_minority_payout = 0;
-        }
+        game.start_turn(1);
 
-        if maj_count == 1 {
-            self.message_callback.send_message(&format!(
-                "{} has the majority in the defunct chain",
-                self.players[majority_indices[0] as usize].name,
-            ));
-        } else {
-            self.message_callback.send_message(&format!(
-                "The following players have the majority in the defunct chain:",
-            ));
-            for i in 0..maj_count {
-                self.message_callback.send_message(&format!(
-                    "{}",
-                    self.players[majority_indices[i] as usize].name,
-                ));
-            }
-        }
-        self.distribute_payouts_array(majority_indices, maj_count, total_majority_payout);
+        assert_eq!(game.players[1].tiles.len(), 1);
+        assert_ne!(game.players[1].tiles[0], Tile::from((1, 0)));
+        assert_eq!(game.available_tiles.len(), tiles_in_bag - 1);
+        assert!(game
+            .message_callback
+            .messages
+            .lock()
+            .unwrap()
+            .contains(&"Player 2 discarded the dead tile 1-B".to_string()));
+    }
 
-        // Distribute payouts to minority stockholders, if there is a single majority stockholder
-        if total_minority_payout > 0 {
-            if min_count == 1 {
-                self.message_callback.send_message(&format!(
-                    "{} has the minority in the defunct chain",
-                    self.players[minority_indices[0] as usize].name,
-                ));
-            } else {
-                self.message_callback.send_message(&format!(
-                    "The following players have the minority in the defunct chain:",
-                ));
-                for i in 0..min_count {
-                    self.message_callback.send_message(&format!(
-                        "{}",
-                        self.players[minority_indices[i] as usize].name,
-                    ));
-                }
-            }
-            self.distribute_payouts_array(minority_indices, min_count, total_minority_payout);
-        }
+    #[test]
+    fn test_game_ends_when_no_player_can_place_a_tile() {
+        let mut game = game_with_a_dead_tile_in_hand(0);
+        game.players[1].tiles = vec![Tile::from((1, 1))];
+        game.available_tiles.clear();
+
+        game.pass_turn(0);
+
+        assert!(game.is_game_over());
+        assert!(game
+            .message_callback
+            .messages
+            .lock()
+            .unwrap()
+            .contains(&"No player can place a tile".to_string()));
     }
 
-    fn distribute_payouts_array(&mut self, indices: [isize; 6], count: usize, total_payout: u32) {
-        if count == 0 {
-            return;
-        }
+    #[test]
+    fn test_player_without_a_playable_tile_skips_to_buying_stock() {
+        let mut game = game_with_a_dead_tile_in_hand(1);
+        // with an empty bag the dead tile can't be replaced
+        game.available_tiles.clear();
 
-        let payout_per_player = total_payout / (count as u32);
-        for &index in indices.iter().take(count) {
-            if index != -1 {
-                self.message_callback.send_message(&format!(
-                    "{} receives a payout of ${}",
-                    self.players[index as usize].name, payout_per_player
-                ));
-                self.players[index as usize].cash += payout_per_player;
-            }
-        }
+        game.start_turn(1);
+
+        assert_eq!(game.players[1].tiles, vec![Tile::from((1, 0))]);
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(1));
+        assert!(game
+            .message_callback
+            .messages
+            .lock()
+            .unwrap()
+            .contains(&"Player 2 has no playable tiles".to_string()));
     }
 
-    fn sell_off_players_stock(&mut self, hotel: Hotel, player: usize, shares: u32) {
-        if self.players[player].stocks[hotel as usize] < shares {
-            return;
-        }
+    #[test]
+    fn test_drawing_from_an_empty_bag_leaves_the_hand_alone() {
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        let hand = game.players[0].tiles.clone();
+        game.available_tiles.clear();
 
-        let stock_value = hotel.get_stock_value(self.board.get_hotel_chain_size(hotel));
-        let payout = stock_value * shares;
-        self.players[player].cash += payout;
-        self.available_stock[hotel as usize] += shares;
-        self.players[player].stocks[hotel as usize] -= shares;
+        game.give_player_tile(0);
 
-        self.message_callback.send_message(&format!(
-            "{} has sold {} stock in {} for ${}",
-            self.players[player].name, shares, hotel, payout
-        ));
+        assert_eq!(game.players[0].tiles, hand);
     }
 
-    fn give_player_stock(&mut self, hotel: Hotel, player: usize, shares: u32) {
-        if self.available_stock[hotel as usize] < shares {
-            return;
-        }
+    #[test]
+    fn test_keep_all_keeps_every_share_in_one_response() {
+        let mut game = game_in_dispose_phase();
+        let cash_before = cash(&game);
 
-        self.message_callback.send_message(&format!(
-            "{} has received {} stock in {}",
-            self.players[player].name, shares, hotel
-        ));
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::KeepAll),
+        )
+        .unwrap();
 
-        self.players[player].stocks[hotel as usize] += shares;
-        self.available_stock[hotel as usize] -= shares;
+        assert_eq!(game.player_shares(1, Hotel::Tower), 4);
+        assert_eq!(cash(&game), cash_before);
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
     }
 
-    fn take_back_players_stock(&mut self, hotel: Hotel, player: usize, shares: u32) {
-        if self.players[player].stocks[hotel as usize] < shares {
-            return;
-        }
+    #[test]
+    fn test_disposal_is_skipped_when_no_one_holds_the_defunct_stock() {
+        let game = game_in_dispose_phase_with_shares(0);
 
-        self.players[player].stocks[hotel as usize] -= shares;
-        self.available_stock[hotel as usize] += shares;
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+        assert!(!game
+            .message_callback
+            .messages
+            .lock()
+            .unwrap()
+            .contains(&"Players must now dispose of their stock in Tower".to_string()));
     }
 
-    fn start_buy_stock_phase(&mut self, player: usize) {
-        let active_hotels = self.board.get_active_hotels();
-        if active_hotels.is_empty() {
-            self.message_callback.send_message("There are no active chains to buy stock in");
-            self.end_turn(player);
-            return;
+    #[test]
+    fn test_buy_several_shares_at_once() {
+        let mut game = game_with_luxor_holdings(&[0, 0]);
+        game.current_state = AcquireGameState::BuyStock(BuyStockState::new(0, 3));
+        game.current_request = AcquireRequest::BuyStock(0);
+
+        respond(
+            &mut game,
+            AcquireResponse::BuyStock(BuyStockChoice::BuyN(Hotel::Luxor, 2)),
+        )
+        .unwrap();
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 2);
+        assert_eq!(game.players[0].cash, 5600);
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+
+        // only one buy is left this turn, the rest of the request is dropped
+        respond(
+            &mut game,
+            AcquireResponse::BuyStock(BuyStockChoice::BuyN(Hotel::Luxor, 3)),
+        )
+        .unwrap();
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 3);
+        assert_eq!(game.players[0].cash, 5400);
+        assert_ne!(game.current_request, AcquireRequest::BuyStock(0));
+    }
+
+    #[test]
+    fn test_buy_several_shares_limited_by_cash() {
+        let mut game = game_with_luxor_holdings(&[0, 0]);
+        game.players[0].cash = 500;
+        game.current_state = AcquireGameState::BuyStock(BuyStockState::new(0, 3));
+        game.current_request = AcquireRequest::BuyStock(0);
+
+        respond(
+            &mut game,
+            AcquireResponse::BuyStock(BuyStockChoice::BuyN(Hotel::Luxor, 3)),
+        )
+        .unwrap();
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 2);
+        assert_eq!(game.players[0].cash, 100);
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+    }
+
+    #[test]
+    fn test_starting_tiles_and_turn_order_are_announced() {
+        let mut game = AcquireGame::with_seed(3, Arc::new(TestCallback::default()), 7);
+        for _ in 0..3 {
+            respond(&mut game, AcquireResponse::StartingTile).unwrap();
         }
 
-        if active_hotels
+        let messages = game.message_callback.messages.lock().unwrap();
+        let draws: Vec<&String> = messages
             .iter()
-            .all(|hotel| self.available_stock[*hotel as usize] == 0)
-        {
-            self.message_callback.send_message("There is no stock available to buy");
-            self.end_turn(player);
-            return;
-        }
+            .filter(|message| message.contains(" drew "))
+            .collect();
+        assert_eq!(draws.len(), 3);
 
-        let buy_stock_state = BuyStockState::new(player);
+        let tiles: Vec<Tile> = draws
+            .iter()
+            .map(|message| message.rsplit(' ').next().unwrap().parse().unwrap())
+            .collect();
+        assert!(tiles.windows(2).all(|pair| pair[0] < pair[1]));
 
-        self.current_request = AcquireRequest::BuyStock(player);
-        self.current_state = AcquireGameState::BuyStock(buy_stock_state);
+        let first_player = match game.current_request {
+            AcquireRequest::PlayTile(player) | AcquireRequest::BuyStock(player) => player,
+            request => panic!("Unexpected request {:?}", request),
+        };
+        let expected_order = (0..3)
+            .map(|offset| format!("Player {}", (first_player + offset) % 3 + 1))
+            .collect::<Vec<_>>()
+            .join(", ");
+        assert!(messages.contains(&format!("Turn order: {}", expected_order)));
     }
 
-    fn start_merge_phase(&mut self, merge_maker: usize, merge_survivor: Hotel) {
-        let merge_state = MergerState::new(merge_maker, merge_survivor, &self.board);
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_save_and_load_mid_dispose() {
+        let house_rules = RuleSet {
+            buys_per_turn: 2,
+            trade_ratio: 3,
+            ..RuleSet::standard()
+        };
+        let game = game_in_dispose_phase().with_rules(house_rules).unwrap();
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(1));
 
-        self.message_callback.send_message(&format!(
-            "{} has triggered a merge!",
-            self.players[merge_maker].name
-        ));
+        let path = std::env::temp_dir().join("actuire_test_save_and_load_mid_dispose.json");
+        game.save_to_path(&path).unwrap();
+        let loaded = AcquireGame::load_from_path(&path, Arc::new(TestCallback::default())).unwrap();
+        std::fs::remove_file(&path).unwrap();
 
-        self.message_callback.send_message(&format!(
-            "The following hotel(s) will be merged into {}:",
-            merge_survivor
-        ));
+        assert_eq!(loaded.players, game.players);
+        assert_eq!(loaded.board, game.board);
+        assert_eq!(loaded.available_tiles, game.available_tiles);
+        assert_eq!(loaded.available_stock, game.available_stock);
+        assert_eq!(loaded.current_request, game.current_request);
+        assert_eq!(loaded.current_state, game.current_state);
+        assert_eq!(loaded.turn_events, game.turn_events);
+        assert_eq!(loaded.turn_history, game.turn_history);
+        assert_eq!(loaded.rules, house_rules);
+    }
 
-        for hotel in merge_state.defunct_hotels_remaining.iter() {
-            self.message_callback.send_message(&format!("{}", hotel));
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_save_with_more_tiles_than_cells_is_rejected() {
+        let game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        let path = std::env::temp_dir().join("actuire_test_save_with_more_tiles.json");
+        game.save_to_path(&path).unwrap();
+
+        let mut save: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        save["board"]["rows"] = 1.into();
+        std::fs::write(&path, save.to_string()).unwrap();
+        let loaded = AcquireGame::load_from_path(&path, Arc::new(TestCallback::default()));
+        std::fs::remove_file(&path).unwrap();
+
+        let error = loaded.err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "The save has more tiles in the bag than the board has cells"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_loaded_game_draws_where_the_save_left_off() {
+        let mut game = AcquireGame::with_seed(3, Arc::new(TestCallback::default()), 9);
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..12 {
+            let response = random_response(&game, &mut rng);
+            respond(&mut game, response).unwrap();
         }
 
-        self.current_state = AcquireGameState::Merger(merge_state);
-        self.determine_next_defunct_hotel();
+        let path = std::env::temp_dir().join("actuire_test_loaded_game_draws.json");
+        game.save_to_path(&path).unwrap();
+        let mut loaded =
+            AcquireGame::load_from_path(&path, Arc::new(TestCallback::default())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.action_log(), game.action_log());
+        let replayed = AcquireGame::replay(
+            &loaded.setup(),
+            loaded.action_log(),
+            Arc::new(TestCallback::default()),
+        )
+        .unwrap();
+        assert_eq!(replayed.snapshot(), game.snapshot());
+
+        for _ in 0..5 {
+            assert_eq!(loaded.take_random_tile(), game.take_random_tile());
+        }
     }
 
-    fn determine_next_defunct_hotel(&mut self) {
-        if let AcquireGameState::Merger(merge_state) = &self.current_state {
-            let merge_maker = merge_state.merge_maker;
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_event_log_rebuilds_the_game() {
+        use crate::logic::acquire_player_strategy::{AcquirePlayerStrategy, GreedyBot};
 
-            match merge_state.get_next_defunct_hotel(&self.board) {
-                Some(defunct_hotel) => {
-                    self.handle_defunct_hotel(defunct_hotel);
-                }
-                None => {
-                    self.message_callback.send_message(&format!(
-                        "There is a tie in the defunct chains! {} must choose which chain to resolve first",
-                        self.players[merge_maker].name
-                    ));
+        let path = std::env::temp_dir().join("actuire_test_event_log_rebuilds_the_game.jsonl");
+        let mut game = AcquireGame::with_seed(3, Arc::new(TestCallback::default()), 5)
+            .with_event_log(&path)
+            .unwrap();
+        let bots: Vec<GreedyBot> = (0..3).map(GreedyBot::new).collect();
+        for _ in 0..60 {
+            let player = game.get_players_to_respond()[0];
+            let request = game.current_request;
+            let response = bots[player].decide(&game, &request);
+            game.handle_player_response(AcquirePlayerResponse::new(response, player))
+                .unwrap();
+        }
 
-                    self.current_request = AcquireRequest::ChooseDefunctChainToResolve(merge_maker);
-                }
-            }
-        } else {
-            panic!("Cannot determine next defunct hotel without a merge state");
+        // an undone response is dropped from the log as well
+        if game.can_undo() {
+            game.undo().unwrap();
         }
+
+        let rebuilt =
+            AcquireGame::from_event_log(&path, Arc::new(TestCallback::default())).unwrap();
+        assert_eq!(rebuilt.snapshot(), game.snapshot());
+        assert_eq!(rebuilt.action_log(), game.action_log());
+        std::fs::remove_file(&path).unwrap();
     }
 
-    fn continue_merge_phase(&mut self, merge_maker: usize, merge_survivor: Hotel) {
-        let merge_state = MergerState::new(merge_maker, merge_survivor, &self.board);
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_event_log_rebuilds_a_variant_game() {
+        let path = std::env::temp_dir().join("actuire_test_event_log_rebuilds_a_variant.jsonl");
+        let setup = GameSetup {
+            names: vec!["Ann".to_string(), "Bob".to_string()],
+            starting_cash: 4000,
+            rules: RuleSet {
+                buys_per_turn: 2,
+                safe_chain_size: 8,
+                ..RuleSet::standard()
+            },
+            board_rows: 6,
+            board_cols: 8,
+            ..GameSetup::new(5, 2)
+        };
+        let mut game = setup
+            .build(Arc::new(TestCallback::default()))
+            .unwrap()
+            .with_event_log(&path)
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(8);
+        for _ in 0..20 {
+            let response = random_response(&game, &mut rng);
+            respond(&mut game, response).unwrap();
+        }
 
-        if merge_state.defunct_hotels_remaining.is_empty() {
-            self.message_callback.send_message(&format!(
-                "All defunct chains have been merged"
-            ));
+        assert_eq!(game.setup(), setup);
+        let rebuilt =
+            AcquireGame::from_event_log(&path, Arc::new(TestCallback::default())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(rebuilt.setup(), setup);
+        assert_eq!(rebuilt.snapshot(), game.snapshot());
 
-            self.message_callback.send_message(&format!(
-                "The merger into {} is complete!",
-                merge_survivor
-            ));
+        let replayed =
+            AcquireGame::replay(&setup, game.action_log(), Arc::new(TestCallback::default()))
+                .unwrap();
+        assert_eq!(replayed.snapshot(), game.snapshot());
+    }
 
-            let _ = self.board.resolve_conflict(merge_survivor);
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_event_log_with_the_wrong_player_is_rejected() {
+        let path = std::env::temp_dir().join("actuire_test_event_log_wrong_player.jsonl");
+        let log = [
+            r#"{"seed":5,"number_of_players":2}"#,
+            r#"{"player":0,"response":"StartingTile"}"#,
+            r#"{"player":0,"response":"StartingTile"}"#,
+        ];
+        std::fs::write(&path, log.join("\n")).unwrap();
 
-            self.message_callback.send_message(&format!(
-                "{} can now buy stock",
-                self.players[merge_maker].name
-            ));
-            self.start_buy_stock_phase(merge_maker);
-        } else {
-            self.message_callback.send_message(&format!("Continuing the mergo into {}", merge_survivor));
+        let result = AcquireGame::from_event_log(&path, Arc::new(TestCallback::default()));
+        std::fs::remove_file(&path).unwrap();
+        let error = result.err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("Line 3 of the event log"));
+    }
 
-            self.current_state = AcquireGameState::Merger(merge_state);
-            self.determine_next_defunct_hotel();
+    #[test]
+    fn test_resigning_the_current_player_passes_the_turn() {
+        let mut game = game_with_luxor_holdings(&[2, 0, 0]);
+        game.available_stock[Hotel::Luxor as usize] -= 2;
+        for player in 0..3 {
+            let tile = Tile::from((5, player * 2));
+            game.available_tiles.remove(&tile);
+            game.players[player].tiles.push(tile);
         }
+        game.current_state = AcquireGameState::PlayTile(0);
+        game.current_request = AcquireRequest::PlayTile(0);
+
+        game.resign_player(0).unwrap();
+        assert!(game.players[0].resigned);
+        assert!(game.players[0].tiles.is_empty());
+        assert_eq!(game.player_shares(0, Hotel::Luxor), 0);
+        assert_eq!(cash(&game), vec![6400, 6000, 6000]);
+        assert!(game.verify_stock_conservation());
+        assert_eq!(game.current_request, AcquireRequest::PlayTile(1));
+        assert_eq!(
+            game.resign_player(0),
+            Err(AcquireError::AlreadyResigned {
+                player_name: "Player 1".to_string()
+            })
+        );
+        assert_eq!(game.resign_player(3), Err(AcquireError::NoSuchPlayer(3)));
+
+        // the resigned player is skipped when the turn comes back around
+        game.pass_turn(2);
+        assert_eq!(game.current_request, AcquireRequest::PlayTile(1));
     }
 
-    fn handle_defunct_hotel(&mut self, defunct_hotel: Hotel) {
-        if let AcquireGameState::Merger(merge_state) = &self.current_state {
-            let merge_survivor = merge_state.surviving_hotel;
+    #[test]
+    fn test_resigning_during_disposal_skips_the_player() {
+        let mut game = AcquireGame::new(3, Arc::new(TestCallback::default()));
+        for col in 0..3 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Luxor));
+            game.available_tiles.remove(&Tile::from((0, col)));
+        }
+        for col in 4..6 {
+            game.board.set_cell(0, col, Cell::Hotel(Hotel::Tower));
+            game.available_tiles.remove(&Tile::from((0, col)));
+        }
+        for (player, shares) in [(1, 4), (2, 2)] {
+            game.players[player].stocks[Hotel::Tower as usize] = shares;
+            game.available_stock[Hotel::Tower as usize] -= shares;
+        }
 
-            self.message_callback.send_message(&format!(
-                "A merger of {} into {} will now commence",
-                defunct_hotel, merge_survivor
-            ));
+        let tile = Tile::from((0, 3));
+        game.available_tiles.remove(&tile);
+        game.players[0].tiles.push(tile);
+        game.current_state = AcquireGameState::PlayTile(0);
+        game.current_request = AcquireRequest::PlayTile(0);
+        respond(&mut game, AcquireResponse::Tile(tile)).unwrap();
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(1));
 
-            // pay out the owners of the defunct chain
-            self.pay_out_defunct_chain(defunct_hotel);
+        // the minority bonus is kept and the two Tower shares are sold at $200
+        game.resign_player(2).unwrap();
+        assert_eq!(game.get_remaining_shares_to_dispose(2), 0);
+        assert_eq!(cash(&game)[2], 7400);
+        assert_eq!(game.current_request, AcquireRequest::DisposeStock(1));
 
-            // begin the stock disposal phase
-            self.begin_stock_disposal(defunct_hotel);
-        } else {
-            panic!("Cannot handle defunct hotel without a merge state");
-        }
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::SellAll),
+        )
+        .unwrap();
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
     }
 
-    fn begin_stock_disposal(&mut self, defunct_hotel: Hotel) {
-        if let AcquireGameState::Merger(merge_state) = &self.current_state {
-            let merge_maker = merge_state.merge_maker;
-            let merge_survivor = merge_state.surviving_hotel;
+    #[test]
+    fn test_resigning_is_replayed() {
+        let mut game = AcquireGame::with_seed(3, Arc::new(TestCallback::default()), 4);
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..12 {
+            let response = random_response(&game, &mut rng);
+            respond(&mut game, response).unwrap();
+        }
 
-            let mut remaining_shares_per_player = Vec::new();
-            for player in &self.players {
-                remaining_shares_per_player.push(player.stocks[defunct_hotel as usize]);
-            }
+        // a player may resign out of turn
+        let resigning = (game.current_request.get_player() + 1) % 3;
+        game.handle_player_response(AcquirePlayerResponse::new(
+            AcquireResponse::Resign,
+            resigning,
+        ))
+        .unwrap();
+        assert!(game.players[resigning].resigned);
+        for _ in 0..12 {
+            let response = random_response(&game, &mut rng);
+            respond(&mut game, response).unwrap();
+        }
 
-            let dispose_stock_state = DisposeStockState::new(
-                merge_maker,
-                merge_survivor,
-                defunct_hotel,
-                remaining_shares_per_player,
-            );
+        let replayed = AcquireGame::replay(
+            &game.setup(),
+            game.action_log(),
+            Arc::new(TestCallback::default()),
+        )
+        .unwrap();
+        assert!(replayed.players[resigning].resigned);
+        assert_eq!(replayed.snapshot(), game.snapshot());
+    }
 
-            self.current_state = AcquireGameState::DisposeStock(dispose_stock_state);
-            self.current_request = AcquireRequest::DisposeStock;
+    #[test]
+    fn test_last_player_standing_ends_the_game() {
+        let mut game = game_in_dispose_phase();
+        game.resign_player(1).unwrap();
 
-            self.message_callback.send_message(&format!(
-                "Players must now dispose of their stock in {}",
-                defunct_hotel
-            ));
-        } else {
-            panic!("Cannot begin stock disposal without a merge state");
-        }
+        assert!(game.is_game_over());
+        assert_eq!(game.player_shares(1, Hotel::Tower), 0);
+        assert_eq!(game.resign_player(0), Err(AcquireError::GameOver));
     }
 
-    fn handle_game_start_complete(&mut self) {
-        if let AcquireGameState::GameStart(game_start_state) = &self.current_state {
-            self.message_callback.send_message(&format!("All players have placed their starting tiles."));
+    #[test]
+    fn test_refused_resignation_leaves_the_game_unchanged() {
+        let rules = RuleSet {
+            allow_sell_and_trade: false,
+            ..RuleSet::standard()
+        };
+        let mut game = game_in_dispose_phase().with_rules(rules).unwrap();
+        respond(
+            &mut game,
+            AcquireResponse::DisposeStock(1, DisposeStockChoice::TradeN(2)),
+        )
+        .unwrap();
+        let before = game.snapshot();
+        let action_log = game.action_log().to_vec();
 
-            let player_with_winning_tile = game_start_state.player_with_winning_tile;
-            let winning_tile = game_start_state.winning_tile;
-            self.message_callback.send_message(&format!(
-                "{} has the winning tile {} and will start the game!",
-                self.players[player_with_winning_tile].name, winning_tile
-            ));
+        // the shares left to dispose of cannot be sold for them after trading
+        assert_eq!(
+            game.resign_player(1),
+            Err(AcquireError::SellAndTradeNotAllowed(
+                SellAndTrade::SellAfterTrading
+            ))
+        );
+        assert_eq!(game.snapshot(), before);
+        assert_eq!(game.action_log(), action_log);
+        assert!(!game.players[1].resigned);
+    }
 
-            // give each player 6 tiles
-            for _ in 0..6 {
-                for player in 0..self.players.len() {
-                    self.give_player_tile(player);
-                }
+    #[test]
+    fn test_resigned_player_never_leads_or_wins() {
+        let mut game = game_in_dispose_phase();
+        game.resign_player(1).unwrap();
+
+        // selling the Tower shares left player 2 with the most cash
+        assert!(game.players[1].cash > game.players[0].cash);
+        assert_eq!(game.current_leader(), vec![0]);
+        assert_eq!(game.game_results().winners, vec![0]);
+    }
+
+    #[test]
+    fn test_tile_next_to_independent_stays_independent_with_every_chain_active() {
+        let mut game = AcquireGame::new(2, Arc::new(TestCallback::default()));
+        for hotel in Hotel::iter() {
+            for col in 0..2 {
+                let tile = Tile::from((hotel as usize, col));
+                game.board.set_cell(tile.row, tile.col, Cell::Hotel(hotel));
+                game.available_tiles.remove(&tile);
             }
+        }
+        let independent = Tile::from((8, 10));
+        game.board
+            .set_cell(independent.row, independent.col, Cell::Independent);
+        game.available_tiles.remove(&independent);
 
-            self.current_state = AcquireGameState::PlayTile(player_with_winning_tile);
-            self.current_request = AcquireRequest::PlayTile(player_with_winning_tile);
-        } else {
-            panic!("Cannot handle game start complete without a game start state");
+        let tile = Tile::from((8, 11));
+        game.available_tiles.remove(&tile);
+        game.players[0].tiles.push(tile);
+        game.current_state = AcquireGameState::PlayTile(0);
+        game.current_request = AcquireRequest::PlayTile(0);
+
+        assert_eq!(game.preview_tile(tile), TilePreview::Independent);
+        respond(&mut game, AcquireResponse::Tile(tile)).unwrap();
+        assert_eq!(game.board.get_cell_state(8, 11), Cell::Independent);
+        assert_eq!(game.current_request, AcquireRequest::BuyStock(0));
+    }
+
+    #[test]
+    fn test_new_with_first_player() {
+        let game = AcquireGame::new_with_first_player(3, Arc::new(TestCallback::default()), 2);
+        assert_eq!(game.current_request, AcquireRequest::PlayTile(2));
+        assert_eq!(game.current_state, AcquireGameState::PlayTile(2));
+        for player in &game.players {
+            assert_eq!(player.tiles.len(), TILES_PER_PLAYER);
         }
+        assert_eq!(game.tiles_drawn(), 3 * TILES_PER_PLAYER);
+        assert!(game
+            .board
+            .tiles()
+            .all(|tile| game.board.get_cell_state(tile.row, tile.col) == Cell::Empty));
     }
 
-    fn give_player_tile(&mut self, player: usize) {
-        let tile = self.take_random_tile();
-        self.players[player].tiles.push(tile);
+    #[test]
+    fn test_playable_tiles_in_hand() {
+        let mut game =
+            game_with_chains_around_tile(&[(Hotel::Luxor, (0, 1), 2), (Hotel::Tower, (0, -1), 2)]);
+        // (4, 4) would merge Tower into Luxor, (8, 0) is out on its own
+        let lone_tile = Tile::from((8, 0));
+        game.available_tiles.remove(&lone_tile);
+        game.players[0].tiles.push(lone_tile);
+        assert_eq!(game.playable_tiles(0), vec![Tile::from((4, 4)), lone_tile]);
+
+        game.board
+            .set_cell(8, 1, Cell::Conflict(game_board::CellConflictType::NewChain));
+        assert!(game.playable_tiles(0).is_empty());
+        assert!(game.playable_tiles(1).is_empty());
     }
 
-    pub(crate) fn get_current_chain_size(&self, hotel: Hotel) -> usize {
-        self.board.get_hotel_chain_size(hotel)
+    #[test]
+    fn test_suggest_tile() {
+        let mut game = AcquireGame::new_with_first_player(2, Arc::new(TestCallback::default()), 0);
+        for (tile, cell) in [
+            ((0, 0), Cell::Hotel(Hotel::Luxor)),
+            ((0, 1), Cell::Hotel(Hotel::Luxor)),
+            ((2, 0), Cell::Hotel(Hotel::Tower)),
+            ((2, 1), Cell::Hotel(Hotel::Tower)),
+            ((2, 2), Cell::Hotel(Hotel::Tower)),
+            ((7, 7), Cell::Independent),
+        ] {
+            game.board.set_cell(tile.0, tile.1, cell);
+        }
+        game.players[0].stocks[Hotel::Luxor as usize] = 2;
+        game.players[1].stocks[Hotel::Tower as usize] = 2;
+
+        // from worst to best: grows Tower, independent, founds a chain,
+        // merges Luxor away for a bonus and grows Luxor
+        game.players[0].tiles = [(3, 2), (5, 5), (7, 8), (1, 0), (0, 2)]
+            .into_iter()
+            .map(Tile::from)
+            .collect();
+        for expected in [(0, 2), (1, 0), (7, 8), (5, 5), (3, 2)] {
+            assert_eq!(game.suggest_tile(0), Some(Tile::from(expected)));
+            game.players[0]
+                .tiles
+                .retain(|tile| *tile != Tile::from(expected));
+        }
+        assert_eq!(game.suggest_tile(0), None);
+
+        // without Luxor stock the merger pays player 2 nothing, so the
+        // independent tile is suggested over it
+        game.players[1].tiles = [(1, 0), (5, 5)].into_iter().map(Tile::from).collect();
+        assert_eq!(game.suggest_tile(1), Some(Tile::from((5, 5))));
     }
 }
@@ -1,10 +1,10 @@
-use std::usize;
-
 use super::game_states::{
-    buy_stock_state::BuyStockState, dispose_stock_state::DisposeStockState, game_start_state::GameStartState, merge_state::MergerState
+    buy_stock_state::BuyStockState, dispose_stock_state::DisposeStockState,
+    game_start_state::GameStartState, merge_state::MergerState,
 };
 
-
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AcquireGameState {
     GameStart(GameStartState),
     PlayTile(usize),
@@ -12,4 +12,5 @@ pub enum AcquireGameState {
     Merger(MergerState),
     BuyStock(BuyStockState),
     EndGame(usize),
+    GameOver,
 }
@@ -0,0 +1,156 @@
+use std::cmp::Reverse;
+
+use super::{
+    acquire_game::{AcquireGame, AcquireGameCallback},
+    acquire_request::AcquireRequest,
+    acquire_response::{AcquireResponse, BuyStockChoice, DisposeStockChoice},
+    hotel_data::Hotel,
+};
+
+// decides how a computer controlled player responds to the game's requests
+pub trait AcquirePlayerStrategy<T: AcquireGameCallback> {
+    fn decide(&self, game: &AcquireGame<T>, request: &AcquireRequest) -> AcquireResponse;
+}
+
+// plays the first playable tile, founds the chain it holds the most stock in,
+// buys the cheapest stock it can afford and keeps its stock during mergers
+pub struct GreedyBot {
+    player: usize,
+}
+
+impl GreedyBot {
+    pub fn new(player: usize) -> Self {
+        GreedyBot { player }
+    }
+
+    fn choose_hotel<T: AcquireGameCallback>(&self, game: &AcquireGame<T>) -> Hotel {
+        let stocks = game.players[self.player].stocks;
+        game.get_acceptable_hotels_for_response()
+            .into_iter()
+            .min_by_key(|hotel| Reverse(stocks[*hotel as usize]))
+            .expect("the game should always offer a hotel to choose")
+    }
+}
+
+impl<T: AcquireGameCallback> AcquirePlayerStrategy<T> for GreedyBot {
+    fn decide(&self, game: &AcquireGame<T>, request: &AcquireRequest) -> AcquireResponse {
+        match *request {
+            AcquireRequest::PlayStartingTile(_) => AcquireResponse::StartingTile,
+            AcquireRequest::PlayTile(player) => {
+                let tile = game
+                    .playable_tiles(player)
+                    .first()
+                    .copied()
+                    .expect("the game should only ask for a tile when one is playable");
+                AcquireResponse::Tile(tile)
+            }
+            AcquireRequest::ChooseNewChain(_) => AcquireResponse::NewChain(self.choose_hotel(game)),
+            AcquireRequest::ChooseMergerSurvivor(_) => {
+                AcquireResponse::MergerSurvivor(self.choose_hotel(game))
+            }
+            AcquireRequest::ChooseDefunctChainToResolve(_) => {
+                AcquireResponse::DefunctChainToResolve(self.choose_hotel(game))
+            }
+            AcquireRequest::DisposeStock(player) => {
+                AcquireResponse::DisposeStock(player, DisposeStockChoice::KeepAll)
+            }
+            AcquireRequest::BuyStock(player) => {
+                let cheapest_hotel = game
+                    .buyable_hotels(player)
+                    .into_iter()
+                    .min_by_key(|(_, price)| *price);
+
+                match cheapest_hotel {
+                    Some((hotel, _)) => AcquireResponse::BuyStock(BuyStockChoice::Buy(hotel)),
+                    None => AcquireResponse::BuyStock(BuyStockChoice::Pass),
+                }
+            }
+            AcquireRequest::EndGame(_) => AcquireResponse::EndGame(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::logic::acquire_response::AcquirePlayerResponse;
+
+    struct NoMessages;
+
+    impl AcquireGameCallback for NoMessages {}
+
+    #[test]
+    fn test_greedy_bots_play_to_completion() {
+        for seed in 0..20 {
+            let mut game = AcquireGame::with_seed(3, Arc::new(NoMessages), seed);
+            let bots: Vec<GreedyBot> = (0..3).map(GreedyBot::new).collect();
+
+            let mut responses = 0;
+            while !game.is_game_over() {
+                let player = game.get_players_to_respond()[0];
+                let request = *game.get_current_request();
+                let response = bots[player].decide(&game, &request);
+                game.handle_player_response(AcquirePlayerResponse::new(response, player))
+                    .unwrap();
+
+                responses += 1;
+                assert!(responses < 10_000, "seed {} did not finish", seed);
+            }
+
+            assert_eq!(game.get_players_to_respond(), Vec::<usize>::new());
+        }
+    }
+
+    #[test]
+    fn test_run_to_completion_over_many_seeds() {
+        for seed in 0..1000 {
+            let number_of_players = 2 + seed as usize % 5;
+            let mut game = AcquireGame::with_seed(number_of_players, Arc::new(NoMessages), seed);
+            let mut strategies: Vec<Box<dyn AcquirePlayerStrategy<NoMessages>>> = Vec::new();
+            for player in 0..number_of_players {
+                strategies.push(Box::new(GreedyBot::new(player)));
+            }
+
+            let results = game.run_to_completion(&strategies);
+            assert!(game.is_game_over());
+            assert!(game.verify_stock_conservation(), "seed {}", seed);
+
+            let highest = results.players.iter().map(|p| p.net_worth).max().unwrap();
+            assert!(!results.winners.is_empty(), "seed {} has no winner", seed);
+            for winner in &results.winners {
+                assert_eq!(results.players[*winner].net_worth, highest, "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_greedy_bots_play_to_completion_on_a_small_board() {
+        for seed in 0..20 {
+            let mut game = AcquireGame::with_seed(2, Arc::new(NoMessages), seed)
+                .with_board_size(4, 5)
+                .unwrap();
+            let bots: Vec<GreedyBot> = (0..2).map(GreedyBot::new).collect();
+
+            let mut responses = 0;
+            while !game.is_game_over() {
+                let player = game.get_players_to_respond()[0];
+                let request = *game.get_current_request();
+                let response = bots[player].decide(&game, &request);
+                game.handle_player_response(AcquirePlayerResponse::new(response, player))
+                    .unwrap();
+
+                responses += 1;
+                assert!(responses < 1_000, "seed {} did not finish", seed);
+            }
+
+            // every tile dealt came from the smaller board
+            assert!(game
+                .players
+                .iter()
+                .flat_map(|player| &player.tiles)
+                .all(|tile| game.board.contains(tile)));
+        }
+    }
+}
@@ -1,13 +1,31 @@
 // This represents what the "game" is asking for
-// in most cases the usize stored is the player being asked
+// the usize stored is the player being asked
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AcquireRequest {
     PlayStartingTile(usize),
     PlayTile(usize),
     ChooseNewChain(usize),
     ChooseMergerSurvivor(usize),
     ChooseDefunctChainToResolve(usize),
-    //Dispose stock can be done in any order, so no need to ask a specific player
-    DisposeStock,
+    //Dispose stock is done in turn order starting from the merge maker
+    DisposeStock(usize),
     BuyStock(usize),
     EndGame(usize),
 }
+
+impl AcquireRequest {
+    // the player being asked
+    pub fn get_player(&self) -> usize {
+        match self {
+            AcquireRequest::PlayStartingTile(player)
+            | AcquireRequest::PlayTile(player)
+            | AcquireRequest::ChooseNewChain(player)
+            | AcquireRequest::ChooseMergerSurvivor(player)
+            | AcquireRequest::ChooseDefunctChainToResolve(player)
+            | AcquireRequest::DisposeStock(player)
+            | AcquireRequest::BuyStock(player)
+            | AcquireRequest::EndGame(player) => *player,
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use super::{hotel_data::Hotel, tile::Tile};
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DisposeStockChoice {
     Keep,
     Sell,
@@ -7,14 +9,25 @@ pub enum DisposeStockChoice {
     SellAll,
     KeepAll,
     TradeAll,
+    // sells or trades exactly this many shares
+    SellN(u32),
+    TradeN(u32),
+    // keeps, sells and trades shares all at once
+    Split { keep: u32, sell: u32, trade: u32 },
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BuyStockChoice {
     Buy(Hotel),
+    // buys up to this many shares, as many as the player is allowed to
+    BuyN(Hotel, u32),
     Pass,
 }
 
 // This represents the response from the player
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AcquireResponse {
     StartingTile,
     Tile(Tile),
@@ -24,8 +37,15 @@ pub enum AcquireResponse {
     DisposeStock(usize, DisposeStockChoice),
     BuyStock(BuyStockChoice),
     EndGame(bool),
+    // accepted at any time rather than in answer to a request, they are logged
+    // like any other response so a replayed game resigns and concedes as well
+    Resign,
+    // every player behind the leader concedes, logged for the player who conceded
+    Concede,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AcquirePlayerResponse {
     pub player: usize,
     pub response: AcquireResponse,
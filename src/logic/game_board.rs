@@ -1,19 +1,25 @@
-use core::fmt;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
-use super::hotel_data::Hotel;
+use super::{
+    hotel_data::Hotel,
+    tile::{self, Tile},
+};
 
+// the size of a standard board, other sizes can be made with GameBoard::with_size
 pub const BOARD_ROWS: usize = 9;
 pub const BOARD_COLS: usize = 12;
 pub const SAFE_CHAIN_SIZE: usize = 11;
+pub const END_GAME_CHAIN_SIZE: usize = 41;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CellConflictType {
     NewChain,
     Merge(usize),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Cell {
     Empty,
     Independent,
@@ -24,20 +30,35 @@ pub enum Cell {
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CellNotPlayableReason {
     ConflictOnBoard,
-    HotelsAreAllActive,
     AdjacentHotelsAreSafe,
     CellIsNotEmpty,
     CellIsOffBoard,
 }
 
 impl CellNotPlayableReason {
-    pub fn as_display_message(&self) -> String {
+    // safe chains never shrink and placed tiles never move, so only a conflict
+    // waiting to be resolved goes away by itself
+    pub fn is_permanent(&self) -> bool {
         match self {
-            CellNotPlayableReason::ConflictOnBoard => "There is a conflict on the board".to_string(),
-            CellNotPlayableReason::HotelsAreAllActive => "All hotels are currently active, cannot start a new chain".to_string(),
-            CellNotPlayableReason::AdjacentHotelsAreSafe => "Adjacent hotels are safe and cannot be merged".to_string(),
-            CellNotPlayableReason::CellIsNotEmpty => "The cell is not empty".to_string(),
-            CellNotPlayableReason::CellIsOffBoard => "The cell is off the board".to_string(),
+            CellNotPlayableReason::ConflictOnBoard => false,
+            CellNotPlayableReason::AdjacentHotelsAreSafe
+            | CellNotPlayableReason::CellIsNotEmpty
+            | CellNotPlayableReason::CellIsOffBoard => true,
+        }
+    }
+
+    pub fn as_display_message(&self) -> String {
+        let reason = match self {
+            CellNotPlayableReason::ConflictOnBoard => "There is a conflict on the board",
+            CellNotPlayableReason::AdjacentHotelsAreSafe => {
+                "Adjacent hotels are safe and cannot be merged"
+            }
+            CellNotPlayableReason::CellIsNotEmpty => "The cell is not empty",
+            CellNotPlayableReason::CellIsOffBoard => "The cell is off the board",
+        };
+        match self.is_permanent() {
+            true => format!("{} (permanently dead)", reason),
+            false => format!("{} (try again later)", reason),
         }
     }
 }
@@ -49,70 +70,182 @@ pub enum PlaceTileResult {
     ConflictCreated(CellConflictType),
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SavedGameBoard"))]
 pub struct GameBoard {
-    pub cells: [[Cell; BOARD_COLS]; BOARD_ROWS],
+    pub rows: usize,
+    pub cols: usize,
+    // chains this large can no longer be merged, set by the game's rules
+    pub safe_chain_size: usize,
+    // indexed by row then column. cells are written through set_cell so the chain sizes
+    // stay current, after writing them directly call recount_chain_sizes
+    pub cells: Vec<Vec<Cell>>,
+    // the number of tiles in each chain, kept up to date as cells change
+    #[cfg_attr(feature = "serde", serde(skip))]
+    chain_sizes: [usize; Hotel::count()],
+}
+
+// the chain sizes are not saved, they are counted again when a board is loaded
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SavedGameBoard {
+    #[serde(default = "default_rows")]
+    rows: usize,
+    #[serde(default = "default_cols")]
+    cols: usize,
+    #[serde(default = "default_safe_chain_size")]
+    safe_chain_size: usize,
+    cells: Vec<Vec<Cell>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SavedGameBoard> for GameBoard {
+    fn from(saved: SavedGameBoard) -> Self {
+        let mut board = GameBoard {
+            rows: saved.rows,
+            cols: saved.cols,
+            safe_chain_size: saved.safe_chain_size,
+            cells: saved.cells,
+            chain_sizes: [0; Hotel::count()],
+        };
+        board.recount_chain_sizes();
+        board
+    }
+}
+
+// boards saved before the size was configurable are standard boards
+#[cfg(feature = "serde")]
+fn default_rows() -> usize {
+    BOARD_ROWS
+}
+
+#[cfg(feature = "serde")]
+fn default_cols() -> usize {
+    BOARD_COLS
+}
+
+#[cfg(feature = "serde")]
+fn default_safe_chain_size() -> usize {
+    SAFE_CHAIN_SIZE
+}
+
+impl Default for GameBoard {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameBoard {
     pub fn new() -> GameBoard {
+        GameBoard::with_size(BOARD_ROWS, BOARD_COLS)
+    }
+
+    pub fn with_size(rows: usize, cols: usize) -> GameBoard {
         GameBoard {
-            cells: [[Cell::Empty; BOARD_COLS]; BOARD_ROWS],
+            rows,
+            cols,
+            safe_chain_size: SAFE_CHAIN_SIZE,
+            cells: vec![vec![Cell::Empty; cols]; rows],
+            chain_sizes: [0; Hotel::count()],
+        }
+    }
+
+    // a board with these cells, the rows must all be the same length
+    pub fn from_cells(cells: Vec<Vec<Cell>>) -> Result<GameBoard, String> {
+        let rows = cells.len();
+        let cols = cells.first().map_or(0, |row| row.len());
+        if rows == 0 || cols == 0 {
+            return Err("The board has no cells".to_string());
+        }
+        if cells.iter().any(|row| row.len() != cols) {
+            return Err("Every row of the board must be the same length".to_string());
+        }
+
+        let mut board = GameBoard {
+            rows,
+            cols,
+            safe_chain_size: SAFE_CHAIN_SIZE,
+            cells,
+            chain_sizes: [0; Hotel::count()],
+        };
+        board.recount_chain_sizes();
+        Ok(board)
+    }
+
+    pub fn set_cell(&mut self, row: usize, col: usize, cell: Cell) {
+        if let Cell::Hotel(hotel) = self.cells[row][col] {
+            self.chain_sizes[hotel as usize] -= 1;
         }
+        if let Cell::Hotel(hotel) = cell {
+            self.chain_sizes[hotel as usize] += 1;
+        }
+        self.cells[row][col] = cell;
+    }
+
+    // a full scan of the board, only needed when the cells were written directly
+    pub fn recount_chain_sizes(&mut self) {
+        self.chain_sizes = self.count_chain_sizes();
+    }
+
+    // checked once at the end of each operation that writes cells, not on every write
+    fn debug_assert_chain_sizes(&self) {
+        debug_assert_eq!(self.chain_sizes, self.count_chain_sizes());
+    }
+
+    fn count_chain_sizes(&self) -> [usize; Hotel::count()] {
+        let mut chain_sizes = [0; Hotel::count()];
+        for cell in self.cells.iter().flatten() {
+            if let Cell::Hotel(hotel) = cell {
+                chain_sizes[*hotel as usize] += 1;
+            }
+        }
+        chain_sizes
+    }
+
+    // every tile on this board, row by row
+    pub fn tiles(&self) -> impl Iterator<Item = Tile> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| Tile::from((row, col))))
+    }
+
+    pub fn contains(&self, tile: &Tile) -> bool {
+        tile.row < self.rows && tile.col < self.cols
     }
 
     pub fn get_cell_state(&self, row: usize, col: usize) -> Cell {
-        match row < BOARD_ROWS && col < BOARD_COLS {
+        match row < self.rows && col < self.cols {
             true => self.cells[row][col],
             false => Cell::Empty,
         }
     }
 
     pub fn get_hotel_at(&self, row: usize, col: usize) -> Option<Hotel> {
-        match self.cells[row][col] {
+        match self.get_cell_state(row, col) {
             Cell::Hotel(hotel) => Some(hotel),
             _ => None,
         }
     }
 
     pub fn get_active_hotels(&self) -> Vec<Hotel> {
-        let mut hotel_is_active = [false; Hotel::count()];
-
-        for row in 0..BOARD_ROWS {
-            for col in 0..BOARD_COLS {
-                if let Cell::Hotel(hotel) = self.cells[row][col] {
-                    hotel_is_active[hotel as usize] = true;
-                }
-            }
-        }
-
-        let mut active_hotels = Vec::new();
-        for hotel in Hotel::iter() {
-            if hotel_is_active[hotel as usize] {
-                active_hotels.push(hotel);
-            }
-        }
-        active_hotels
+        Hotel::iter()
+            .filter(|hotel| self.chain_sizes[*hotel as usize] > 0)
+            .collect()
     }
 
     pub fn get_inactive_hotels(&self) -> Vec<Hotel> {
-        let active_hotels = self.get_active_hotels();
         Hotel::iter()
-            .filter(|hotel| !active_hotels.contains(hotel))
+            .filter(|hotel| self.chain_sizes[*hotel as usize] == 0)
             .collect()
     }
 
     pub fn get_hotel_chain_size(&self, hotel: Hotel) -> usize {
-        let mut chain_size = 0;
-        for row in 0..BOARD_ROWS {
-            for col in 0..BOARD_COLS {
-                if let Cell::Hotel(h) = self.cells[row][col] {
-                    if h == hotel {
-                        chain_size += 1;
-                    }
-                }
-            }
-        }
-        chain_size
+        self.chain_sizes[hotel as usize]
+    }
+
+    // a safe chain is too large to ever be merged into another
+    pub fn is_chain_safe(&self, hotel: Hotel) -> bool {
+        self.get_hotel_chain_size(hotel) >= self.safe_chain_size
     }
 
     pub fn get_adjacent_hotels(&self, row: usize, col: usize) -> Vec<Hotel> {
@@ -172,10 +305,14 @@ impl GameBoard {
         false
     }
 
-    fn would_cell_start_new_chain(&self, row: usize, col: usize) -> bool {
+    // no chain can be founded while every hotel is on the board, the tile just
+    // joins the independent tiles next to it
+    pub fn would_cell_start_new_chain(&self, row: usize, col: usize) -> bool {
         // get the adjacent hotels
         let adjacent_hotels = self.get_adjacent_hotels(row, col);
-        adjacent_hotels.len() == 0 && self.is_cell_next_to_independent(row, col)
+        adjacent_hotels.is_empty()
+            && self.is_cell_next_to_independent(row, col)
+            && !self.get_inactive_hotels().is_empty()
     }
 
     fn would_cell_merge_chains(&self, row: usize, col: usize) -> bool {
@@ -185,12 +322,12 @@ impl GameBoard {
 
     pub fn is_cell_playable(&self, row: usize, col: usize) -> Result<bool, CellNotPlayableReason> {
         // check if the cell is off the board
-        if row >= BOARD_ROWS || col >= BOARD_COLS {
+        if row >= self.rows || col >= self.cols {
             return Err(CellNotPlayableReason::CellIsOffBoard);
         }
 
         // check if there is a conflict on the board
-        if let Some(_) = self.get_conflict_on_board() {
+        if self.get_conflict_on_board().is_some() {
             return Err(CellNotPlayableReason::ConflictOnBoard);
         }
 
@@ -199,20 +336,12 @@ impl GameBoard {
             return Err(CellNotPlayableReason::CellIsNotEmpty);
         }
 
-        let active_hotels = self.get_active_hotels();
-        let adjacent_hotels = self.get_adjacent_hotels(row, col);
-
-        // check if placing this tile would create an 8th chain
-        if active_hotels.len() >= Hotel::count() && self.would_cell_start_new_chain(row, col) {
-            return Err(CellNotPlayableReason::HotelsAreAllActive);
-        }
-
         // check if placing a tile would merge safe hotel chains
-        if adjacent_hotels.len() > 1 {
-            let number_of_adjacent_safe_chains: usize = adjacent_hotels
+        if self.would_cell_merge_chains(row, col) {
+            let number_of_adjacent_safe_chains: usize = self
+                .get_adjacent_hotels(row, col)
                 .iter()
-                .map(|hotel| self.get_hotel_chain_size(*hotel))
-                .filter(|chain_size| *chain_size >= SAFE_CHAIN_SIZE)
+                .filter(|hotel| self.is_chain_safe(**hotel))
                 .count();
 
             if number_of_adjacent_safe_chains > 1 {
@@ -223,9 +352,16 @@ impl GameBoard {
         Ok(true)
     }
 
+    // every empty cell a tile could be played on right now
+    pub fn playable_tiles(&self) -> Vec<Tile> {
+        self.tiles()
+            .filter(|tile| self.is_cell_playable(tile.row, tile.col).is_ok())
+            .collect()
+    }
+
     pub fn get_conflict_on_board(&self) -> Option<(usize, usize, CellConflictType)> {
-        for row in 0..BOARD_ROWS {
-            for col in 0..BOARD_COLS {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
                 if let Cell::Conflict(conflict_type) = self.cells[row][col] {
                     return Some((row, col, conflict_type));
                 }
@@ -240,7 +376,8 @@ impl GameBoard {
         }
 
         if self.would_cell_start_new_chain(row, col) {
-            self.cells[row][col] = Cell::Conflict(CellConflictType::NewChain);
+            self.set_cell(row, col, Cell::Conflict(CellConflictType::NewChain));
+            self.debug_assert_chain_sizes();
             return PlaceTileResult::ConflictCreated(CellConflictType::NewChain);
         }
 
@@ -248,17 +385,23 @@ impl GameBoard {
 
         if adjacent_hotels.len() > 1 {
             let number_of_mergers = adjacent_hotels.len() - 1;
-            self.cells[row][col] = Cell::Conflict(CellConflictType::Merge(number_of_mergers));
+            self.set_cell(
+                row,
+                col,
+                Cell::Conflict(CellConflictType::Merge(number_of_mergers)),
+            );
+            self.debug_assert_chain_sizes();
             return PlaceTileResult::ConflictCreated(CellConflictType::Merge(number_of_mergers));
         }
 
-        self.cells[row][col] = Cell::Independent;
+        self.set_cell(row, col, Cell::Independent);
 
         if adjacent_hotels.len() == 1 {
             self.flood_hotel(row, col, adjacent_hotels[0]);
             return PlaceTileResult::Success;
         }
 
+        self.debug_assert_chain_sizes();
         PlaceTileResult::Success
     }
 
@@ -274,7 +417,7 @@ impl GameBoard {
                     return;
                 }
             }
-            board.cells[row][col] = Cell::Hotel(hotel);
+            board.set_cell(row, col, Cell::Hotel(hotel));
 
             let (left, _) = col.overflowing_sub(1);
             let right = col + 1;
@@ -289,6 +432,7 @@ impl GameBoard {
         }
 
         fill(self, row, col, hotel);
+        self.debug_assert_chain_sizes();
     }
 
     pub fn acceptable_conflict_resolutions(&self) -> Vec<Hotel> {
@@ -329,7 +473,8 @@ impl GameBoard {
     }
 
     pub fn place_initial_tile(&mut self, row: usize, col: usize) {
-        self.cells[row][col] = Cell::Independent;
+        self.set_cell(row, col, Cell::Independent);
+        self.debug_assert_chain_sizes();
     }
 
     pub fn get_hotel_stock_price(&self, hotel: Hotel) -> u32 {
@@ -347,20 +492,60 @@ impl GameBoard {
         hotel.get_minority_holder_bonus(chain_length)
     }
 
+    // the game may be ended once any chain reaches 41 tiles
+    // or every chain on the board is safe
+    pub fn can_game_end(&self) -> bool {
+        let chain_sizes: Vec<usize> = self
+            .get_active_hotels()
+            .iter()
+            .map(|hotel| self.get_hotel_chain_size(*hotel))
+            .collect();
+
+        chain_sizes.iter().any(|size| *size >= END_GAME_CHAIN_SIZE)
+            || (!chain_sizes.is_empty()
+                && chain_sizes.iter().all(|size| *size >= self.safe_chain_size))
+    }
+
     pub fn replace_defunct_hotel_with_surviving_hotel(
         &mut self,
         defunct_hotel: Hotel,
         surviving_hotel: Hotel,
     ) {
-        for row in 0..BOARD_ROWS {
-            for col in 0..BOARD_COLS {
-                if let Cell::Hotel(hotel) = self.cells[row][col] {
-                    if hotel == defunct_hotel {
-                        self.cells[row][col] = Cell::Hotel(surviving_hotel);
-                    }
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                if self.cells[row][col] == Cell::Hotel(defunct_hotel) {
+                    self.set_cell(row, col, Cell::Hotel(surviving_hotel));
+                }
+            }
+        }
+        self.debug_assert_chain_sizes();
+    }
+}
+
+// a plain text view of the board: rows by letter, columns by number, hotels by their
+// first two letters, independent tiles as # and conflicts as !
+impl fmt::Display for GameBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label_width = tile::row_label(self.rows.saturating_sub(1)).len();
+        write!(f, "{:label_width$}", "")?;
+        for col in 0..self.cols {
+            write!(f, "{:>3}", col + 1)?;
+        }
+        writeln!(f)?;
+
+        for (row, cells) in self.cells.iter().enumerate() {
+            write!(f, "{:label_width$}", tile::row_label(row))?;
+            for cell in cells {
+                match cell {
+                    Cell::Empty => write!(f, "{:>3}", ".")?,
+                    Cell::Independent => write!(f, "{:>3}", "#")?,
+                    Cell::Conflict(_) => write!(f, "{:>3}", "!")?,
+                    Cell::Hotel(hotel) => write!(f, "{:>3}", &hotel.to_string()[..2])?,
                 }
             }
+            writeln!(f)?;
         }
+        Ok(())
     }
 }
 
@@ -375,6 +560,48 @@ mod tests {
         assert_eq!(game_board.cells[0].len(), BOARD_COLS);
     }
 
+    #[test]
+    fn test_with_size() {
+        let mut game_board = GameBoard::with_size(3, 4);
+        assert_eq!(game_board.tiles().count(), 12);
+        assert!(game_board.contains(&Tile::from((2, 3))));
+        assert!(!game_board.contains(&Tile::from((3, 0))));
+        assert_eq!(
+            game_board.is_cell_playable(0, 4),
+            Err(CellNotPlayableReason::CellIsOffBoard)
+        );
+
+        game_board.set_cell(2, 3, Cell::Independent);
+        assert_eq!(
+            game_board.to_string(),
+            "   1  2  3  4\nA  .  .  .  .\nB  .  .  .  .\nC  .  .  .  #\n"
+        );
+
+        // row labels past Z are two letters wide
+        let text = GameBoard::with_size(27, 1).to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "    1");
+        assert_eq!(lines[1], "A   .");
+        assert_eq!(lines[27], "AA  .");
+    }
+
+    #[test]
+    fn test_display() {
+        let mut game_board = GameBoard::new();
+        game_board.set_cell(0, 0, Cell::Hotel(Hotel::Luxor));
+        game_board.set_cell(0, 1, Cell::Hotel(Hotel::Luxor));
+        game_board.set_cell(1, 10, Cell::Independent);
+        game_board.set_cell(8, 11, Cell::Conflict(CellConflictType::NewChain));
+
+        let text = game_board.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), BOARD_ROWS + 1);
+        assert_eq!(lines[0], "   1  2  3  4  5  6  7  8  9 10 11 12");
+        assert_eq!(lines[1], "A Lu Lu  .  .  .  .  .  .  .  .  .  .");
+        assert_eq!(lines[2], "B  .  .  .  .  .  .  .  .  .  .  #  .");
+        assert_eq!(lines[9], "I  .  .  .  .  .  .  .  .  .  .  .  !");
+    }
+
     #[test]
     fn test_get_cell_state() {
         let game_board = GameBoard::new();
@@ -384,44 +611,72 @@ mod tests {
     #[test]
     fn test_get_hotel_at() {
         let mut game_board = GameBoard::new();
-        game_board.cells[0][0] = Cell::Hotel(Hotel::Luxor);
+        game_board.set_cell(0, 0, Cell::Hotel(Hotel::Luxor));
         assert_eq!(game_board.get_hotel_at(0, 0), Some(Hotel::Luxor));
+        assert_eq!(game_board.get_hotel_at(0, 1), None);
+        assert_eq!(game_board.get_hotel_at(BOARD_ROWS, 0), None);
+        assert_eq!(game_board.get_hotel_at(0, BOARD_COLS), None);
     }
 
     #[test]
     fn test_get_active_hotels() {
         let mut game_board = GameBoard::new();
-        game_board.cells[0][0] = Cell::Hotel(Hotel::Luxor);
-        game_board.cells[0][1] = Cell::Hotel(Hotel::Luxor);
+        game_board.set_cell(0, 0, Cell::Hotel(Hotel::Luxor));
+        game_board.set_cell(0, 1, Cell::Hotel(Hotel::Luxor));
 
-        game_board.cells[2][0] = Cell::Hotel(Hotel::Tower);
-        game_board.cells[2][1] = Cell::Hotel(Hotel::Tower);
+        game_board.set_cell(2, 0, Cell::Hotel(Hotel::Tower));
+        game_board.set_cell(2, 1, Cell::Hotel(Hotel::Tower));
 
         let active_hotels = game_board.get_active_hotels();
         let inactive_hotels = game_board.get_inactive_hotels();
 
-        for hotel in vec![Hotel::Luxor, Hotel::Tower] {
-            assert_eq!(active_hotels.contains(&hotel), true);
-            assert_eq!(inactive_hotels.contains(&hotel), false);
+        for hotel in [Hotel::Luxor, Hotel::Tower] {
+            assert!(active_hotels.contains(&hotel));
+            assert!(!inactive_hotels.contains(&hotel));
         }
 
-        for hotel in vec![
+        for hotel in [
             Hotel::American,
             Hotel::Worldwide,
             Hotel::Festival,
             Hotel::Imperial,
             Hotel::Continental,
         ] {
-            assert_eq!(active_hotels.contains(&hotel), false);
-            assert_eq!(inactive_hotels.contains(&hotel), true);
+            assert!(!active_hotels.contains(&hotel));
+            assert!(inactive_hotels.contains(&hotel));
+        }
+    }
+
+    #[test]
+    fn test_can_game_end() {
+        let mut game_board = GameBoard::new();
+        assert!(!game_board.can_game_end());
+
+        // a single safe chain
+        for col in 0..SAFE_CHAIN_SIZE {
+            game_board.set_cell(0, col, Cell::Hotel(Hotel::Luxor));
+        }
+        assert!(game_board.can_game_end());
+
+        // an unsafe chain keeps the game going
+        game_board.set_cell(2, 0, Cell::Hotel(Hotel::Tower));
+        game_board.set_cell(2, 1, Cell::Hotel(Hotel::Tower));
+        assert!(!game_board.can_game_end());
+
+        // unless some chain has reached 41 tiles
+        for row in 4..BOARD_ROWS {
+            for col in 0..BOARD_COLS {
+                game_board.set_cell(row, col, Cell::Hotel(Hotel::Luxor));
+            }
         }
+        assert!(game_board.can_game_end());
     }
 
     #[test]
     fn test_would_cell_start_new_chain() {
         let mut game_board = GameBoard::new();
-        game_board.cells[0][0] = Cell::Independent;
-        assert_eq!(game_board.would_cell_start_new_chain(0, 1), true);
+        game_board.set_cell(0, 0, Cell::Independent);
+        assert!(game_board.would_cell_start_new_chain(0, 1));
     }
 
     #[test]
@@ -429,40 +684,60 @@ mod tests {
         let mut game_board = GameBoard::new();
 
         for i in 0..2 {
-            game_board.cells[0][i] = Cell::Hotel(Hotel::Luxor);
+            game_board.set_cell(0, i, Cell::Hotel(Hotel::Luxor));
         }
 
         for i in 0..4 {
-            game_board.cells[2][i] = Cell::Hotel(Hotel::Tower);
+            game_board.set_cell(2, i, Cell::Hotel(Hotel::Tower));
         }
 
         for i in 0..8 {
-            game_board.cells[4][i] = Cell::Hotel(Hotel::American);
+            game_board.set_cell(4, i, Cell::Hotel(Hotel::American));
         }
 
         assert_eq!(game_board.get_hotel_chain_size(Hotel::Luxor), 2);
         assert_eq!(game_board.get_hotel_chain_size(Hotel::Tower), 4);
         assert_eq!(game_board.get_hotel_chain_size(Hotel::American), 8);
+
+        // cells written directly are only counted once the sizes are recounted
+        game_board.cells[0][2] = Cell::Hotel(Hotel::Luxor);
+        game_board.cells[2][0] = Cell::Empty;
+        game_board.recount_chain_sizes();
+        assert_eq!(game_board.get_hotel_chain_size(Hotel::Luxor), 3);
+        assert_eq!(game_board.get_hotel_chain_size(Hotel::Tower), 3);
+    }
+
+    #[test]
+    fn test_is_chain_safe() {
+        let mut game_board = GameBoard::new();
+        for i in 0..SAFE_CHAIN_SIZE - 1 {
+            game_board.set_cell(0, i, Cell::Hotel(Hotel::Luxor));
+        }
+        assert!(!game_board.is_chain_safe(Hotel::Luxor));
+        assert!(!game_board.is_chain_safe(Hotel::Tower));
+
+        game_board.set_cell(1, 0, Cell::Hotel(Hotel::Luxor));
+        assert!(game_board.is_chain_safe(Hotel::Luxor));
     }
 
     #[test]
     fn test_get_adjacent_hotels() {
         let mut game_board = GameBoard::new();
-        game_board.cells[0][1] = Cell::Hotel(Hotel::Tower);
-        game_board.cells[1][0] = Cell::Hotel(Hotel::Festival);
-        game_board.cells[1][2] = Cell::Hotel(Hotel::Luxor);
+        game_board.set_cell(0, 1, Cell::Hotel(Hotel::Tower));
+        game_board.set_cell(1, 0, Cell::Hotel(Hotel::Festival));
+        game_board.set_cell(1, 2, Cell::Hotel(Hotel::Luxor));
 
         {
             let adjacent_hotels = game_board.get_adjacent_hotels(0, 0);
-            for hotel in vec![Hotel::Tower, Hotel::Festival] {
-                assert_eq!(adjacent_hotels.contains(&hotel), true);
+            for hotel in [Hotel::Tower, Hotel::Festival] {
+                assert!(adjacent_hotels.contains(&hotel));
             }
         }
 
         {
             let adjacent_hotels = game_board.get_adjacent_hotels(1, 1);
-            for hotel in vec![Hotel::Tower, Hotel::Festival, Hotel::Luxor] {
-                assert_eq!(adjacent_hotels.contains(&hotel), true);
+            for hotel in [Hotel::Tower, Hotel::Festival, Hotel::Luxor] {
+                assert!(adjacent_hotels.contains(&hotel));
             }
         }
     }
@@ -476,19 +751,19 @@ mod tests {
         );
         assert_eq!(game_board.is_cell_playable(0, 0), Ok(true));
 
-        game_board.cells[0][0] = Cell::Hotel(Hotel::Luxor);
+        game_board.set_cell(0, 0, Cell::Hotel(Hotel::Luxor));
         assert_eq!(
             game_board.is_cell_playable(0, 0),
             Err(CellNotPlayableReason::CellIsNotEmpty)
         );
 
-        game_board.cells[0][0] = Cell::Conflict(CellConflictType::NewChain);
+        game_board.set_cell(0, 0, Cell::Conflict(CellConflictType::NewChain));
         assert_eq!(
             game_board.is_cell_playable(1, 1),
             Err(CellNotPlayableReason::ConflictOnBoard)
         );
 
-        // test if placing this tile would create an 8th chain
+        // a tile that would found an 8th chain is still playable, it stays independent
         let mut game_board = GameBoard::new();
         for (i, hotel) in [
             Hotel::Luxor,
@@ -501,30 +776,39 @@ mod tests {
         .enumerate()
         {
             for j in 0..2 {
-                game_board.cells[i * 2][j] = Cell::Hotel(*hotel);
+                game_board.set_cell(i * 2, j, Cell::Hotel(*hotel));
             }
         }
 
         for (i, hotel) in [Hotel::Worldwide, Hotel::Continental].iter().enumerate() {
             for j in 0..2 {
-                game_board.cells[i * 2][j + 3] = Cell::Hotel(*hotel);
+                game_board.set_cell(i * 2, j + 3, Cell::Hotel(*hotel));
             }
         }
 
-        game_board.cells[BOARD_ROWS - 1][BOARD_COLS - 1] = Cell::Independent;
+        game_board.set_cell(BOARD_ROWS - 1, BOARD_COLS - 1, Cell::Independent);
         assert_eq!(
             game_board.is_cell_playable(BOARD_ROWS - 2, BOARD_COLS - 1),
-            Err(CellNotPlayableReason::HotelsAreAllActive)
+            Ok(true)
+        );
+        assert!(!game_board.would_cell_start_new_chain(BOARD_ROWS - 2, BOARD_COLS - 1));
+        assert_eq!(
+            game_board.place_tile(BOARD_ROWS - 2, BOARD_COLS - 1),
+            PlaceTileResult::Success
+        );
+        assert_eq!(
+            game_board.cells[BOARD_ROWS - 2][BOARD_COLS - 1],
+            Cell::Independent
         );
 
         // test if placing a tile would merge safe hotel chains
         let mut game_board = GameBoard::new();
         for i in 0..SAFE_CHAIN_SIZE {
-            game_board.cells[0][i] = Cell::Hotel(Hotel::Luxor);
+            game_board.set_cell(0, i, Cell::Hotel(Hotel::Luxor));
         }
 
         for i in 0..SAFE_CHAIN_SIZE {
-            game_board.cells[2][i] = Cell::Hotel(Hotel::Tower);
+            game_board.set_cell(2, i, Cell::Hotel(Hotel::Tower));
         }
 
         assert_eq!(
@@ -533,6 +817,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_only_a_merger_of_two_safe_chains_is_unplayable() {
+        // a safe chain on two sides of the cell is one chain, nothing merges
+        let mut game_board = GameBoard::new();
+        for i in 0..SAFE_CHAIN_SIZE {
+            game_board.set_cell(0, i, Cell::Hotel(Hotel::Luxor));
+        }
+        game_board.set_cell(1, 1, Cell::Hotel(Hotel::Luxor));
+        assert_eq!(game_board.is_cell_playable(1, 0), Ok(true));
+
+        // a safe chain may take over one that is not safe yet
+        for i in 0..SAFE_CHAIN_SIZE - 1 {
+            game_board.set_cell(2, i, Cell::Hotel(Hotel::Tower));
+        }
+        assert_eq!(game_board.is_cell_playable(1, 0), Ok(true));
+
+        game_board.set_cell(2, SAFE_CHAIN_SIZE - 1, Cell::Hotel(Hotel::Tower));
+        assert_eq!(
+            game_board.is_cell_playable(1, 0),
+            Err(CellNotPlayableReason::AdjacentHotelsAreSafe)
+        );
+    }
+
+    #[test]
+    fn test_playable_tiles() {
+        let mut game_board = GameBoard::with_size(2, 3);
+        game_board.set_cell(0, 0, Cell::Independent);
+        assert_eq!(game_board.playable_tiles().len(), 5);
+        assert!(!game_board.playable_tiles().contains(&Tile::from((0, 0))));
+
+        // nothing can be played until a conflict is resolved
+        game_board.set_cell(1, 1, Cell::Conflict(CellConflictType::NewChain));
+        assert!(game_board.playable_tiles().is_empty());
+
+        // a tile between two safe chains is never playable
+        let mut game_board = GameBoard::new();
+        for i in 0..SAFE_CHAIN_SIZE {
+            game_board.set_cell(0, i, Cell::Hotel(Hotel::Luxor));
+            game_board.set_cell(2, i, Cell::Hotel(Hotel::Tower));
+        }
+        let playable_tiles = game_board.playable_tiles();
+        assert!(!playable_tiles.contains(&Tile::from((1, 0))));
+        assert!(playable_tiles.contains(&Tile::from((1, SAFE_CHAIN_SIZE))));
+    }
+
     #[test]
     fn test_place_tile() {
         // basic
@@ -551,13 +880,13 @@ mod tests {
 
         for (i, hotel) in [Hotel::Worldwide, Hotel::Continental].iter().enumerate() {
             for j in 0..2 {
-                game_board.cells[i * 2][j] = Cell::Hotel(*hotel);
+                game_board.set_cell(i * 2, j, Cell::Hotel(*hotel));
             }
         }
 
         for (i, hotel) in [Hotel::Imperial].iter().enumerate() {
             for j in 0..2 {
-                game_board.cells[i * 2 + 1][j + 2] = Cell::Hotel(*hotel);
+                game_board.set_cell(i * 2 + 1, j + 2, Cell::Hotel(*hotel));
             }
         }
         assert_eq!(
@@ -567,10 +896,47 @@ mod tests {
 
         // placing a tile causes a chain to grow
         let mut game_board = GameBoard::new();
-        game_board.cells[0][0] = Cell::Hotel(Hotel::Luxor);
-        game_board.cells[0][2] = Cell::Independent;
-        game_board.cells[1][2] = Cell::Independent;
+        game_board.set_cell(0, 0, Cell::Hotel(Hotel::Luxor));
+        game_board.set_cell(0, 2, Cell::Independent);
+        game_board.set_cell(1, 2, Cell::Independent);
         assert_eq!(game_board.place_tile(0, 1), PlaceTileResult::Success);
         assert_eq!(game_board.get_hotel_chain_size(Hotel::Luxor), 4);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        // a board in the middle of a merger between Luxor and Tower
+        let mut game_board = GameBoard::new();
+        for i in 0..3 {
+            game_board.set_cell(0, i, Cell::Hotel(Hotel::Luxor));
+        }
+        for i in 4..6 {
+            game_board.set_cell(0, i, Cell::Hotel(Hotel::Tower));
+        }
+        game_board.set_cell(0, 3, Cell::Conflict(CellConflictType::Merge(1)));
+        game_board.set_cell(5, 5, Cell::Independent);
+
+        let json = serde_json::to_string(&game_board).unwrap();
+        let deserialized: GameBoard = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, game_board);
+        assert_eq!(
+            deserialized.get_conflict_on_board(),
+            Some((0, 3, CellConflictType::Merge(1)))
+        );
+    }
+
+    #[test]
+    fn test_not_playable_reasons_say_whether_they_last() {
+        assert!(!CellNotPlayableReason::ConflictOnBoard.is_permanent());
+        assert!(CellNotPlayableReason::AdjacentHotelsAreSafe.is_permanent());
+        assert_eq!(
+            CellNotPlayableReason::ConflictOnBoard.as_display_message(),
+            "There is a conflict on the board (try again later)"
+        );
+        assert_eq!(
+            CellNotPlayableReason::AdjacentHotelsAreSafe.as_display_message(),
+            "Adjacent hotels are safe and cannot be merged (permanently dead)"
+        );
+    }
 }
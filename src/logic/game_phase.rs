@@ -0,0 +1,65 @@
+use super::{acquire_game_state::AcquireGameState, hotel_data::Hotel};
+
+// a read-only view of the phase the game is in, copied out of the game's
+// internal state so front ends can show progress without being able to change it
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamePhase {
+    GameStart {
+        players_remaining: usize,
+    },
+    PlayTile {
+        player: usize,
+    },
+    Merger {
+        merge_maker: usize,
+        survivor: Hotel,
+        defunct_remaining: Vec<Hotel>,
+    },
+    DisposeStock {
+        merge_maker: usize,
+        survivor: Hotel,
+        defunct: Hotel,
+        // defunct chains still to be resolved after this one
+        defunct_remaining: Vec<Hotel>,
+    },
+    BuyStock {
+        player: usize,
+        buys_remaining: u32,
+    },
+    EndGame {
+        player: usize,
+    },
+    GameOver,
+}
+
+impl From<&AcquireGameState> for GamePhase {
+    fn from(state: &AcquireGameState) -> Self {
+        match state {
+            AcquireGameState::GameStart(game_start_state) => GamePhase::GameStart {
+                players_remaining: game_start_state.remaining_number_of_players,
+            },
+            AcquireGameState::PlayTile(player) => GamePhase::PlayTile { player: *player },
+            AcquireGameState::Merger(merge_state) => GamePhase::Merger {
+                merge_maker: merge_state.merge_maker,
+                survivor: merge_state.surviving_hotel,
+                defunct_remaining: merge_state.defunct_hotels_remaining.clone(),
+            },
+            AcquireGameState::DisposeStock(dispose_stock_state) => GamePhase::DisposeStock {
+                merge_maker: dispose_stock_state.merger_state.merge_maker,
+                survivor: dispose_stock_state.merger_state.surviving_hotel,
+                defunct: dispose_stock_state.defunct_chain,
+                defunct_remaining: dispose_stock_state
+                    .merger_state
+                    .defunct_hotels_remaining
+                    .clone(),
+            },
+            AcquireGameState::BuyStock(buy_stock_state) => GamePhase::BuyStock {
+                player: buy_stock_state.player,
+                buys_remaining: buy_stock_state.buys_remaining,
+            },
+            AcquireGameState::EndGame(player) => GamePhase::EndGame { player: *player },
+            AcquireGameState::GameOver => GamePhase::GameOver,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use super::{
+    acquire_constants::STARTING_CASH,
+    acquire_game::{AcquireGame, AcquireGameCallback},
+    game_board::{BOARD_COLS, BOARD_ROWS},
+    rule_set::RuleSet,
+};
+
+// everything needed to build the same game again, together with the responses
+// applied so far it reproduces a game exactly
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameSetup {
+    pub seed: u64,
+    pub number_of_players: usize,
+    // anything left out when the setup was written is the standard game
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub names: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default = "standard_starting_cash"))]
+    pub starting_cash: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: RuleSet,
+    #[cfg_attr(feature = "serde", serde(default = "standard_board_rows"))]
+    pub board_rows: usize,
+    #[cfg_attr(feature = "serde", serde(default = "standard_board_cols"))]
+    pub board_cols: usize,
+}
+
+impl GameSetup {
+    // a standard game with default names
+    pub fn new(seed: u64, number_of_players: usize) -> Self {
+        Self {
+            seed,
+            number_of_players,
+            names: Vec::new(),
+            starting_cash: STARTING_CASH,
+            rules: RuleSet::standard(),
+            board_rows: BOARD_ROWS,
+            board_cols: BOARD_COLS,
+        }
+    }
+
+    pub fn build<T: AcquireGameCallback>(
+        &self,
+        message_callback: Arc<T>,
+    ) -> Result<AcquireGame<T>, String> {
+        AcquireGame::try_with_seed(self.number_of_players, message_callback, self.seed)?
+            .with_rules(self.rules)?
+            .with_board_size(self.board_rows, self.board_cols)?
+            .with_player_names(self.names.clone())
+            .map(|game| game.with_starting_cash(self.starting_cash))
+    }
+}
+
+#[cfg(feature = "serde")]
+fn standard_starting_cash() -> u32 {
+    STARTING_CASH
+}
+
+#[cfg(feature = "serde")]
+fn standard_board_rows() -> usize {
+    BOARD_ROWS
+}
+
+#[cfg(feature = "serde")]
+fn standard_board_cols() -> usize {
+    BOARD_COLS
+}
@@ -0,0 +1,14 @@
+use super::{acquire_request::AcquireRequest, game_board::Cell, hotel_data::Hotel, player::Player};
+
+// a plain copy of everything a front end needs to draw a frame,
+// chain sizes are computed up front so rendering never has to scan the board
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameSnapshot {
+    // indexed by row then column
+    pub cells: Vec<Vec<Cell>>,
+    pub players: Vec<Player>,
+    pub available_stock: [u32; Hotel::count()],
+    pub chain_sizes: [usize; Hotel::count()],
+    pub current_request: AcquireRequest,
+}
@@ -1,27 +1,29 @@
-use crate::logic::acquire_constants::STOCK_TO_BUY_PER_TURN;
+use crate::logic::acquire_error::AcquireError;
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BuyStockState {
     pub player: usize,
     pub buys_remaining: u32,
 }
 
 impl BuyStockState {
-    pub fn new(player: usize) -> Self {
+    pub fn new(player: usize, buys_per_turn: u32) -> Self {
         BuyStockState {
             player,
-            buys_remaining: STOCK_TO_BUY_PER_TURN,
+            buys_remaining: buys_per_turn,
         }
     }
 
     // This is called when a player has bought stock
     // It returns true if the player has bought all the stock they can
-    // signaling this state is over
-    pub fn player_has_bought_stock(&mut self) -> bool {
+    // signaling this state is over, a buy past the last one is an error
+    pub fn player_has_bought_stock(&mut self) -> Result<bool, AcquireError> {
         if self.buys_remaining == 0 {
-            panic!("Player cannot buy more stock");
+            return Err(AcquireError::BuyLimitReached);
         }
 
         self.buys_remaining -= 1;
-        self.buys_remaining == 0
+        Ok(self.buys_remaining == 0)
     }
 }
@@ -1,24 +1,34 @@
 use crate::logic::hotel_data::Hotel;
 
+use super::merge_state::MergerState;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DisposeStockState {
-    pub merge_maker: usize,
-    pub surviving_chain: Hotel,
+    // the merger this disposal is part of, carried so it can continue afterwards
+    pub merger_state: MergerState,
     pub defunct_chain: Hotel,
     pub remaining_shares_per_player: Vec<u32>,
+    // who has sold or traded so far, for rules that only allow one of the two
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sold_per_player: Vec<bool>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub traded_per_player: Vec<bool>,
 }
 
 impl DisposeStockState {
     pub fn new(
-        merge_maker: usize,
-        surviving_chain: Hotel,
+        merger_state: MergerState,
         defunct_chain: Hotel,
         remaining_shares_per_player: Vec<u32>,
     ) -> Self {
+        let number_of_players = remaining_shares_per_player.len();
         DisposeStockState {
-            merge_maker,
-            surviving_chain,
+            merger_state,
             defunct_chain,
             remaining_shares_per_player,
+            sold_per_player: vec![false; number_of_players],
+            traded_per_player: vec![false; number_of_players],
         }
     }
 
@@ -26,15 +36,44 @@ impl DisposeStockState {
         self.remaining_shares_per_player[player]
     }
 
+    // disposals saved before this was tracked count as neither
+    pub fn has_sold(&self, player: usize) -> bool {
+        self.sold_per_player.get(player).copied().unwrap_or(false)
+    }
+
+    pub fn has_traded(&self, player: usize) -> bool {
+        self.traded_per_player.get(player).copied().unwrap_or(false)
+    }
+
+    // players dispose of their stock in turn order starting from the merge maker,
+    // those without any shares left are skipped
+    pub fn current_disposer(&self) -> Option<usize> {
+        let number_of_players = self.remaining_shares_per_player.len();
+        (0..number_of_players)
+            .map(|offset| (self.merger_state.merge_maker + offset) % number_of_players)
+            .find(|player| self.remaining_shares_per_player[*player] > 0)
+    }
+
     // This is called when a player has decided what to do with their stock
-    // It returns true if all players have disposed of their stock
+    // once there is no current disposer every player has disposed of their stock
     // signaling this state is over
-    pub fn player_handled_stock(&mut self, player: usize, shares: u32) -> bool {
+    pub fn player_handled_stock(&mut self, player: usize, shares: u32) {
         if shares > self.remaining_shares_per_player[player] {
             panic!("Player cannot dispose of more shares than they have");
         }
 
         self.remaining_shares_per_player[player] -= shares;
-        self.remaining_shares_per_player.iter().all(|&shares| shares == 0)
+    }
+
+    pub fn record_sale(&mut self, player: usize) {
+        self.sold_per_player
+            .resize(self.remaining_shares_per_player.len(), false);
+        self.sold_per_player[player] = true;
+    }
+
+    pub fn record_trade(&mut self, player: usize) {
+        self.traded_per_player
+            .resize(self.remaining_shares_per_player.len(), false);
+        self.traded_per_player[player] = true;
     }
 }
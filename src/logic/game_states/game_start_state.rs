@@ -1,9 +1,14 @@
 use crate::logic::tile::Tile;
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameStartState {
     pub player_with_winning_tile: usize,
     pub winning_tile: Tile,
     pub remaining_number_of_players: usize,
+    // every starting tile drawn so far and who drew it, in draw order
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub starting_tiles: Vec<(usize, Tile)>,
 }
 
 impl GameStartState {
@@ -12,6 +17,7 @@ impl GameStartState {
             player_with_winning_tile: usize::MAX,
             winning_tile: Tile::from((usize::MAX, usize::MAX)),
             remaining_number_of_players: number_of_players,
+            starting_tiles: Vec::new(),
         }
     }
 
@@ -22,7 +28,9 @@ impl GameStartState {
             panic!("GameStartState: All players have already played their tiles");
         }
 
-        if self.winning_tile > tile {
+        self.starting_tiles.push((player, tile));
+
+        if starting_rank(tile) < starting_rank(self.winning_tile) {
             self.winning_tile = tile;
             self.player_with_winning_tile = player;
         }
@@ -31,4 +39,42 @@ impl GameStartState {
 
         self.remaining_number_of_players == 0
     }
+
+    // the starting tiles from the one closest to 1-A to the one furthest from it
+    pub fn sorted_starting_tiles(&self) -> Vec<(usize, Tile)> {
+        let mut starting_tiles = self.starting_tiles.clone();
+        starting_tiles.sort_by_key(|(_, tile)| starting_rank(*tile));
+        starting_tiles
+    }
+}
+
+// the Acquire rules rank starting tiles by number before letter, 1-A, 1-B, ... 1-I, 2-A,
+// so a lower number always goes first however far down its letter is.
+// kept apart from Tile's ordering so the order hands are sorted in cannot change who starts
+fn starting_rank(tile: Tile) -> (usize, usize) {
+    (tile.col, tile.row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lowest_number_starts_before_lowest_letter() {
+        let mut state = GameStartState::new(4);
+        let drawn = ["2-A", "1-I", "3-A", "1-C"];
+        for (player, tile) in drawn.iter().enumerate() {
+            let all_played = state.player_played_tile(player, tile.parse().unwrap());
+            assert_eq!(all_played, player == drawn.len() - 1);
+        }
+
+        assert_eq!(state.player_with_winning_tile, 3);
+        assert_eq!(state.winning_tile, "1-C".parse().unwrap());
+        let order: Vec<usize> = state
+            .sorted_starting_tiles()
+            .iter()
+            .map(|(player, _)| *player)
+            .collect();
+        assert_eq!(order, vec![3, 1, 0, 2]);
+    }
 }
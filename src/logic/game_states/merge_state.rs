@@ -1,5 +1,10 @@
-use crate::logic::{game_board::{CellConflictType, GameBoard}, hotel_data::Hotel};
+use crate::logic::{
+    game_board::{CellConflictType, GameBoard},
+    hotel_data::Hotel,
+};
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MergerState {
     pub merge_maker: usize,
     pub surviving_hotel: Hotel,
@@ -29,7 +34,8 @@ impl MergerState {
     pub fn get_largest_defunct_chains(&self, board: &GameBoard) -> Vec<Hotel> {
         let mut defunct_hotels = self.defunct_hotels_remaining.clone();
 
-        let largest_defunct_chain_size = self.defunct_hotels_remaining
+        let largest_defunct_chain_size = self
+            .defunct_hotels_remaining
             .iter()
             .map(|hotel| board.get_hotel_chain_size(*hotel))
             .max()
@@ -65,6 +71,6 @@ impl MergerState {
         self.hotel_to_merge = Some(hotel);
         self.defunct_hotels_remaining.retain(|h| *h != hotel);
 
-        self.defunct_hotels_remaining.len() == 0
+        self.defunct_hotels_remaining.is_empty()
     }
 }
@@ -0,0 +1,117 @@
+use super::hotel_data::Hotel;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerStats {
+    pub cash: u32,
+    pub net_worth: u32,
+    // the largest single majority or minority bonus, 0 if they were never paid one
+    pub biggest_payout: u32,
+}
+
+// a recap of the game, meant to be shown once it is over
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameStats {
+    pub players: Vec<PlayerStats>,
+    // the shares players held in each hotel, at the end of the game this is what
+    // they held before it was all sold
+    pub shares_held: [u32; Hotel::count()],
+    pub chain_sizes: [usize; Hotel::count()],
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerResult {
+    pub name: String,
+    pub cash: u32,
+    pub net_worth: u32,
+    // once the game is over this is the stock held before it was sold
+    pub stocks: [u32; Hotel::count()],
+}
+
+// the final standings, for tracking results across many games
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameResults {
+    pub seed: u64,
+    pub players: Vec<PlayerResult>,
+    // more than one player when the highest net worth is tied
+    pub winners: Vec<usize>,
+}
+
+impl GameStats {
+    // one line per player followed by one per hotel that was held or on the board
+    pub fn describe(&self, player_names: &[String]) -> Vec<String> {
+        let name = |player: usize| {
+            player_names
+                .get(player)
+                .cloned()
+                .unwrap_or_else(|| format!("Player {}", player + 1))
+        };
+
+        let mut lines: Vec<String> = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(player, stats)| {
+                format!(
+                    "{}: ${} cash, ${} net worth, biggest payout ${}",
+                    name(player),
+                    stats.cash,
+                    stats.net_worth,
+                    stats.biggest_payout
+                )
+            })
+            .collect();
+
+        for hotel in Hotel::iter() {
+            let shares = self.shares_held[hotel as usize];
+            let chain_size = self.chain_sizes[hotel as usize];
+            if shares > 0 || chain_size > 0 {
+                lines.push(format!(
+                    "{}: {} shares held, {} tiles",
+                    hotel, shares, chain_size
+                ));
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        let mut stats = GameStats {
+            players: vec![
+                PlayerStats {
+                    cash: 8000,
+                    net_worth: 8000,
+                    biggest_payout: 3000,
+                },
+                PlayerStats {
+                    cash: 5000,
+                    net_worth: 5600,
+                    biggest_payout: 0,
+                },
+            ],
+            shares_held: [0; Hotel::count()],
+            chain_sizes: [0; Hotel::count()],
+        };
+        stats.shares_held[Hotel::Luxor as usize] = 3;
+        stats.chain_sizes[Hotel::Luxor as usize] = 12;
+
+        let names = vec!["Ann".to_string()];
+        assert_eq!(
+            stats.describe(&names),
+            vec![
+                "Ann: $8000 cash, $8000 net worth, biggest payout $3000",
+                "Player 2: $5000 cash, $5600 net worth, biggest payout $0",
+                "Luxor: 3 shares held, 12 tiles",
+            ]
+        );
+    }
+}
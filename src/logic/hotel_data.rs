@@ -1,6 +1,7 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Hotel {
     Tower,
     Luxor,
@@ -11,36 +12,71 @@ pub enum Hotel {
     Continental,
 }
 
+/// The three price groups of the published Acquire price chart
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PriceGroup {
+    Cheap,
+    Medium,
+    Expensive,
+}
+
+// share prices from the published Acquire price chart, indexed by price group
+// and then by chain length bucket: 2, 3, 4, 5, 6-10, 11-20, 21-30, 31-40, 41+
+const STOCK_PRICE_TABLE: [[u32; 9]; 3] = [
+    [200, 300, 400, 500, 600, 700, 800, 900, 1000],
+    [300, 400, 500, 600, 700, 800, 900, 1000, 1100],
+    [400, 500, 600, 700, 800, 900, 1000, 1100, 1200],
+];
+
+// a label for each chain length bucket of the price chart, with the shortest
+// chain length that falls in it
+pub const CHAIN_LENGTH_BUCKETS: [(&str, usize); 9] = [
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6-10", 6),
+    ("11-20", 11),
+    ("21-30", 21),
+    ("31-40", 31),
+    ("41+", 41),
+];
+
 impl Hotel {
     pub const fn count() -> usize {
         7
     }
 
-    fn get_hotel_row_advantage(&self) -> usize {
+    pub fn get_price_group(&self) -> PriceGroup {
         match self {
-            Hotel::Tower | Hotel::Luxor => 0,
-            Hotel::American | Hotel::Worldwide | Hotel::Festival => 1,
-            Hotel::Imperial | Hotel::Continental => 2,
+            Hotel::Tower | Hotel::Luxor => PriceGroup::Cheap,
+            Hotel::American | Hotel::Worldwide | Hotel::Festival => PriceGroup::Medium,
+            Hotel::Imperial | Hotel::Continental => PriceGroup::Expensive,
         }
     }
 
-    fn get_row_from_chain_length(&self, chain_length: usize) -> u32 {
-        let base_row: u32 = match chain_length {
-            0..=2 => 0,
-            3 => 1,
-            4 => 2,
-            5 => 3,
-            6..=10 => 4,
-            11..=20 => 5,
-            21..=30 => 6,
-            31..=40 => 7,
-            _ => 8,
-        };
-        base_row + self.get_hotel_row_advantage() as u32
+    // returns the column of the price table for a chain of the given length
+    // chains shorter than two tiles are not on the board and have no price
+    fn get_chain_length_bucket(chain_length: usize) -> Option<usize> {
+        match chain_length {
+            0..=1 => None,
+            2 => Some(0),
+            3 => Some(1),
+            4 => Some(2),
+            5 => Some(3),
+            6..=10 => Some(4),
+            11..=20 => Some(5),
+            21..=30 => Some(6),
+            31..=40 => Some(7),
+            _ => Some(8),
+        }
     }
 
     pub fn get_stock_value(&self, chain_length: usize) -> u32 {
-        (self.get_row_from_chain_length(chain_length) + 2) * 100
+        match Hotel::get_chain_length_bucket(chain_length) {
+            Some(bucket) => STOCK_PRICE_TABLE[self.get_price_group() as usize][bucket],
+            None => 0,
+        }
     }
 
     pub fn get_majority_holder_bonus(&self, chain_length: usize) -> u32 {
@@ -92,6 +128,17 @@ impl fmt::Display for Hotel {
     }
 }
 
+// parses a hotel from its name, ignoring case
+impl FromStr for Hotel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Hotel::iter()
+            .find(|hotel| hotel.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("{} is not a hotel", s))
+    }
+}
+
 pub struct HotelIter {
     index: usize,
 }
@@ -131,20 +178,58 @@ mod tests {
     }
 
     #[test]
-    fn test_get_hotel_row_advantage() {
-        assert_eq!(Hotel::Tower.get_hotel_row_advantage(), 0);
-        assert_eq!(Hotel::Luxor.get_hotel_row_advantage(), 0);
-        assert_eq!(Hotel::American.get_hotel_row_advantage(), 1);
-        assert_eq!(Hotel::Worldwide.get_hotel_row_advantage(), 1);
-        assert_eq!(Hotel::Festival.get_hotel_row_advantage(), 1);
-        assert_eq!(Hotel::Imperial.get_hotel_row_advantage(), 2);
-        assert_eq!(Hotel::Continental.get_hotel_row_advantage(), 2);
+    fn test_get_price_group() {
+        assert_eq!(Hotel::Tower.get_price_group(), PriceGroup::Cheap);
+        assert_eq!(Hotel::Luxor.get_price_group(), PriceGroup::Cheap);
+        assert_eq!(Hotel::American.get_price_group(), PriceGroup::Medium);
+        assert_eq!(Hotel::Worldwide.get_price_group(), PriceGroup::Medium);
+        assert_eq!(Hotel::Festival.get_price_group(), PriceGroup::Medium);
+        assert_eq!(Hotel::Imperial.get_price_group(), PriceGroup::Expensive);
+        assert_eq!(Hotel::Continental.get_price_group(), PriceGroup::Expensive);
+    }
+
+    #[test]
+    fn test_get_chain_length_bucket() {
+        assert_eq!(Hotel::get_chain_length_bucket(1), None);
+        assert_eq!(Hotel::get_chain_length_bucket(2), Some(0));
+        assert_eq!(Hotel::get_chain_length_bucket(5), Some(3));
+        assert_eq!(Hotel::get_chain_length_bucket(10), Some(4));
+        assert_eq!(Hotel::get_chain_length_bucket(11), Some(5));
+        assert_eq!(Hotel::get_chain_length_bucket(41), Some(8));
+    }
+
+    #[test]
+    fn test_get_stock_value() {
+        assert_eq!(Hotel::Luxor.get_stock_value(0), 0);
+        assert_eq!(Hotel::Tower.get_stock_value(2), 200);
+        assert_eq!(Hotel::Festival.get_stock_value(2), 300);
+        assert_eq!(Hotel::Imperial.get_stock_value(2), 400);
+        assert_eq!(Hotel::Luxor.get_stock_value(6), 600);
+        assert_eq!(Hotel::American.get_stock_value(6), 700);
+        assert_eq!(Hotel::Worldwide.get_stock_value(20), 800);
+        assert_eq!(Hotel::Tower.get_stock_value(41), 1000);
+        assert_eq!(Hotel::Continental.get_stock_value(41), 1200);
+    }
+
+    #[test]
+    fn test_holder_bonuses() {
+        assert_eq!(Hotel::American.get_majority_holder_bonus(6), 7000);
+        assert_eq!(Hotel::American.get_minority_holder_bonus(6), 3500);
+        assert_eq!(Hotel::Continental.get_majority_holder_bonus(41), 12000);
+        assert_eq!(Hotel::Continental.get_minority_holder_bonus(41), 6000);
+    }
+
+    #[test]
+    fn test_chain_length_buckets_match_the_price_table() {
+        for (bucket, (_, chain_length)) in CHAIN_LENGTH_BUCKETS.iter().enumerate() {
+            assert_eq!(Hotel::get_chain_length_bucket(*chain_length), Some(bucket));
+        }
     }
 
     #[test]
-    fn test_get_row_from_chain_length() {
-        assert_eq!(Hotel::Tower.get_row_from_chain_length(2), 0);
-        assert_eq!(Hotel::American.get_row_from_chain_length(5), 4);
-        assert_eq!(Hotel::Imperial.get_row_from_chain_length(11), 7);
+    fn test_from_str() {
+        assert_eq!("Tower".parse::<Hotel>(), Ok(Hotel::Tower));
+        assert_eq!("continental".parse::<Hotel>(), Ok(Hotel::Continental));
+        assert!("Hilton".parse::<Hotel>().is_err());
     }
 }
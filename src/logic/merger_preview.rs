@@ -0,0 +1,141 @@
+use super::{acquire_event::PayoutKind, hotel_data::Hotel};
+
+// the bonuses a chain would pay out if it went defunct with the board and stock as they are now
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MergerPreview {
+    pub defunct: Hotel,
+    pub majority_holders: Vec<usize>,
+    // empty when the majority is tied or there is only one shareholder
+    pub minority_holders: Vec<usize>,
+    // (player, amount, kind) in the order they would be paid
+    pub payouts: Vec<(usize, u32, PayoutKind)>,
+}
+
+impl MergerPreview {
+    // shares is how many shares of the defunct chain each player holds, by player index
+    pub fn new(defunct: Hotel, chain_size: usize, shares: &[u32]) -> Self {
+        let majority_payout = defunct.get_majority_holder_bonus(chain_size);
+        let minority_payout = defunct.get_minority_holder_bonus(chain_size);
+        let (majority_holders, mut minority_holders) = rank_shareholders(shares);
+
+        let mut payouts = Vec::new();
+        if majority_holders.is_empty() {
+            // no one holds stock, so no bonuses are paid
+        } else if majority_holders.len() > 1 || minority_holders.is_empty() {
+            // a tied majority or a sole stockholder splits both bonuses, no minority is paid
+            minority_holders.clear();
+            let payout = split_payout(
+                majority_payout + minority_payout,
+                majority_holders.len() as u32,
+            );
+            for &player in &majority_holders {
+                payouts.push((player, payout, PayoutKind::MajorityAndMinority));
+            }
+        } else {
+            payouts.push((majority_holders[0], majority_payout, PayoutKind::Majority));
+            let payout = split_payout(minority_payout, minority_holders.len() as u32);
+            for &player in &minority_holders {
+                payouts.push((player, payout, PayoutKind::Minority));
+            }
+        }
+
+        MergerPreview {
+            defunct,
+            majority_holders,
+            minority_holders,
+            payouts,
+        }
+    }
+
+    // what the player would be paid, 0 if they hold no majority or minority
+    pub fn payout_for(&self, player: usize) -> u32 {
+        self.payouts
+            .iter()
+            .filter(|(payee, _, _)| *payee == player)
+            .map(|(_, amount, _)| amount)
+            .sum()
+    }
+}
+
+// the players holding the most and second most shares, by player index,
+// players with no shares are never stockholders, so they are skipped
+fn rank_shareholders(shares: &[u32]) -> (Vec<usize>, Vec<usize>) {
+    let mut max_shares = 0;
+    let mut second_max_shares = 0;
+
+    // Find the highest and second-highest share counts
+    for &held in shares {
+        if held > max_shares {
+            second_max_shares = max_shares;
+            max_shares = held;
+        } else if held > second_max_shares && held < max_shares {
+            second_max_shares = held;
+        }
+    }
+
+    // Classify stockholders into majority and minority, using indices
+    let mut majority_indices = Vec::new();
+    let mut minority_indices = Vec::new();
+    for (index, &held) in shares.iter().enumerate() {
+        if held == 0 {
+            continue;
+        }
+        if held == max_shares {
+            majority_indices.push(index);
+        } else if held == second_max_shares {
+            minority_indices.push(index);
+        }
+    }
+
+    (majority_indices, minority_indices)
+}
+
+// divides a bonus evenly between `ways` players, rounding each share up to the nearest $100
+fn split_payout(total_payout: u32, ways: u32) -> u32 {
+    total_payout.div_ceil(ways).div_ceil(100) * 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_payout() {
+        assert_eq!(split_payout(3000, 1), 3000);
+        assert_eq!(split_payout(3000, 2), 1500);
+        assert_eq!(split_payout(1000, 3), 400);
+        assert_eq!(split_payout(4500, 4), 1200);
+    }
+
+    #[test]
+    fn test_merger_preview() {
+        // Luxor at size 2 pays 2000 and 1000
+        let preview = MergerPreview::new(Hotel::Luxor, 2, &[3, 5, 3, 0]);
+        assert_eq!(preview.majority_holders, vec![1]);
+        assert_eq!(preview.minority_holders, vec![0, 2]);
+        assert_eq!(
+            preview.payouts,
+            vec![
+                (1, 2000, PayoutKind::Majority),
+                (0, 500, PayoutKind::Minority),
+                (2, 500, PayoutKind::Minority),
+            ]
+        );
+        assert_eq!(preview.payout_for(3), 0);
+
+        let tied = MergerPreview::new(Hotel::Luxor, 2, &[4, 4, 1]);
+        assert!(tied.minority_holders.is_empty());
+        assert_eq!(tied.payout_for(0), 1500);
+        assert_eq!(tied.payout_for(2), 0);
+
+        let sole = MergerPreview::new(Hotel::Luxor, 2, &[0, 2]);
+        assert_eq!(
+            sole.payouts,
+            vec![(1, 3000, PayoutKind::MajorityAndMinority)]
+        );
+
+        assert!(MergerPreview::new(Hotel::Luxor, 2, &[0, 0])
+            .payouts
+            .is_empty());
+    }
+}
@@ -7,6 +7,17 @@ pub mod acquire_game;
 pub mod acquire_request;
 pub mod acquire_response;
 pub mod acquire_game_state;
+pub mod acquire_player_strategy;
 pub mod acquire_constants;
+pub mod acquire_event;
+pub mod game_snapshot;
+pub mod game_phase;
+pub mod tile_preview;
+pub mod turn_record;
+pub mod game_stats;
+pub mod rule_set;
+pub mod merger_preview;
+pub mod acquire_error;
 mod game_states;
 
+pub mod game_setup;
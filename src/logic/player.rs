@@ -1,20 +1,29 @@
-use super::{hotel_data::Hotel, tile::Tile};
+use super::{acquire_constants::STARTING_CASH, hotel_data::Hotel, tile::Tile};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Player {
     pub name: String,
     pub stocks: [u32; Hotel::count()],
     pub cash: u32,
     pub tiles: Vec<Tile>,
+    // resigned players hold nothing and their turns are skipped
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub resigned: bool,
 }
 
 impl Player {
     pub fn new(name: &str) -> Self {
+        Self::with_cash(name, STARTING_CASH)
+    }
+
+    pub fn with_cash(name: &str, cash: u32) -> Self {
         Self {
             name: name.to_string(),
             stocks: [0; Hotel::count()],
-            cash: 6000,
+            cash,
             tiles: Vec::new(),
+            resigned: false,
         }
     }
 }
@@ -0,0 +1,73 @@
+use super::{acquire_constants::STOCK_TO_BUY_PER_TURN, game_board::SAFE_CHAIN_SIZE};
+
+// the house rules a game is played with, standard gives the published rules
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleSet {
+    // the most shares a player may buy on their turn
+    pub buys_per_turn: u32,
+    // defunct shares traded in for each share of the survivor
+    pub trade_ratio: u32,
+    // whether a player may both sell and trade their shares in the same disposal
+    pub allow_sell_and_trade: bool,
+    // chains this large can no longer be merged into another
+    pub safe_chain_size: usize,
+}
+
+impl RuleSet {
+    pub fn standard() -> Self {
+        RuleSet {
+            buys_per_turn: STOCK_TO_BUY_PER_TURN,
+            trade_ratio: 2,
+            allow_sell_and_trade: true,
+            safe_chain_size: SAFE_CHAIN_SIZE,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.buys_per_turn == 0 {
+            return Err("Players must be able to buy at least 1 stock per turn".to_string());
+        }
+        if self.trade_ratio == 0 {
+            return Err("At least 1 share must be traded for each share received".to_string());
+        }
+        if self.safe_chain_size < 2 {
+            return Err("A safe chain must be at least 2 tiles long".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(RuleSet::standard().validate(), Ok(()));
+        assert_eq!(RuleSet::default(), RuleSet::standard());
+
+        for rules in [
+            RuleSet {
+                buys_per_turn: 0,
+                ..RuleSet::standard()
+            },
+            RuleSet {
+                trade_ratio: 0,
+                ..RuleSet::standard()
+            },
+            RuleSet {
+                safe_chain_size: 1,
+                ..RuleSet::standard()
+            },
+        ] {
+            assert!(rules.validate().is_err());
+        }
+    }
+}
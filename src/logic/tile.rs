@@ -1,11 +1,27 @@
 use core::fmt;
+use std::str::FromStr;
+
+use super::game_board::{BOARD_COLS, BOARD_ROWS};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tile {
     pub row: usize,
     pub col: usize,
 }
 
+impl Tile {
+    // every tile on a standard board, row by row
+    pub fn all() -> impl Iterator<Item = Tile> {
+        (0..BOARD_ROWS).flat_map(|row| (0..BOARD_COLS).map(move |col| Tile::from((row, col))))
+    }
+
+    // whether the tile is on a standard board, use GameBoard::contains for other sizes
+    pub fn is_on_board(&self) -> bool {
+        self.row < BOARD_ROWS && self.col < BOARD_COLS
+    }
+}
+
 impl From<(usize, usize)> for Tile {
     fn from(value: (usize, usize)) -> Self {
         Self {
@@ -15,11 +31,60 @@ impl From<(usize, usize)> for Tile {
     }
 }
 
+// rows are lettered like spreadsheet columns: A-Z, then AA, AB and so on
+pub fn row_label(row: usize) -> String {
+    let mut label = String::new();
+    let mut remaining = row + 1;
+    while remaining > 0 {
+        remaining -= 1;
+        label.insert(0, (b'A' + (remaining % 26) as u8) as char);
+        remaining /= 26;
+    }
+    label
+}
+
+fn parse_row_label(label: &str) -> Option<usize> {
+    if label.is_empty() || !label.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    label
+        .chars()
+        .try_fold(0usize, |row, letter| {
+            let digit = (letter.to_ascii_uppercase() as u8 - b'A') as usize + 1;
+            row.checked_mul(26)?.checked_add(digit)
+        })
+        .map(|row| row - 1)
+}
+
 impl fmt::Display for Tile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // row is a letter, col is a number
-        // use the ASCII value of 'A' to get the letter
-        write!(f, "{}-{}", self.col + 1, (b'A' + self.row as u8) as char)
+        write!(f, "{}-{}", self.col + 1, row_label(self.row))
+    }
+}
+
+// parses the display form of a tile, e.g. "3-A"
+impl FromStr for Tile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (col, row) = s
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| format!("Tile {} should look like 3-A", s))?;
+
+        let col: usize = col
+            .parse()
+            .map_err(|_| format!("Tile {} has an invalid column", s))?;
+
+        let row = parse_row_label(row).ok_or_else(|| format!("Tile {} has an invalid row", s))?;
+
+        if col == 0 {
+            return Err(format!("Tile {} has an invalid column", s));
+        }
+
+        Ok(Tile::from((row, col - 1)))
     }
 }
 
@@ -38,3 +103,53 @@ impl Ord for Tile {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all() {
+        let tiles: Vec<Tile> = Tile::all().collect();
+        assert_eq!(tiles.len(), BOARD_ROWS * BOARD_COLS);
+        assert_eq!(tiles.first(), Some(&Tile::from((0, 0))));
+        assert_eq!(
+            tiles.last(),
+            Some(&Tile::from((BOARD_ROWS - 1, BOARD_COLS - 1)))
+        );
+        assert!(tiles.iter().all(Tile::is_on_board));
+
+        assert!(!Tile::from((BOARD_ROWS, 0)).is_on_board());
+        assert!(!Tile::from((0, BOARD_COLS)).is_on_board());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("1-A".parse::<Tile>(), Ok(Tile::from((0, 0))));
+        assert_eq!("12-i".parse::<Tile>(), Ok(Tile::from((8, 11))));
+        assert!("0-A".parse::<Tile>().is_err());
+        assert!("A-1".parse::<Tile>().is_err());
+        assert!("3A".parse::<Tile>().is_err());
+
+        let tile = Tile::from((4, 6));
+        assert_eq!(tile.to_string().parse::<Tile>(), Ok(tile));
+
+        // rows past the standard board keep counting like spreadsheet columns
+        assert_eq!("1-J".parse::<Tile>(), Ok(Tile::from((9, 0))));
+        assert_eq!("2-ab".parse::<Tile>(), Ok(Tile::from((27, 1))));
+        assert!("1-A1".parse::<Tile>().is_err());
+        let tile = Tile::from((30, 14));
+        assert_eq!(tile.to_string(), "15-AE");
+        assert_eq!(tile.to_string().parse::<Tile>(), Ok(tile));
+    }
+
+    #[test]
+    fn test_row_label() {
+        assert_eq!(row_label(0), "A");
+        assert_eq!(row_label(8), "I");
+        assert_eq!(row_label(25), "Z");
+        assert_eq!(row_label(26), "AA");
+        assert_eq!(row_label(701), "ZZ");
+        assert_eq!(row_label(702), "AAA");
+    }
+}
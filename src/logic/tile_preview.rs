@@ -0,0 +1,16 @@
+use super::{game_board::CellNotPlayableReason, hotel_data::Hotel};
+
+// what would happen if a tile were played on the board as it is now
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TilePreview {
+    Independent,
+    GrowsChain(Hotel),
+    FoundsChain,
+    Merges {
+        // the largest adjacent chains, one of which will survive
+        survivor_candidates: Vec<Hotel>,
+        // the smaller adjacent chains, which are certain to be taken over
+        defunct: Vec<Hotel>,
+    },
+    Illegal(CellNotPlayableReason),
+}
@@ -0,0 +1,152 @@
+use super::{acquire_event::AcquireEvent, hotel_data::Hotel, tile::Tile};
+
+// a one line summary of a player's turn, like an entry in a chess move list
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TurnRecord {
+    // turns are numbered from 1
+    pub turn: usize,
+    pub player: usize,
+    pub tile: Option<Tile>,
+    pub chain_founded: Option<Hotel>,
+    // the survivor and the defunct chain of every takeover caused this turn
+    pub mergers: Vec<(Hotel, Hotel)>,
+    // how many shares of each hotel were bought, in the order they were first bought
+    pub stock_bought: Vec<(Hotel, u32)>,
+    // every bonus paid out this turn, as the player and the amount
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub payouts: Vec<(usize, u32)>,
+}
+
+impl TurnRecord {
+    // summarizes the events that happened during the player's turn
+    pub fn from_events(turn: usize, player: usize, events: &[AcquireEvent]) -> Self {
+        let mut record = TurnRecord {
+            turn,
+            player,
+            tile: None,
+            chain_founded: None,
+            mergers: Vec::new(),
+            stock_bought: Vec::new(),
+            payouts: Vec::new(),
+        };
+
+        for event in events {
+            match *event {
+                AcquireEvent::TilePlaced { tile, .. } => record.tile = Some(tile),
+                AcquireEvent::ChainFounded { hotel, .. } => record.chain_founded = Some(hotel),
+                AcquireEvent::MergerStarted { survivor, defunct } => {
+                    record.mergers.push((survivor, defunct))
+                }
+                AcquireEvent::StockBought { hotel, .. } => {
+                    match record.stock_bought.iter_mut().find(|(h, _)| *h == hotel) {
+                        Some((_, shares)) => *shares += 1,
+                        None => record.stock_bought.push((hotel, 1)),
+                    }
+                }
+                AcquireEvent::PayoutAwarded { player, amount, .. } => {
+                    record.payouts.push((player, amount))
+                }
+                _ => {}
+            }
+        }
+
+        record
+    }
+
+    // e.g. "12. Player 2 5-C founds Luxor, buys 2 Luxor"
+    pub fn describe(&self, player_names: &[String]) -> String {
+        let name = player_names
+            .get(self.player)
+            .cloned()
+            .unwrap_or_else(|| format!("Player {}", self.player + 1));
+
+        let mut actions = Vec::new();
+        if let Some(hotel) = self.chain_founded {
+            actions.push(format!("founds {}", hotel));
+        }
+        for (survivor, defunct) in &self.mergers {
+            actions.push(format!("merges {} into {}", defunct, survivor));
+        }
+        if !self.stock_bought.is_empty() {
+            let stock = self
+                .stock_bought
+                .iter()
+                .map(|(hotel, shares)| format!("{} {}", shares, hotel))
+                .collect::<Vec<_>>()
+                .join(" and ");
+            actions.push(format!("buys {}", stock));
+        }
+
+        let tile = self
+            .tile
+            .map(|tile| tile.to_string())
+            .unwrap_or_else(|| "--".to_string());
+        let mut description = format!("{}. {} {}", self.turn, name, tile);
+        if !actions.is_empty() {
+            description.push(' ');
+            description.push_str(&actions.join(", "));
+        }
+        description
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::acquire_event::PayoutKind;
+
+    #[test]
+    fn test_from_events_and_describe() {
+        let events = [
+            AcquireEvent::TilePlaced {
+                player: 1,
+                tile: Tile::from((2, 4)),
+            },
+            AcquireEvent::MergerStarted {
+                survivor: Hotel::Luxor,
+                defunct: Hotel::Tower,
+            },
+            AcquireEvent::PayoutAwarded {
+                player: 0,
+                amount: 2000,
+                kind: PayoutKind::Majority,
+            },
+            AcquireEvent::StockBought {
+                player: 1,
+                hotel: Hotel::Luxor,
+                price: 600,
+            },
+            AcquireEvent::StockBought {
+                player: 1,
+                hotel: Hotel::American,
+                price: 300,
+            },
+            AcquireEvent::StockBought {
+                player: 1,
+                hotel: Hotel::Luxor,
+                price: 600,
+            },
+        ];
+
+        let record = TurnRecord::from_events(7, 1, &events);
+        assert_eq!(record.tile, Some(Tile::from((2, 4))));
+        assert_eq!(record.chain_founded, None);
+        assert_eq!(record.mergers, vec![(Hotel::Luxor, Hotel::Tower)]);
+        assert_eq!(record.payouts, vec![(0, 2000)]);
+        assert_eq!(
+            record.stock_bought,
+            vec![(Hotel::Luxor, 2), (Hotel::American, 1)]
+        );
+
+        let names = vec!["Ann".to_string(), "Bob".to_string()];
+        assert_eq!(
+            record.describe(&names),
+            "7. Bob 5-C merges Tower into Luxor, buys 2 Luxor and 1 American"
+        );
+        assert_eq!(
+            TurnRecord::from_events(8, 0, &[]).describe(&names),
+            "8. Ann --"
+        );
+    }
+}
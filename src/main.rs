@@ -1,10 +1,25 @@
-use std::io::Result;
+use std::{io::Result, sync::Arc};
 
+use actuire::logic::acquire_game::{AcquireGame, AcquireGameCallback};
+#[cfg(feature = "serde")]
+use actuire::logic::{game_setup::GameSetup, rule_set::RuleSet};
+use message_log::MessageLog;
 use structopt::StructOpt;
-use visuals::{tui, tui_app::TuiApp};
+use visuals::{
+    key_bindings::KeyBindings,
+    palette::Palette,
+    tui,
+    tui_app::{AcquireMessages, TuiApp, TuiMessages},
+};
 
-pub mod logic;
-pub mod visuals;
+mod daily;
+mod headless;
+mod message_log;
+#[cfg(feature = "serde")]
+mod network;
+mod visuals;
+#[cfg(feature = "websocket")]
+mod websocket;
 
 #[derive(Debug, structopt::StructOpt)]
 struct Opt {
@@ -19,20 +34,259 @@ struct Opt {
     /// The height of each cell.
     #[structopt(short = "-H", long, default_value = "3")]
     cell_height: u16,
+
+    /// The seed for tile draws, a random seed is used if not given.
+    #[structopt(long)]
+    seed: Option<u64>,
+
+    /// Use the same seed as everyone else playing today (UTC).
+    #[structopt(long, conflicts_with = "seed")]
+    daily: bool,
+
+    /// The number of players controlled by the computer, taken from the end of the turn order.
+    #[structopt(long, default_value = "0")]
+    bots: usize,
+
+    /// The most stock a player may buy each turn.
+    #[structopt(long, default_value = "3")]
+    buys_per_turn: u32,
+
+    /// The player names in turn order, separated by commas.
+    #[structopt(long, use_delimiter = true)]
+    names: Vec<String>,
+
+    /// The cash each player starts with.
+    #[structopt(long, default_value = "6000")]
+    starting_cash: u32,
+
+    /// The hotel colors to use, default or colorblind.
+    #[structopt(long, default_value = "default")]
+    palette: Palette,
+
+    /// Always view the player whose turn it is, for hot-seat play.
+    #[structopt(long)]
+    follow: bool,
+
+    /// Practice against the computer: a 2 player game where player 2 is played by the computer, pausing after each of its moves.
+    #[structopt(long, conflicts_with_all = &["spectate", "headless", "bots", "players"])]
+    practice: bool,
+
+    /// Watch the computer play every seat, with all players shown at once.
+    #[structopt(long, conflicts_with = "headless")]
+    spectate: bool,
+
+    /// Hide the cash of players you do not control, as in the board game.
+    #[structopt(long)]
+    hidden_cash: bool,
+
+    /// Play without the TUI, reading commands from stdin.
+    #[structopt(long)]
+    headless: bool,
+
+    /// Seconds each player has to respond before a default response is made for them.
+    #[structopt(long)]
+    turn_seconds: Option<u64>,
+
+    /// Append every message the game shows to this file, with the time it was shown.
+    #[structopt(long, parse(from_os_str), conflicts_with = "headless")]
+    log: Option<std::path::PathBuf>,
+
+    /// A TOML file of key bindings, e.g. quit = "x", for the keys you want to move.
+    #[structopt(long, parse(from_os_str))]
+    keys: Option<std::path::PathBuf>,
+
+    /// Start from a game snapshot saved as JSON, to reproduce a position.
+    #[cfg(feature = "serde")]
+    #[structopt(long, parse(from_os_str))]
+    snapshot: Option<std::path::PathBuf>,
+
+    /// Host a network game at this address, e.g. 0.0.0.0:4000.
+    #[cfg(feature = "serde")]
+    #[structopt(long, conflicts_with = "connect")]
+    serve: Option<String>,
+
+    /// Expose a game to browsers over WebSockets at this address.
+    #[cfg(feature = "websocket")]
+    #[structopt(long, conflicts_with_all = &["serve", "connect"])]
+    ws: Option<String>,
+
+    /// Join the network game hosted at this address.
+    #[cfg(feature = "serde")]
+    #[structopt(long, requires = "player")]
+    connect: Option<String>,
+
+    /// The player to join a network game as, starting from 1.
+    #[cfg(feature = "serde")]
+    #[structopt(long)]
+    player: Option<usize>,
+
+    /// The token given when first joining, to take the seat back after a disconnect.
+    #[cfg(feature = "serde")]
+    #[structopt(long, requires = "connect")]
+    token: Option<u64>,
+}
+
+// sets up a game with the options given on the command line
+fn new_game<T: AcquireGameCallback>(
+    opt: &Opt,
+    seed: Option<u64>,
+    message_callback: Arc<T>,
+) -> std::result::Result<AcquireGame<T>, String> {
+    #[cfg(feature = "serde")]
+    if let Some(path) = &opt.snapshot {
+        let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let snapshot = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        return AcquireGame::from_snapshot(snapshot, message_callback)
+            .map_err(|violations| violations.join("\n"));
+    }
+
+    let game = match seed {
+        Some(seed) => AcquireGame::try_with_seed(opt.players, message_callback, seed)?,
+        None => AcquireGame::try_new(opt.players, message_callback)?,
+    };
+    game.with_player_names(opt.names.clone())?
+        .with_starting_cash(opt.starting_cash)
+        .with_buys_per_turn(opt.buys_per_turn)
+}
+
+// the setup a server shares with its clients so they build the same game
+#[cfg(feature = "serde")]
+fn game_setup(opt: &Opt, seed: Option<u64>) -> GameSetup {
+    GameSetup {
+        names: opt.names.clone(),
+        starting_cash: opt.starting_cash,
+        rules: RuleSet {
+            buys_per_turn: opt.buys_per_turn,
+            ..RuleSet::standard()
+        },
+        ..GameSetup::new(seed.unwrap_or_else(rand::random), opt.players)
+    }
 }
 
 fn main() -> Result<()> {
-    let Opt {
-        players,
-        cell_width,
-        cell_height,
-    } = Opt::from_args();
+    let mut opt = Opt::from_args();
+    if opt.practice {
+        opt.players = 2;
+        opt.bots = 1;
+        opt.follow = true;
+    }
+    if opt.bots > opt.players {
+        exit_with_error(&format!(
+            "--bots cannot be more than the {} players",
+            opt.players
+        ));
+    }
+
+    let keys = match &opt.keys {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| KeyBindings::from_toml(&text))
+            .unwrap_or_else(|e| exit_with_error(&format!("{}: {}", path.display(), e))),
+        None => KeyBindings::default(),
+    };
+
+    let daily_date = opt.daily.then(daily::today);
+    let seed = match &daily_date {
+        Some(date) => Some(daily::seed_for_date(date)),
+        None => opt.seed,
+    };
+
+    #[cfg(feature = "serde")]
+    if let Some(addr) = &opt.serve {
+        return network::serve(addr, game_setup(&opt, seed), opt.bots);
+    }
+
+    #[cfg(feature = "websocket")]
+    if let Some(addr) = &opt.ws {
+        return websocket::serve(addr, game_setup(&opt, seed), opt.bots);
+    }
+
+    #[cfg(feature = "serde")]
+    if let Some(addr) = &opt.connect {
+        let player = opt
+            .player
+            .filter(|player| *player > 0)
+            .unwrap_or_else(|| exit_with_error("--player must be at least 1"));
+        let (remote, setup, history, snapshot) =
+            network::RemoteGame::connect(addr, player - 1, opt.token)
+                .unwrap_or_else(|e| exit_with_error(&e.to_string()));
+
+        let acquire_messages = Arc::new(tui_messages(&opt));
+        let mut acquire_game = setup
+            .build(acquire_messages.clone())
+            .unwrap_or_else(|e| exit_with_error(&e));
+        for response in history {
+            acquire_game
+                .handle_player_response(response)
+                .unwrap_or_else(|e| exit_with_error(&e.to_string()));
+        }
+        if acquire_game.snapshot() != snapshot {
+            exit_with_error("This game is out of sync with the server");
+        }
+        acquire_messages.send_message(&format!(
+            "If you are disconnected, rejoin with --token {}",
+            remote.token
+        ));
+
+        let mut terminal = tui::init()?;
+        let mut tui_app = TuiApp::builder(acquire_game)
+            .cell_size(opt.cell_width, opt.cell_height)
+            .palette(opt.palette)
+            .turn_seconds(opt.turn_seconds)
+            .hidden_cash(opt.hidden_cash)
+            .key_bindings(keys)
+            .remote(remote)
+            .build();
+
+        let app_result = tui_app.run(&mut terminal);
+        tui::restore()?;
+
+        return app_result;
+    }
+
+    if opt.headless {
+        let messages = Arc::new(headless::StdoutMessages::default());
+        let game = new_game(&opt, seed, messages.clone()).unwrap_or_else(|e| exit_with_error(&e));
+        return headless::run(game, messages, opt.bots);
+    }
+
+    let acquire_messages = Arc::new(tui_messages(&opt));
+    let acquire_game =
+        new_game(&opt, seed, acquire_messages).unwrap_or_else(|e| exit_with_error(&e));
 
     let mut terminal = tui::init()?;
-    let mut tui_app = TuiApp::new(cell_width, cell_height, players);
+    let mut tui_app = TuiApp::builder(acquire_game)
+        .cell_size(opt.cell_width, opt.cell_height)
+        .palette(opt.palette)
+        .daily_date(daily_date)
+        .bots(match opt.spectate {
+            true => opt.players,
+            false => opt.bots,
+        })
+        .follow_active_player(opt.follow)
+        .spectate(opt.spectate)
+        .pace_bots(opt.practice)
+        .hidden_cash(opt.hidden_cash)
+        .turn_seconds(opt.turn_seconds)
+        .key_bindings(keys)
+        .build();
 
     let app_result = tui_app.run(&mut terminal);
     tui::restore()?;
 
     app_result
 }
+
+// the messages shown in the TUI, teed to the --log file when one is given
+fn tui_messages(opt: &Opt) -> TuiMessages {
+    match &opt.log {
+        Some(path) => MessageLog::open(path, AcquireMessages::default())
+            .unwrap_or_else(|e| exit_with_error(&format!("{}: {}", path.display(), e))),
+        None => MessageLog::new(AcquireMessages::default()),
+    }
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("error: {}", message);
+    std::process::exit(1);
+}
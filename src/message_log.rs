@@ -0,0 +1,131 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use actuire::logic::{
+    acquire_event::AcquireEvent, acquire_game::AcquireGameCallback, acquire_request::AcquireRequest,
+};
+
+use crate::daily;
+
+// passes everything the game reports on to another callback, and when opened on a file
+// also appends every message and event to it with the time it happened
+#[derive(Default)]
+pub struct MessageLog<T: AcquireGameCallback> {
+    inner: T,
+    file: Option<Mutex<File>>,
+}
+
+impl<T: AcquireGameCallback> MessageLog<T> {
+    // forwards to the inner callback without writing anything
+    pub fn new(inner: T) -> Self {
+        MessageLog { inner, file: None }
+    }
+
+    // lines are added to the end of the file, so earlier games are kept
+    pub fn open(path: &Path, inner: T) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(MessageLog {
+            inner,
+            file: Some(Mutex::new(file)),
+        })
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    fn write_line(&self, line: &str) {
+        if let Some(file) = &self.file {
+            // a log that cannot be written to should not stop the game
+            let _ = writeln!(file.lock().unwrap(), "[{}] {}", timestamp(), line);
+        }
+    }
+}
+
+impl<T: AcquireGameCallback> AcquireGameCallback for MessageLog<T> {
+    fn send_message(&self, message: &str) {
+        self.inner.send_message(message);
+        self.write_line(message);
+    }
+
+    // events only carry player indices, so they are written as they are
+    fn on_event(&self, event: &AcquireEvent) {
+        self.inner.on_event(event);
+        self.write_line(&format!("{:?}", event));
+    }
+
+    fn on_request_changed(&self, old: &AcquireRequest, new: &AcquireRequest) {
+        self.inner.on_request_changed(old, new);
+    }
+}
+
+// the current time in UTC as YYYY-MM-DD HH:MM:SS
+fn timestamp() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    format_timestamp(seconds)
+}
+
+fn format_timestamp(seconds: u64) -> String {
+    let time_of_day = seconds % 86_400;
+    format!(
+        "{} {:02}:{:02}:{:02}",
+        daily::date_from_days(seconds / 86_400),
+        time_of_day / 3600,
+        time_of_day % 3600 / 60,
+        time_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Collected {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl AcquireGameCallback for Collected {
+        fn send_message(&self, message: &str) {
+            self.messages.lock().unwrap().push(message.to_string());
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(
+            format_timestamp(1_704_067_200 + 45_296),
+            "2024-01-01 12:34:56"
+        );
+    }
+
+    #[test]
+    fn test_messages_are_forwarded_and_appended() {
+        let path = std::env::temp_dir().join(format!("actuire-log-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        for message in ["first game", "second game"] {
+            let log = MessageLog::open(&path, Collected::default()).unwrap();
+            log.send_message(message);
+            assert_eq!(*log.inner().messages.lock().unwrap(), vec![message]);
+        }
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with('[') && lines[0].ends_with("] first game"));
+        assert!(lines[1].ends_with("] second game"));
+    }
+}
@@ -0,0 +1,587 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+};
+
+use actuire::logic::{
+    acquire_game::AcquireGame,
+    acquire_player_strategy::{AcquirePlayerStrategy, GreedyBot},
+    acquire_request::AcquireRequest,
+    acquire_response::{AcquirePlayerResponse, AcquireResponse},
+    game_setup::GameSetup,
+    game_snapshot::GameSnapshot,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::headless::StdoutMessages;
+
+// sent by a client, one JSON object per line
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum ClientMessage {
+    // the first line of every connection claims a seat, a seat that was claimed
+    // before can only be taken back with the token it was issued
+    Join {
+        player: usize,
+        #[serde(default)]
+        token: Option<u64>,
+    },
+    Response(AcquireResponse),
+}
+
+// sent by the server, one JSON object per line
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum ServerMessage {
+    // the seat was taken, the history brings the client's copy of the game up to date
+    // and the token lets the player take the seat back if they are disconnected
+    Welcome {
+        player: usize,
+        token: u64,
+        setup: GameSetup,
+        history: Vec<AcquirePlayerResponse>,
+        snapshot: GameSnapshot,
+    },
+    // a response the server applied, with the state of the game afterwards
+    Applied {
+        response: AcquirePlayerResponse,
+        request: AcquireRequest,
+        snapshot: GameSnapshot,
+    },
+    // the client's last message was rejected
+    Error {
+        message: String,
+    },
+    // responses are refused until these players connect
+    Paused {
+        waiting_for: Vec<usize>,
+    },
+    Resumed,
+}
+
+fn send_line<M: Serialize>(stream: &mut TcpStream, message: &M) -> io::Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())
+}
+
+fn parse_line<M: DeserializeOwned>(line: io::Result<String>) -> io::Result<M> {
+    Ok(serde_json::from_str(&line?)?)
+}
+
+// what the connection threads tell the game loop, connections are numbered
+// so a rejected connection closing is never mistaken for a seated player leaving
+enum ServerEvent {
+    Joined(usize, usize, Option<u64>, TcpStream),
+    Message(usize, ClientMessage),
+    Left(usize),
+}
+
+// reads a connection's lines until it closes, the first must claim a seat
+fn read_connection(connection: usize, stream: TcpStream, events: Sender<ServerEvent>) {
+    let mut lines = BufReader::new(match stream.try_clone() {
+        Ok(reader) => reader,
+        Err(_) => return,
+    })
+    .lines();
+
+    match lines.next().map(parse_line) {
+        Some(Ok(ClientMessage::Join { player, token })) => {
+            if events
+                .send(ServerEvent::Joined(connection, player, token, stream))
+                .is_err()
+            {
+                return;
+            }
+        }
+        _ => {
+            let _ = send_line(
+                &mut { stream },
+                &ServerMessage::Error {
+                    message: "The first message must join as a player".to_string(),
+                },
+            );
+            return;
+        }
+    }
+
+    for message in lines.map(parse_line::<ClientMessage>) {
+        match message {
+            Ok(message) => {
+                if events
+                    .send(ServerEvent::Message(connection, message))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            Err(_) => break,
+        }
+    }
+    let _ = events.send(ServerEvent::Left(connection));
+}
+
+// runs the game at addr until it is over, each human player connects with --connect
+pub fn serve(addr: impl ToSocketAddrs, setup: GameSetup, number_of_bots: usize) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    println!("Serving Acquire on {}", listener.local_addr()?);
+    run_server(listener, setup, number_of_bots)
+}
+
+struct Server {
+    game: AcquireGame<StdoutMessages>,
+    setup: GameSetup,
+    bots: Vec<Option<GreedyBot>>,
+    // the connection and stream of the client in each seat
+    seats: Vec<Option<(usize, TcpStream)>>,
+    // issued when a seat is first claimed, None until then
+    tokens: Vec<Option<u64>>,
+}
+
+fn run_server(listener: TcpListener, setup: GameSetup, number_of_bots: usize) -> io::Result<()> {
+    let messages = Arc::new(StdoutMessages::default());
+    let game = setup
+        .build(messages.clone())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    messages.set_player_names(game.players.iter().map(|p| p.name.clone()).collect());
+
+    let number_of_players = setup.number_of_players;
+    let mut server = Server {
+        game,
+        setup,
+        bots: (0..number_of_players)
+            .map(|player| {
+                (player + number_of_bots >= number_of_players).then(|| GreedyBot::new(player))
+            })
+            .collect(),
+        seats: (0..number_of_players).map(|_| None).collect(),
+        tokens: vec![None; number_of_players],
+    };
+
+    let (events, received_events) = mpsc::channel();
+    thread::spawn(move || {
+        for (connection, stream) in listener.incoming().enumerate() {
+            let Ok(stream) = stream else { continue };
+            let events = events.clone();
+            thread::spawn(move || read_connection(connection, stream, events));
+        }
+    });
+
+    // the game itself only ever runs on this thread
+    for event in received_events {
+        match event {
+            ServerEvent::Joined(connection, player, token, stream) => {
+                server.join(connection, player, token, stream)
+            }
+            ServerEvent::Message(connection, ClientMessage::Response(response)) => {
+                server.respond(connection, response)
+            }
+            ServerEvent::Message(connection, ClientMessage::Join { .. }) => server.send_to(
+                connection,
+                &ServerMessage::Error {
+                    message: "You have already joined".to_string(),
+                },
+            ),
+            ServerEvent::Left(connection) => server.leave(connection),
+        }
+
+        if server.game.is_game_over() {
+            println!("The game is over, shutting down");
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+impl Server {
+    // the game does not start until every human player has joined, after that
+    // it only waits for the disconnected players it needs a response from
+    fn waiting_for(&self) -> Vec<usize> {
+        let disconnected =
+            |player: &usize| self.bots[*player].is_none() && self.seats[*player].is_none();
+
+        let never_joined: Vec<_> = (0..self.seats.len())
+            .filter(|player| disconnected(player) && self.tokens[*player].is_none())
+            .collect();
+        if !never_joined.is_empty() {
+            return never_joined;
+        }
+        self.game
+            .get_players_to_respond()
+            .into_iter()
+            .filter(disconnected)
+            .collect()
+    }
+
+    fn broadcast_if_paused(&mut self) {
+        let waiting_for = self.waiting_for();
+        if !waiting_for.is_empty() {
+            self.broadcast(&ServerMessage::Paused { waiting_for });
+        }
+    }
+
+    fn seat_of(&self, connection: usize) -> Option<usize> {
+        self.seats
+            .iter()
+            .position(|seat| matches!(seat, Some((c, _)) if *c == connection))
+    }
+
+    fn send_to(&mut self, connection: usize, message: &ServerMessage) {
+        if let Some(player) = self.seat_of(connection) {
+            if let Some((_, stream)) = &mut self.seats[player] {
+                // a failed write shows up as the connection leaving
+                let _ = send_line(stream, message);
+            }
+        }
+    }
+
+    fn broadcast(&mut self, message: &ServerMessage) {
+        for (_, stream) in self.seats.iter_mut().flatten() {
+            let _ = send_line(stream, message);
+        }
+    }
+
+    fn join(
+        &mut self,
+        connection: usize,
+        player: usize,
+        token: Option<u64>,
+        mut stream: TcpStream,
+    ) {
+        let rejection = match self.seats.get(player) {
+            None => Some(format!("There is no player {}", player + 1)),
+            Some(_) if self.bots[player].is_some() => {
+                Some(format!("Player {} is played by the computer", player + 1))
+            }
+            Some(Some(_)) => Some(format!("Player {} is already connected", player + 1)),
+            Some(None) if self.tokens[player].is_some_and(|issued| Some(issued) != token) => {
+                Some(format!(
+                    "Player {} has already joined, reconnect with their token",
+                    player + 1
+                ))
+            }
+            Some(None) => None,
+        };
+        if let Some(message) = rejection {
+            let _ = send_line(&mut stream, &ServerMessage::Error { message });
+            let _ = stream.shutdown(Shutdown::Both);
+            return;
+        }
+
+        println!("{} connected", self.game.players[player].name);
+        let was_paused = !self.waiting_for().is_empty();
+        let token = *self.tokens[player].get_or_insert_with(rand::random);
+        let _ = send_line(
+            &mut stream,
+            &ServerMessage::Welcome {
+                player,
+                token,
+                setup: self.setup.clone(),
+                history: self.game.action_log().to_vec(),
+                snapshot: self.game.snapshot(),
+            },
+        );
+
+        self.seats[player] = Some((connection, stream));
+
+        if self.waiting_for().is_empty() {
+            if was_paused {
+                self.broadcast(&ServerMessage::Resumed);
+                self.advance_bots();
+            }
+        } else {
+            self.broadcast_if_paused();
+        }
+    }
+
+    fn leave(&mut self, connection: usize) {
+        let Some(player) = self.seat_of(connection) else {
+            return;
+        };
+        println!("{} disconnected", self.game.players[player].name);
+        self.seats[player] = None;
+
+        // the others carry on unless the game needs this player's response
+        if self.waiting_for().contains(&player) {
+            self.broadcast_if_paused();
+        }
+    }
+
+    fn respond(&mut self, connection: usize, response: AcquireResponse) {
+        let Some(player) = self.seat_of(connection) else {
+            return;
+        };
+
+        // the game itself rejects responses from a player whose turn it is not.
+        // players leave a network game by disconnecting, as the clients offer no resigning
+        let result = if !self.waiting_for().is_empty() {
+            Err("The game is paused until the players it is waiting for connect".to_string())
+        } else if response == AcquireResponse::Resign {
+            Err("Resigning is not available in network games".to_string())
+        } else {
+            self.apply(AcquirePlayerResponse::new(response, player))
+        };
+
+        match result {
+            Ok(()) => self.advance_bots(),
+            Err(message) => self.send_to(connection, &ServerMessage::Error { message }),
+        }
+    }
+
+    // applies the response and tells every client about it
+    fn apply(&mut self, response: AcquirePlayerResponse) -> Result<(), String> {
+        self.game.handle_player_response(response.clone())?;
+        let message = ServerMessage::Applied {
+            response,
+            request: *self.game.get_current_request(),
+            snapshot: self.game.snapshot(),
+        };
+        self.broadcast(&message);
+        // the game may have moved on to a player who is not connected
+        self.broadcast_if_paused();
+        Ok(())
+    }
+
+    fn advance_bots(&mut self) {
+        while !self.game.is_game_over() {
+            let Some((player, bot)) = self
+                .game
+                .get_players_to_respond()
+                .into_iter()
+                .find_map(|player| self.bots[player].as_ref().map(|bot| (player, bot)))
+            else {
+                return;
+            };
+            let request = *self.game.get_current_request();
+            let response = AcquirePlayerResponse::new(bot.decide(&self.game, &request), player);
+            if let Err(error) = self.apply(response) {
+                println!("Error: {}", error);
+                return;
+            }
+        }
+    }
+}
+
+// a connection to a server for the player in one seat, messages from the
+// server are read on a background thread and collected with try_recv
+pub struct RemoteGame {
+    pub player: usize,
+    // reconnects to the same seat if the connection is lost
+    pub token: u64,
+    stream: TcpStream,
+    messages: Receiver<ServerMessage>,
+    connected: bool,
+}
+
+impl RemoteGame {
+    // joins the game at addr as the player, returning the game's setup and history
+    // and the snapshot the history should replay to, a player who has joined before
+    // must give the token they were issued
+    pub fn connect(
+        addr: impl ToSocketAddrs,
+        player: usize,
+        token: Option<u64>,
+    ) -> io::Result<(Self, GameSetup, Vec<AcquirePlayerResponse>, GameSnapshot)> {
+        let mut stream = TcpStream::connect(addr)?;
+        send_line(&mut stream, &ClientMessage::Join { player, token })?;
+
+        let mut lines = BufReader::new(stream.try_clone()?).lines();
+        let (token, setup, history, snapshot) = match lines.next().map(parse_line) {
+            Some(Ok(ServerMessage::Welcome {
+                token,
+                setup,
+                history,
+                snapshot,
+                ..
+            })) => (token, setup, history, snapshot),
+            Some(Ok(ServerMessage::Error { message })) => {
+                return Err(io::Error::new(io::ErrorKind::ConnectionRefused, message))
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "The server did not welcome the player",
+                ))
+            }
+        };
+
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || {
+            for message in lines.map(parse_line::<ServerMessage>) {
+                let Ok(message) = message else { return };
+                if sender.send(message).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok((
+            RemoteGame {
+                player,
+                token,
+                stream,
+                messages,
+                connected: true,
+            },
+            setup,
+            history,
+            snapshot,
+        ))
+    }
+
+    pub fn send(&mut self, response: AcquireResponse) -> io::Result<()> {
+        send_line(&mut self.stream, &ClientMessage::Response(response))
+    }
+
+    // the next message from the server if one has arrived, Err the first time
+    // it is found the server has gone
+    pub fn try_recv(&mut self) -> Result<Option<ServerMessage>, String> {
+        if !self.connected {
+            return Ok(None);
+        }
+
+        match self.messages.try_recv() {
+            Ok(message) => Ok(Some(message)),
+            Err(mpsc::TryRecvError::Empty) => Ok(None),
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.connected = false;
+                Err("Lost the connection to the server".to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn next_message(remote: &RemoteGame) -> ServerMessage {
+        remote
+            .messages
+            .recv_timeout(Duration::from_secs(5))
+            .expect("the server should have sent a message")
+    }
+
+    #[test]
+    fn test_clients_follow_the_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let setup = GameSetup {
+            names: vec!["Ann".to_string(), "Bob".to_string()],
+            ..GameSetup::new(7, 2)
+        };
+        let server_setup = setup.clone();
+        thread::spawn(move || run_server(listener, server_setup, 0));
+
+        let (mut ann, ann_setup, history, _) = RemoteGame::connect(addr, 0, None).unwrap();
+        assert_eq!(ann_setup, setup);
+        assert!(history.is_empty());
+        assert_eq!(
+            next_message(&ann),
+            ServerMessage::Paused {
+                waiting_for: vec![1]
+            }
+        );
+
+        // the game is paused until both players are connected
+        ann.send(AcquireResponse::StartingTile).unwrap();
+        assert!(matches!(next_message(&ann), ServerMessage::Error { .. }));
+
+        let (mut bob, _, _, _) = RemoteGame::connect(addr, 1, None).unwrap();
+        assert_eq!(next_message(&ann), ServerMessage::Resumed);
+        assert_eq!(next_message(&bob), ServerMessage::Resumed);
+        assert!(RemoteGame::connect(addr, 1, None).is_err());
+
+        // responses out of turn are rejected
+        bob.send(AcquireResponse::StartingTile).unwrap();
+        assert_eq!(
+            next_message(&bob),
+            ServerMessage::Error {
+                message: "It is not your turn".to_string()
+            }
+        );
+
+        // players leave by disconnecting, the server never resigns a seat for them
+        ann.send(AcquireResponse::Resign).unwrap();
+        assert_eq!(
+            next_message(&ann),
+            ServerMessage::Error {
+                message: "Resigning is not available in network games".to_string()
+            }
+        );
+
+        // both clients apply what the server applied and end up with the same game
+        let mut ann_game = ann_setup
+            .build(Arc::new(StdoutMessages::default()))
+            .unwrap();
+        ann.send(AcquireResponse::StartingTile).unwrap();
+        for remote in [&ann, &bob] {
+            let ServerMessage::Applied {
+                response, snapshot, ..
+            } = next_message(remote)
+            else {
+                panic!("Expected the starting tile to be applied");
+            };
+            assert_eq!(response.player, 0);
+            if remote.player == 0 {
+                ann_game.handle_player_response(response).unwrap();
+                assert_eq!(ann_game.snapshot(), snapshot);
+            }
+        }
+    }
+
+    #[test]
+    fn test_players_reconnect_with_their_token() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let setup = GameSetup {
+            names: vec!["Ann".to_string(), "Bob".to_string()],
+            ..GameSetup::new(7, 2)
+        };
+        thread::spawn(move || run_server(listener, setup, 0));
+
+        let (mut ann, _, _, _) = RemoteGame::connect(addr, 0, None).unwrap();
+        next_message(&ann);
+        let (bob, _, _, _) = RemoteGame::connect(addr, 1, None).unwrap();
+        assert_eq!(next_message(&ann), ServerMessage::Resumed);
+
+        // once the server has seen Bob leave his seat needs his token
+        bob.stream.shutdown(Shutdown::Both).unwrap();
+        let rejection = loop {
+            let error = RemoteGame::connect(addr, 1, None).err().unwrap();
+            if !error.to_string().contains("already connected") {
+                break error;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(
+            rejection.to_string(),
+            "Player 2 has already joined, reconnect with their token"
+        );
+        assert!(RemoteGame::connect(addr, 1, Some(bob.token.wrapping_add(1))).is_err());
+
+        // Bob is not needed for Ann's move, but the game waits for him afterwards
+        ann.send(AcquireResponse::StartingTile).unwrap();
+        assert!(matches!(next_message(&ann), ServerMessage::Applied { .. }));
+        assert_eq!(
+            next_message(&ann),
+            ServerMessage::Paused {
+                waiting_for: vec![1]
+            }
+        );
+
+        let (bob, _, history, snapshot) = RemoteGame::connect(addr, 1, Some(bob.token)).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(
+            snapshot.current_request,
+            AcquireRequest::PlayStartingTile(1)
+        );
+        assert_eq!(next_message(&ann), ServerMessage::Resumed);
+        assert_eq!(next_message(&bob), ServerMessage::Resumed);
+    }
+}
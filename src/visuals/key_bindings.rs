@@ -0,0 +1,248 @@
+use actuire::logic::acquire_constants::TILES_PER_PLAYER;
+use crossterm::event::KeyCode;
+
+// the keys for the actions players may want to move, everything else keeps its key,
+// read from a TOML file of lines such as quit = "x" or tile_slots = ["a", "s", ...]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct KeyBindings {
+    // play the tile in that position of the hand
+    pub tile_slots: Vec<KeyCode>,
+    pub pass: KeyCode,
+    // buy the cheapest stock until no more can be bought
+    pub auto_buy: KeyCode,
+    pub quit: KeyCode,
+    pub help: KeyCode,
+    // stop the game for a break, nothing else can be played until it is pressed again
+    pub pause: KeyCode,
+    pub keep: KeyCode,
+    pub sell: KeyCode,
+    pub trade: KeyCode,
+    pub keep_all: KeyCode,
+    pub sell_all: KeyCode,
+    pub trade_all: KeyCode,
+    // switch between the messages and the moves
+    pub switch_view: KeyCode,
+    // show or hide the price chart, who holds each hotel, a hint, the drawn tiles
+    // and the recap once the game is over
+    pub price_table: KeyCode,
+    pub ownership: KeyCode,
+    pub hint: KeyCode,
+    pub drawn_tiles: KeyCode,
+    pub stats: KeyCode,
+    // keep viewing the player the game is waiting on
+    pub follow: KeyCode,
+    pub undo: KeyCode,
+    pub resign: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            tile_slots: ('1'..='6').map(KeyCode::Char).collect(),
+            pass: KeyCode::Char('S'),
+            auto_buy: KeyCode::Char('a'),
+            quit: KeyCode::Char('q'),
+            help: KeyCode::Char('?'),
+            pause: KeyCode::Char('P'),
+            keep: KeyCode::Char('k'),
+            sell: KeyCode::Char('s'),
+            trade: KeyCode::Char('t'),
+            keep_all: KeyCode::Char('K'),
+            sell_all: KeyCode::Char('S'),
+            trade_all: KeyCode::Char('T'),
+            switch_view: KeyCode::Tab,
+            price_table: KeyCode::Char('p'),
+            ownership: KeyCode::Char('o'),
+            hint: KeyCode::Char('h'),
+            drawn_tiles: KeyCode::Char('b'),
+            stats: KeyCode::Char('r'),
+            follow: KeyCode::Char('f'),
+            undo: KeyCode::Char('u'),
+            resign: KeyCode::Char('R'),
+        }
+    }
+}
+
+impl KeyBindings {
+    // actions missing from the file keep their default key
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        let table = text.parse::<toml::Table>().map_err(|e| e.to_string())?;
+
+        let mut bindings = KeyBindings::default();
+        for (action, value) in &table {
+            if action == "tile_slots" {
+                let slots = value
+                    .as_array()
+                    .ok_or("tile_slots must be a list of keys")?
+                    .iter()
+                    .map(|slot| parse_key(slot.as_str().unwrap_or_default()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if slots.len() != TILES_PER_PLAYER {
+                    return Err(format!(
+                        "tile_slots must have {} keys, not {}",
+                        TILES_PER_PLAYER,
+                        slots.len()
+                    ));
+                }
+                bindings.tile_slots = slots;
+                continue;
+            }
+
+            let key = parse_key(
+                value
+                    .as_str()
+                    .ok_or_else(|| format!("The key for {} must be a string", action))?,
+            )?;
+            match action.as_str() {
+                "pass" => bindings.pass = key,
+                "auto_buy" => bindings.auto_buy = key,
+                "quit" => bindings.quit = key,
+                "help" => bindings.help = key,
+                "pause" => bindings.pause = key,
+                "keep" => bindings.keep = key,
+                "sell" => bindings.sell = key,
+                "trade" => bindings.trade = key,
+                "keep_all" => bindings.keep_all = key,
+                "sell_all" => bindings.sell_all = key,
+                "trade_all" => bindings.trade_all = key,
+                "switch_view" => bindings.switch_view = key,
+                "price_table" => bindings.price_table = key,
+                "ownership" => bindings.ownership = key,
+                "hint" => bindings.hint = key,
+                "drawn_tiles" => bindings.drawn_tiles = key,
+                "stats" => bindings.stats = key,
+                "follow" => bindings.follow = key,
+                "undo" => bindings.undo = key,
+                "resign" => bindings.resign = key,
+                _ => return Err(format!("Unknown key binding: {}", action)),
+            }
+        }
+
+        bindings.check_for_duplicates()?;
+        Ok(bindings)
+    }
+
+    // a key may be reused by actions that are never available at the same time,
+    // such as passing on stock and selling all of it during a merger
+    fn check_for_duplicates(&self) -> Result<(), String> {
+        let always = [
+            ("quit", self.quit),
+            ("help", self.help),
+            ("pause", self.pause),
+            ("switch_view", self.switch_view),
+            ("price_table", self.price_table),
+            ("ownership", self.ownership),
+            ("hint", self.hint),
+            ("drawn_tiles", self.drawn_tiles),
+            ("stats", self.stats),
+            ("follow", self.follow),
+            ("undo", self.undo),
+            ("resign", self.resign),
+        ];
+        let tile_slots: Vec<_> = self
+            .tile_slots
+            .iter()
+            .map(|key| ("tile_slots", *key))
+            .collect();
+        let disposal = [
+            ("keep", self.keep),
+            ("sell", self.sell),
+            ("trade", self.trade),
+            ("keep_all", self.keep_all),
+            ("sell_all", self.sell_all),
+            ("trade_all", self.trade_all),
+        ];
+        let buying = [("pass", self.pass), ("auto_buy", self.auto_buy)];
+
+        for group in [&tile_slots[..], &disposal[..], &buying[..]] {
+            let actions: Vec<_> = always.iter().chain(group).collect();
+            for (index, (action, key)) in actions.iter().enumerate() {
+                if let Some((other, _)) = actions[..index].iter().find(|(_, other)| other == key) {
+                    return Err(format!(
+                        "{} is bound to both {} and {}",
+                        key_name(*key),
+                        other,
+                        action
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// single characters are themselves, a few other keys are known by name
+fn parse_key(name: &str) -> Result<KeyCode, String> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => return Ok(KeyCode::Char(c)),
+        (None, _) => return Err("A key binding cannot be empty".to_string()),
+        _ => {}
+    }
+
+    match name.to_lowercase().as_str() {
+        "space" => Ok(KeyCode::Char(' ')),
+        "tab" => Ok(KeyCode::Tab),
+        "esc" => Ok(KeyCode::Esc),
+        "enter" => Ok(KeyCode::Enter),
+        "backspace" => Ok(KeyCode::Backspace),
+        _ => Err(format!("Unknown key: {}", name)),
+    }
+}
+
+pub fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        key => format!("{:?}", key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        assert_eq!(KeyBindings::from_toml(""), Ok(KeyBindings::default()));
+
+        let bindings = KeyBindings::from_toml(
+            "quit = \"x\"\nswitch_view = \"space\"\nhint = \"H\"\ntile_slots = [\"a\", \"s\", \"d\", \"j\", \"k\", \"l\"]",
+        )
+        .unwrap();
+        assert_eq!(bindings.hint, KeyCode::Char('H'));
+        assert_eq!(bindings.quit, KeyCode::Char('x'));
+        assert_eq!(bindings.switch_view, KeyCode::Char(' '));
+        assert_eq!(bindings.tile_slots[1], KeyCode::Char('s'));
+        assert_eq!(bindings.help, KeyCode::Char('?'));
+    }
+
+    #[test]
+    fn test_bad_bindings_are_rejected() {
+        assert_eq!(
+            KeyBindings::from_toml("dance = \"d\""),
+            Err("Unknown key binding: dance".to_string())
+        );
+        assert_eq!(
+            KeyBindings::from_toml("quit = \"k\""),
+            Err("k is bound to both quit and keep".to_string())
+        );
+        // the toggles are read on every turn, so no tile slot may share their key
+        assert_eq!(
+            KeyBindings::from_toml("tile_slots = [\"a\", \"s\", \"d\", \"f\", \"g\", \"h\"]"),
+            Err("f is bound to both follow and tile_slots".to_string())
+        );
+        assert_eq!(
+            KeyBindings::from_toml("undo = \"t\""),
+            Err("t is bound to both undo and trade".to_string())
+        );
+        assert!(KeyBindings::from_toml("quit = \"F13\"").is_err());
+        assert!(KeyBindings::from_toml("tile_slots = [\"a\"]").is_err());
+        // passing and selling everything are never asked for at once
+        assert!(KeyBindings::from_toml("pass = \"T\"").is_ok());
+    }
+}
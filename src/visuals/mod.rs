@@ -1,4 +1,6 @@
 
+pub mod key_bindings;
+pub mod palette;
 pub mod tui_app;
 pub mod tui;
 
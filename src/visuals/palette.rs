@@ -0,0 +1,66 @@
+use std::str::FromStr;
+
+use actuire::logic::hotel_data::Hotel;
+use ratatui::style::Color;
+
+const DEFAULT_HOTEL_COLORS: [Color; 7] = [
+    Color::Rgb(201, 128, 6),  // Tower
+    Color::Rgb(191, 37, 45),  // Luxor
+    Color::Rgb(1, 30, 80),    // American
+    Color::Rgb(75, 41, 21),   // Worldwide
+    Color::Rgb(0, 82, 60),    // Festival
+    Color::Rgb(176, 55, 100), // Imperial
+    Color::Rgb(2, 82, 86),    // Continental
+];
+
+// the Okabe-Ito colors, which stay distinct for the common forms of colorblindness
+const COLORBLIND_HOTEL_COLORS: [Color; 7] = [
+    Color::Rgb(240, 228, 66),  // Tower
+    Color::Rgb(213, 94, 0),    // Luxor
+    Color::Rgb(0, 114, 178),   // American
+    Color::Rgb(230, 159, 0),   // Worldwide
+    Color::Rgb(0, 158, 115),   // Festival
+    Color::Rgb(204, 121, 167), // Imperial
+    Color::Rgb(86, 180, 233),  // Continental
+];
+
+const HOTEL_SYMBOLS: [&str; 7] = ["T", "L", "A", "W", "F", "I", "C"];
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Palette {
+    #[default]
+    Default,
+    Colorblind,
+}
+
+impl Palette {
+    pub fn hotel_color(&self, hotel: Hotel) -> Color {
+        match self {
+            Palette::Default => DEFAULT_HOTEL_COLORS[hotel as usize],
+            Palette::Colorblind => COLORBLIND_HOTEL_COLORS[hotel as usize],
+        }
+    }
+
+    // a symbol marking each hotel's cells, so color is never the only cue
+    pub fn hotel_symbol(&self, hotel: Hotel) -> Option<&'static str> {
+        match self {
+            Palette::Default => None,
+            Palette::Colorblind => Some(HOTEL_SYMBOLS[hotel as usize]),
+        }
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(Palette::Default),
+            "colorblind" => Ok(Palette::Colorblind),
+            _ => Err(format!(
+                "Unknown palette {}, expected default or colorblind",
+                s
+            )),
+        }
+    }
+}
@@ -1,28 +1,42 @@
+#[cfg(feature = "serde")]
+use std::path::Path;
 use std::{
-    borrow::Borrow,
-    error::Error,
+    cell::RefCell,
     io::Result,
     sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
 };
 
-use crossterm::event::{self, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{self, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style, Stylize},
+    style::{Color, Style, Stylize},
     symbols::border,
     text::{Line, Span, Text},
     widgets::{
         block::{Position, Title},
-        Block, BorderType, Borders, Paragraph, Widget,
+        Block, BorderType, Borders, Clear, Paragraph,
     },
     Frame,
 };
 
-use crate::logic::{
+use super::{
+    key_bindings::{key_name, KeyBindings},
+    palette::Palette,
+};
+use crate::message_log::MessageLog;
+#[cfg(feature = "serde")]
+use crate::network::{RemoteGame, ServerMessage};
+use actuire::logic::{
+    acquire_event::AcquireEvent,
     acquire_game::{AcquireGame, AcquireGameCallback},
+    acquire_player_strategy::{AcquirePlayerStrategy, GreedyBot},
     acquire_request::AcquireRequest,
-    acquire_response::{AcquireResponse, BuyStockChoice, DisposeStockChoice},
+    acquire_response::{
+        AcquirePlayerResponse, AcquireResponse, BuyStockChoice, DisposeStockChoice,
+    },
     game_board,
+    game_phase::GamePhase,
     hotel_data::Hotel,
     tile::Tile,
 };
@@ -33,66 +47,297 @@ struct InnerRects {
     stocks: Rect,
     prompt: Rect,
     player: Rect,
+    status_bar: Rect,
 }
 
 #[derive(Debug, Default)]
-struct AcquireMessages {
+pub struct AcquireMessages {
     messages: Mutex<Vec<String>>,
+    player_names: Mutex<Vec<String>>,
 }
 
 impl AcquireMessages {
-    pub fn get_messages(&self) -> MutexGuard<Vec<String>> {
+    pub fn get_messages(&self) -> MutexGuard<'_, Vec<String>> {
         self.messages.lock().unwrap()
     }
+
+    // events only carry player indices, so the names are needed to describe them
+    pub fn set_player_names(&self, player_names: Vec<String>) {
+        *self.player_names.lock().unwrap() = player_names;
+    }
 }
 
+// the app's messages, also written to a file when the game is logged
+pub type TuiMessages = MessageLog<AcquireMessages>;
+
 impl AcquireGameCallback for AcquireMessages {
     fn send_message(&self, message: &str) {
         self.messages.lock().unwrap().push(message.to_string());
     }
+
+    fn on_event(&self, event: &AcquireEvent) {
+        let message = event.describe(&self.player_names.lock().unwrap());
+        self.messages.lock().unwrap().push(message);
+    }
 }
 
 pub struct TuiApp {
     cell_width: u16,
     cell_height: u16,
-    acquire_messages: Arc<AcquireMessages>,
-    acquire_game: AcquireGame<AcquireMessages>,
+    acquire_messages: Arc<TuiMessages>,
+    acquire_game: AcquireGame<TuiMessages>,
     error_message_per_player: Vec<String>,
     exit: bool,
     current_player: usize,
+    // when set, current_player tracks the player the current request is for
+    // instead of being chosen with the digit keys
+    follow_active_player: bool,
+    // how many of the newest messages are scrolled past, 0 follows the latest message
+    message_scroll: usize,
+    message_count: usize,
+    // the log shows the move list instead of the messages
+    show_history: bool,
+    show_price_table: bool,
+    show_ownership: bool,
+    // the suggested tile is highlighted on the board and in the hand
+    show_hint: bool,
+    // empty cells whose tile has already left the bag are marked
+    show_drawn_tiles: bool,
+    // the recap shown over the board once the game is over
+    show_stats: bool,
+    show_help: bool,
+    // the game is on a break, only the pause and quit keys do anything
+    paused: bool,
+    confirm_quit: bool,
+    // the viewed player is asked before they leave the game
+    confirm_resign: bool,
+    // once the leader cannot be caught the players are asked to concede, only once
+    concession_declined: bool,
+    keys: KeyBindings,
+    // the number of shares typed so far to sell or trade during a merger,
+    // or the keep, sell and trade counts of a split separated by commas
+    dispose_count: String,
+    palette: Palette,
+    daily_date: Option<String>,
+    // where the board and the hand tiles were last drawn, used to hit test mouse clicks
+    board_rect: RefCell<Rect>,
+    hand_tile_rects: RefCell<Vec<Rect>>,
+    bots: Vec<Option<Box<dyn AcquirePlayerStrategy<TuiMessages>>>>,
+    // spectators see every player at once and cannot make moves, the computer
+    // moves at a pace that can be followed
+    spectate: bool,
+    // the computer's moves are made one at a time with a pause to read each of them,
+    // always the case for spectators
+    pace_bots: bool,
+    // a key was pressed to skip the pause before the computer's next move
+    hurry_bots: bool,
+    // only the players the local user controls show their cash until the game is over
+    hidden_cash: bool,
+    last_bot_response: Instant,
+    // when set, a default response is made for a player who takes longer than this
+    turn_seconds: Option<u64>,
+    // the request the turn clock is timing and when it was first seen
+    timed_request: AcquireRequest,
+    turn_started: Instant,
+    // the server running the game in a network game
+    #[cfg(feature = "serde")]
+    remote: Option<RemoteGame>,
 }
 
-const HOTEL_COLORS: [Color; 7] = [
-    Color::Rgb(201, 128, 6),  // Tower
-    Color::Rgb(191, 37, 45),  // Luxor
-    Color::Rgb(1, 30, 80),    // American
-    Color::Rgb(75, 41, 21),   // Worldwide
-    Color::Rgb(0, 82, 60),    // Festival
-    Color::Rgb(176, 55, 100), // Imperial
-    Color::Rgb(2, 82, 86),    // Continental
-];
+#[cfg(feature = "serde")]
+const SAVE_FILE_PATH: &str = "acquire_save.json";
+// where the results of a finished game are written when the app exits
+#[cfg(feature = "serde")]
+const RESULTS_FILE_PATH: &str = "acquire_results.json";
 
-impl TuiApp {
-    pub fn new(cell_width: u16, cell_height: u16, number_of_players: usize) -> Self {
-        let acquire_messages = Arc::new(AcquireMessages::default());
-        let acquire_game = AcquireGame::new(number_of_players, acquire_messages.clone());
-
-        Self {
-            cell_width,
-            cell_height,
-            acquire_messages: acquire_messages.clone(),
+// how long players are given to take in each move the computer makes when it is paced
+const BOT_MOVE_DELAY: Duration = Duration::from_millis(750);
+
+// what the digits 1-7 type with shift held on a US keyboard
+const SHIFTED_DIGITS: [char; Hotel::count()] = ['!', '@', '#', '$', '%', '^', '&'];
+
+// sets up a TuiApp around a game that was built elsewhere, the app shows
+// the messages of the callback the game was created with
+pub struct TuiAppBuilder {
+    cell_width: u16,
+    cell_height: u16,
+    palette: Palette,
+    daily_date: Option<String>,
+    number_of_bots: usize,
+    follow_active_player: bool,
+    spectate: bool,
+    pace_bots: bool,
+    hidden_cash: bool,
+    turn_seconds: Option<u64>,
+    keys: KeyBindings,
+    #[cfg(feature = "serde")]
+    remote: Option<RemoteGame>,
+    acquire_game: AcquireGame<TuiMessages>,
+}
+
+impl TuiAppBuilder {
+    pub fn cell_size(mut self, cell_width: u16, cell_height: u16) -> Self {
+        self.cell_width = cell_width;
+        self.cell_height = cell_height;
+        self
+    }
+
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn daily_date(mut self, daily_date: Option<String>) -> Self {
+        self.daily_date = daily_date;
+        self
+    }
+
+    // the last number_of_bots players are played by the computer
+    pub fn bots(mut self, number_of_bots: usize) -> Self {
+        self.number_of_bots = number_of_bots;
+        self
+    }
+
+    // the viewed player follows whoever the game is waiting on
+    pub fn follow_active_player(mut self, follow_active_player: bool) -> Self {
+        self.follow_active_player = follow_active_player;
+        self
+    }
+
+    // shows every player at once and ignores moves, bots are still given their seats
+    pub fn spectate(mut self, spectate: bool) -> Self {
+        self.spectate = spectate;
+        self
+    }
+
+    // the computer pauses before each of its moves, any key skips the pause
+    pub fn pace_bots(mut self, pace_bots: bool) -> Self {
+        self.pace_bots = pace_bots;
+        self
+    }
+
+    // cash is hidden information, opponents' cash and net worth are shown as ???
+    pub fn hidden_cash(mut self, hidden_cash: bool) -> Self {
+        self.hidden_cash = hidden_cash;
+        self
+    }
+
+    // players who take longer than turn_seconds to respond have a default response made for them
+    pub fn turn_seconds(mut self, turn_seconds: Option<u64>) -> Self {
+        self.turn_seconds = turn_seconds;
+        self
+    }
+
+    pub fn key_bindings(mut self, keys: KeyBindings) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    // plays one seat of a game run by a server, the game given to the builder
+    // must be the server's game as of connecting
+    #[cfg(feature = "serde")]
+    pub fn remote(mut self, remote: RemoteGame) -> Self {
+        self.remote = Some(remote);
+        self
+    }
+
+    pub fn build(self) -> TuiApp {
+        let acquire_game = self.acquire_game;
+        let acquire_messages = acquire_game.message_callback();
+        let number_of_players = acquire_game.players.len();
+        let timed_request = *acquire_game.get_current_request();
+        acquire_messages.inner().set_player_names(
+            acquire_game
+                .players
+                .iter()
+                .map(|player| player.name.clone())
+                .collect(),
+        );
+
+        TuiApp {
+            cell_width: self.cell_width,
+            cell_height: self.cell_height,
+            acquire_messages,
             acquire_game,
             exit: false,
+            #[cfg(feature = "serde")]
+            current_player: self.remote.as_ref().map_or(0, |remote| remote.player),
+            #[cfg(not(feature = "serde"))]
             current_player: 0,
+            follow_active_player: self.follow_active_player,
+            message_scroll: 0,
+            message_count: 0,
+            show_history: false,
+            show_price_table: false,
+            show_ownership: false,
+            show_hint: false,
+            show_drawn_tiles: false,
+            show_stats: true,
+            show_help: false,
+            paused: false,
+            confirm_quit: false,
+            confirm_resign: false,
+            concession_declined: false,
+            keys: self.keys,
+            dispose_count: String::new(),
+            palette: self.palette,
+            daily_date: self.daily_date,
+            board_rect: RefCell::new(Rect::default()),
+            hand_tile_rects: RefCell::new(Vec::new()),
             error_message_per_player: vec![String::new(); number_of_players],
+            bots: create_bots(number_of_players, self.number_of_bots),
+            spectate: self.spectate,
+            pace_bots: self.pace_bots || self.spectate,
+            hurry_bots: false,
+            hidden_cash: self.hidden_cash,
+            last_bot_response: Instant::now(),
+            turn_seconds: self.turn_seconds,
+            timed_request,
+            turn_started: Instant::now(),
+            #[cfg(feature = "serde")]
+            remote: self.remote,
+        }
+    }
+}
+
+impl TuiApp {
+    pub fn builder(acquire_game: AcquireGame<TuiMessages>) -> TuiAppBuilder {
+        TuiAppBuilder {
+            cell_width: 6,
+            cell_height: 3,
+            palette: Palette::Default,
+            daily_date: None,
+            number_of_bots: 0,
+            follow_active_player: false,
+            spectate: false,
+            pace_bots: false,
+            hidden_cash: false,
+            turn_seconds: None,
+            keys: KeyBindings::default(),
+            #[cfg(feature = "serde")]
+            remote: None,
+            acquire_game,
         }
     }
 
     pub fn run(&mut self, terminal: &mut super::tui::Tui) -> Result<()> {
         while !self.exit {
+            #[cfg(feature = "serde")]
+            self.receive_remote_messages();
+            if !self.paused {
+                self.advance_bots();
+                self.tick_turn_clock();
+            }
+            self.follow_new_messages();
+            self.follow_active_player();
             terminal.draw(|frame| self.render_frame(frame))?;
             self.handle_events()?;
         }
+
+        #[cfg(feature = "serde")]
+        if self.acquire_game.is_game_over() {
+            std::fs::write(RESULTS_FILE_PATH, self.acquire_game.results_json())?;
+        }
         Ok(())
     }
 
@@ -103,23 +348,527 @@ impl TuiApp {
             self.render_messages(inner_rects.messages, frame);
             self.render_stocks(inner_rects.stocks, frame);
             self.render_prompt(inner_rects.prompt, frame);
-            self.render_player(inner_rects.player, frame);
+            self.render_status_bar(inner_rects.status_bar, frame);
+            if self.spectate {
+                self.render_all_players(inner_rects.player, frame);
+            } else {
+                self.render_player(inner_rects.player, frame);
+            }
+
+            if self.show_price_table {
+                self.render_price_table(inner_rects.game_board, frame);
+            }
+            if self.show_ownership {
+                self.render_ownership(inner_rects.game_board, frame);
+            }
+            if self.show_stats && self.acquire_game.is_game_over() {
+                self.render_stats(inner_rects.game_board, frame);
+            }
+            if self.show_help {
+                self.render_help(inner_rects.game_board, frame);
+            }
+            if self.paused {
+                self.render_paused(inner_rects.game_board, frame);
+            }
+        } else {
+            self.render_too_small(frame.size(), frame);
+        }
+    }
+
+    fn render_too_small(&self, area: Rect, frame: &mut Frame) {
+        let (needed_width, needed_height) = self.get_needed_size();
+
+        let text = Text::from(vec![
+            Line::from(vec![format!(
+                "Terminal too small: need at least {}x{}, have {}x{}",
+                needed_width, needed_height, area.width, area.height
+            )
+            .into()]),
+            Line::from(vec![
+                "Resize the terminal or lower --cell-width/--cell-height".into(),
+            ]),
+        ]);
+
+        let y = area.y + area.height.saturating_sub(2) / 2;
+        let text_area = Rect {
+            x: area.x,
+            y,
+            width: area.width,
+            height: area.height.min(2),
+        };
+        frame.render_widget(Paragraph::new(text).centered(), text_area);
+    }
+
+    // network games apply a response once the server has, local games apply it now
+    fn submit_response(&mut self, response: AcquireResponse) -> std::result::Result<(), String> {
+        #[cfg(feature = "serde")]
+        if let Some(remote) = &mut self.remote {
+            return remote.send(response).map_err(|e| e.to_string());
+        }
+        self.acquire_game
+            .handle_player_response(AcquirePlayerResponse::new(response, self.current_player))
+            .map_err(|e| e.to_string())
+    }
+
+    // applies what the server applied so this copy of the game stays the same as the server's
+    #[cfg(feature = "serde")]
+    fn receive_remote_messages(&mut self) {
+        let Some(remote) = &mut self.remote else {
+            return;
+        };
+        let player = remote.player;
+
+        let mut server_messages = Vec::new();
+        let lost_connection = loop {
+            match remote.try_recv() {
+                Ok(Some(message)) => server_messages.push(message),
+                Ok(None) => break None,
+                Err(e) => break Some(e),
+            }
+        };
+
+        for message in server_messages {
+            match message {
+                ServerMessage::Applied {
+                    response, snapshot, ..
+                } => {
+                    let result = self.acquire_game.handle_player_response(response);
+                    if result.is_err() || self.acquire_game.snapshot() != snapshot {
+                        self.acquire_messages
+                            .send_message("This game is out of sync with the server");
+                    }
+                    self.error_message_per_player[player] = String::new();
+                }
+                ServerMessage::Error { message } => self.error_message_per_player[player] = message,
+                ServerMessage::Paused { waiting_for } => {
+                    let names = waiting_for
+                        .iter()
+                        .map(|player| self.acquire_game.players[*player].name.clone())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    self.acquire_messages
+                        .send_message(&format!("Paused, waiting for {} to connect", names));
+                }
+                ServerMessage::Resumed => self
+                    .acquire_messages
+                    .send_message("The players are connected, the game resumes"),
+                ServerMessage::Welcome { .. } => {}
+            }
+        }
+
+        if let Some(error) = lost_connection {
+            self.acquire_messages.send_message(&error);
+        }
+    }
+
+    // bots respond until the game is waiting on a human player
+    fn advance_bots(&mut self) {
+        loop {
+            let bot_player = self
+                .acquire_game
+                .get_players_to_respond()
+                .into_iter()
+                .find(|player| self.bots[*player].is_some());
+            let Some(player) = bot_player else {
+                return;
+            };
+            if self.pace_bots
+                && !self.hurry_bots
+                && self.last_bot_response.elapsed() < BOT_MOVE_DELAY
+            {
+                return;
+            }
+
+            let request = *self.acquire_game.get_current_request();
+            let response = self.bots[player]
+                .as_ref()
+                .unwrap()
+                .decide(&self.acquire_game, &request);
+
+            if let Err(e) = self
+                .acquire_game
+                .handle_player_response(AcquirePlayerResponse::new(response, player))
+            {
+                self.error_message_per_player[player] = e.to_string();
+                return;
+            }
+            if self.pace_bots {
+                self.last_bot_response = Instant::now();
+                self.hurry_bots = false;
+                return;
+            }
+        }
+    }
+
+    // only paced bots are being waited on, so key presses are not meant as moves
+    fn is_bot_move_pending(&self) -> bool {
+        let players_to_respond = self.acquire_game.get_players_to_respond();
+        self.pace_bots
+            && !players_to_respond.is_empty()
+            && players_to_respond
+                .into_iter()
+                .all(|player| self.bots[player].is_some())
+    }
+
+    // restarts the clock whenever the game asks for something new, and responds for
+    // the player once their time is up
+    fn tick_turn_clock(&mut self) {
+        let Some(turn_seconds) = self.turn_seconds else {
+            return;
+        };
+        let request = *self.acquire_game.get_current_request();
+        if request != self.timed_request {
+            self.timed_request = request;
+            self.turn_started = Instant::now();
+            return;
+        }
+        if self.acquire_game.is_game_over()
+            || self.turn_started.elapsed() < Duration::from_secs(turn_seconds)
+        {
+            return;
         }
+        let Some(response) = self.default_response() else {
+            return;
+        };
+        // a default that is rejected waits for the clock to run out again
+        self.turn_started = Instant::now();
+
+        let player = request.get_player();
+        #[cfg(feature = "serde")]
+        if let Some(remote) = &mut self.remote {
+            // each client only answers for its own seat
+            if remote.player == player {
+                if let Err(e) = remote.send(response) {
+                    self.error_message_per_player[player] = e.to_string();
+                }
+            }
+            return;
+        }
+        if let Err(e) = self
+            .acquire_game
+            .handle_player_response(AcquirePlayerResponse::new(response, player))
+        {
+            self.error_message_per_player[player] = e.to_string();
+        }
+    }
+
+    // the response made for a player whose time ran out, the first playable tile,
+    // the first hotel offered, keeping defunct stock and passing on buying
+    fn default_response(&self) -> Option<AcquireResponse> {
+        let game = &self.acquire_game;
+        let first_hotel = game.get_acceptable_hotels_for_response().first().copied();
+        match *game.get_current_request() {
+            AcquireRequest::PlayStartingTile(_) => Some(AcquireResponse::StartingTile),
+            AcquireRequest::PlayTile(player) => game
+                .playable_tiles(player)
+                .first()
+                .map(|tile| AcquireResponse::Tile(*tile)),
+            AcquireRequest::ChooseNewChain(_) => first_hotel.map(AcquireResponse::NewChain),
+            AcquireRequest::ChooseMergerSurvivor(_) => {
+                first_hotel.map(AcquireResponse::MergerSurvivor)
+            }
+            AcquireRequest::ChooseDefunctChainToResolve(_) => {
+                first_hotel.map(AcquireResponse::DefunctChainToResolve)
+            }
+            AcquireRequest::DisposeStock(player) => Some(AcquireResponse::DisposeStock(
+                player,
+                DisposeStockChoice::KeepAll,
+            )),
+            AcquireRequest::BuyStock(_) => Some(AcquireResponse::BuyStock(BuyStockChoice::Pass)),
+            AcquireRequest::EndGame(_) => Some(AcquireResponse::EndGame(false)),
+        }
+    }
+
+    // views the player the game is waiting on
+    fn follow_active_player(&mut self) {
+        if !self.follow_active_player {
+            return;
+        }
+
+        if let Some(&player) = self.acquire_game.get_players_to_respond().first() {
+            self.current_player = player;
+        }
+    }
+
+    // any new message snaps the log back to the latest
+    fn follow_new_messages(&mut self) {
+        let message_count = self.acquire_messages.inner().get_messages().len();
+        if message_count != self.message_count {
+            self.message_count = message_count;
+            self.message_scroll = 0;
+        }
+    }
+
+    fn get_message_page_height(&self) -> usize {
+        let (width, height) = crossterm::terminal::size().unwrap_or_default();
+        self.split_rects(Rect::new(0, 0, width, height))
+            .map(|inner_rects| inner_rects.messages.height.saturating_sub(2) as usize)
+            .unwrap_or_default()
+            .max(1)
+    }
+
+    // the lines of whichever log is shown, oldest first
+    fn get_log_lines(&self) -> Vec<String> {
+        if !self.show_history {
+            return self.acquire_messages.inner().get_messages().clone();
+        }
+
+        let player_names: Vec<String> = self
+            .acquire_game
+            .players
+            .iter()
+            .map(|player| player.name.clone())
+            .collect();
+        self.acquire_game
+            .turn_history()
+            .iter()
+            .map(|record| record.describe(&player_names))
+            .collect()
+    }
+
+    fn scroll_messages_up(&mut self) {
+        let page_height = self.get_message_page_height();
+        let max_scroll = self.get_log_lines().len().saturating_sub(page_height);
+        self.message_scroll = (self.message_scroll + page_height).min(max_scroll);
+    }
+
+    fn scroll_messages_down(&mut self) {
+        let page_height = self.get_message_page_height();
+        self.message_scroll = self.message_scroll.saturating_sub(page_height);
     }
 
     fn handle_events(&mut self) -> Result<()> {
         if event::poll(std::time::Duration::from_millis(100))? {
-            let event = event::read()?;
-
-            // Player-specific controls
-            let current_request = self.acquire_game.get_current_request();
-            match current_request {
-                AcquireRequest::PlayStartingTile(player) if *player == self.current_player => {
-                    match event {
-                        event::Event::Key(event) => match (event.code, event.modifiers, event.kind) {
-                            (KeyCode::Char(' '), KeyModifiers::NONE ,KeyEventKind::Press) => {
-                                let response = AcquireResponse::StartingTile;
-                                let res = self.acquire_game.handle_player_response(response.into());
+            self.handle_event(event::read()?)?;
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: event::Event) -> Result<()> {
+        // While paused only resuming and quitting are listened to, so nothing is played on a break
+        if self.paused && !self.confirm_quit {
+            if let event::Event::Key(event) = event {
+                if event.kind == KeyEventKind::Press {
+                    if event.code == self.keys.pause {
+                        self.resume();
+                    } else if event.code == self.keys.quit {
+                        self.confirm_quit = true;
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        // While help is open it swallows every key so nothing is played by accident
+        if let event::Event::Key(event) = event {
+            match (event.code, event.kind) {
+                (code, KeyEventKind::Press) if code == self.keys.help => {
+                    self.show_help = !self.show_help;
+                    return Ok(());
+                }
+                (KeyCode::Esc, KeyEventKind::Press) if self.show_help => {
+                    self.show_help = false;
+                    return Ok(());
+                }
+                _ if self.show_help => return Ok(()),
+                _ => {}
+            }
+        }
+
+        // Only y confirms quitting, any other key cancels it
+        if self.confirm_quit {
+            if let event::Event::Key(event) = event {
+                if event.kind == KeyEventKind::Press {
+                    self.exit = event.code == KeyCode::Char('y');
+                    self.confirm_quit = false;
+                }
+            }
+            return Ok(());
+        }
+
+        // Resigning is confirmed the same way
+        if self.confirm_resign {
+            if let event::Event::Key(event) = event {
+                if event.kind == KeyEventKind::Press {
+                    if event.code == KeyCode::Char('y') {
+                        self.resign();
+                    }
+                    self.confirm_resign = false;
+                }
+            }
+            return Ok(());
+        }
+
+        // So is conceding once the leader can no longer be caught, any other key declines
+        if self.offers_concession() {
+            if let event::Event::Key(event) = event {
+                if event.kind == KeyEventKind::Press {
+                    if event.code == KeyCode::Char('y') {
+                        self.concede();
+                    }
+                    self.concession_declined = true;
+                }
+            }
+            return Ok(());
+        }
+
+        // Any key skips the pause before the computer's move, spectators keep their controls
+        if self.is_bot_move_pending() && !self.spectate {
+            if let event::Event::Key(event) = event {
+                if event.kind == KeyEventKind::Press
+                    && event.code != self.keys.quit
+                    && event.code != self.keys.pause
+                {
+                    self.hurry_bots = true;
+                    return Ok(());
+                }
+            }
+        }
+
+        // Save and load take priority so Ctrl+S is never read as a dispose choice
+        #[cfg(feature = "serde")]
+        if let event::Event::Key(event) = event {
+            match (event.code, event.modifiers, event.kind) {
+                (KeyCode::Char('s'), KeyModifiers::CONTROL, KeyEventKind::Press) => {
+                    self.save_game();
+                    return Ok(());
+                }
+                (KeyCode::Char('l'), KeyModifiers::CONTROL, KeyEventKind::Press)
+                    if !self.spectate =>
+                {
+                    self.load_game();
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        // Scrolling the message log works no matter whose turn it is, though a key the
+        // viewed player is answering with is left for their request
+        if let event::Event::Key(event) = event {
+            match (event.code, event.modifiers, event.kind) {
+                (code, _, _) if self.answers_request(code) => {}
+                (KeyCode::PageUp, _, KeyEventKind::Press)
+                | (KeyCode::Char('u'), KeyModifiers::CONTROL, KeyEventKind::Press) => {
+                    self.scroll_messages_up();
+                    return Ok(());
+                }
+                (KeyCode::PageDown, _, KeyEventKind::Press)
+                | (KeyCode::Char('d'), KeyModifiers::CONTROL, KeyEventKind::Press) => {
+                    self.scroll_messages_down();
+                    return Ok(());
+                }
+                (code, _, KeyEventKind::Press) if code == self.keys.pause => {
+                    self.paused = true;
+                    return Ok(());
+                }
+                (code, _, KeyEventKind::Press) if code == self.keys.switch_view => {
+                    self.show_history = !self.show_history;
+                    self.message_scroll = 0;
+                    return Ok(());
+                }
+                (code, _, KeyEventKind::Press) if code == self.keys.price_table => {
+                    self.show_price_table = !self.show_price_table;
+                    return Ok(());
+                }
+                (code, _, KeyEventKind::Press) if code == self.keys.ownership => {
+                    self.show_ownership = !self.show_ownership;
+                    return Ok(());
+                }
+                (code, _, KeyEventKind::Press) if code == self.keys.hint => {
+                    self.show_hint = !self.show_hint;
+                    return Ok(());
+                }
+                (code, _, KeyEventKind::Press) if code == self.keys.drawn_tiles => {
+                    self.show_drawn_tiles = !self.show_drawn_tiles;
+                    return Ok(());
+                }
+                (code, _, KeyEventKind::Press) if code == self.keys.quit && self.spectate => {
+                    self.confirm_quit = true;
+                    return Ok(());
+                }
+                (code, _, KeyEventKind::Press)
+                    if code == self.keys.stats && self.acquire_game.is_game_over() =>
+                {
+                    self.show_stats = !self.show_stats;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        // Spectators only get the controls above
+        if self.spectate {
+            return Ok(());
+        }
+
+        // Left clicks on a hand tile play it
+        if let event::Event::Mouse(mouse_event) = event {
+            if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) && !self.show_help {
+                self.handle_click(mouse_event.column, mouse_event.row);
+            }
+            return Ok(());
+        }
+
+        // Player-specific controls
+        let current_request = self.acquire_game.get_current_request();
+        match current_request {
+            AcquireRequest::PlayStartingTile(player) if *player == self.current_player => {
+                if let event::Event::Key(event) = event {
+                    if let (KeyCode::Char(' '), KeyModifiers::NONE, KeyEventKind::Press) =
+                        (event.code, event.modifiers, event.kind)
+                    {
+                        let response = AcquireResponse::StartingTile;
+                        let res = self.submit_response(response);
+                        if let Err(e) = res {
+                            self.error_message_per_player[self.current_player] = e.to_string();
+                        } else {
+                            self.error_message_per_player[self.current_player] = String::new();
+                        }
+                        return Ok(());
+                    }
+                }
+            }
+            AcquireRequest::PlayTile(player) if *player == self.current_player => {
+                if let event::Event::Key(event) = event {
+                    match (event.code, event.kind) {
+                        (code, KeyEventKind::Press) if self.keys.tile_slots.contains(&code) => {
+                            // late in the game a hand may not fill every slot,
+                            // pressing an empty slot does nothing
+                            let slot = self.keys.tile_slots.iter().position(|key| *key == code);
+                            let tile = slot.and_then(|slot| {
+                                self.acquire_game.players[self.current_player]
+                                    .tiles
+                                    .get(slot)
+                                    .cloned()
+                            });
+                            let Some(tile) = tile else {
+                                return Ok(());
+                            };
+                            let response = AcquireResponse::Tile(tile);
+
+                            let res = self.submit_response(response);
+                            if let Err(e) = res {
+                                self.error_message_per_player[self.current_player] = e.to_string();
+                            } else {
+                                self.error_message_per_player[self.current_player] = String::new();
+                            }
+                            return Ok(());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            AcquireRequest::ChooseNewChain(player) if *player == self.current_player => {
+                if let event::Event::Key(event) = event {
+                    match (event.code, event.kind) {
+                        (KeyCode::Char(c), KeyEventKind::Press) if c.is_ascii_digit() => {
+                            let num = c.to_digit(10).unwrap();
+                            if (1..=Hotel::count() as u32).contains(&num) {
+                                let hotel = Hotel::from(num as usize - 1);
+                                let response = AcquireResponse::NewChain(hotel);
+
+                                let res = self.submit_response(response);
                                 if let Err(e) = res {
                                     self.error_message_per_player[self.current_player] =
                                         e.to_string();
@@ -129,24 +878,21 @@ impl TuiApp {
                                 }
                                 return Ok(());
                             }
-                            _ => {}
-                        },
+                        }
                         _ => {}
                     }
                 }
-                AcquireRequest::PlayTile(player) if *player == self.current_player => match event {
-                    event::Event::Key(event) => match (event.code, event.kind) {
-                        (KeyCode::Char(c), KeyEventKind::Press) if c.is_digit(10) => {
+            }
+            AcquireRequest::ChooseMergerSurvivor(player) if *player == self.current_player => {
+                if let event::Event::Key(event) = event {
+                    match (event.code, event.kind) {
+                        (KeyCode::Char(c), KeyEventKind::Press) if c.is_ascii_digit() => {
                             let num = c.to_digit(10).unwrap();
-                            if num <= 6 {
-                                let tile = self.acquire_game.players[self.current_player]
-                                    .tiles
-                                    .get(num as usize - 1)
-                                    .cloned()
-                                    .unwrap();
-                                let response = AcquireResponse::Tile(tile);
+                            if (1..=Hotel::count() as u32).contains(&num) {
+                                let hotel = Hotel::from(num as usize - 1);
+                                let response = AcquireResponse::MergerSurvivor(hotel);
 
-                                let res = self.acquire_game.handle_player_response(response.into());
+                                let res = self.submit_response(response);
                                 if let Err(e) = res {
                                     self.error_message_per_player[self.current_player] =
                                         e.to_string();
@@ -158,145 +904,125 @@ impl TuiApp {
                             }
                         }
                         _ => {}
-                    },
-                    _ => {}
-                },
-                AcquireRequest::ChooseNewChain(player) if *player == self.current_player => {
-                    match event {
-                        event::Event::Key(event) => match (event.code, event.kind) {
-                            (KeyCode::Char(c), KeyEventKind::Press) if c.is_digit(10) => {
-                                let num = c.to_digit(10).unwrap();
-                                if num <= Hotel::count() as u32 {
-                                    let hotel = Hotel::from(num as usize - 1);
-                                    let response = AcquireResponse::NewChain(hotel);
-
-                                    let res =
-                                        self.acquire_game.handle_player_response(response.into());
-                                    if let Err(e) = res {
-                                        self.error_message_per_player[self.current_player] =
-                                            e.to_string();
-                                    } else {
-                                        self.error_message_per_player[self.current_player] =
-                                            String::new();
-                                    }
-                                    return Ok(());
-                                }
-                            }
-                            _ => {}
-                        },
-                        _ => {}
                     }
                 }
-                AcquireRequest::ChooseMergerSurvivor(player) if *player == self.current_player => {
-                    match event {
-                        event::Event::Key(event) => match (event.code, event.kind) {
-                            (KeyCode::Char(c), KeyEventKind::Press) if c.is_digit(10) => {
-                                let num = c.to_digit(10).unwrap();
-                                if num <= Hotel::count() as u32 {
-                                    let hotel = Hotel::from(num as usize - 1);
-                                    let response = AcquireResponse::MergerSurvivor(hotel);
-
-                                    let res =
-                                        self.acquire_game.handle_player_response(response.into());
-                                    if let Err(e) = res {
-                                        self.error_message_per_player[self.current_player] =
-                                            e.to_string();
-                                    } else {
-                                        self.error_message_per_player[self.current_player] =
-                                            String::new();
-                                    }
-                                    return Ok(());
+            }
+            AcquireRequest::ChooseDefunctChainToResolve(player)
+                if *player == self.current_player =>
+            {
+                if let event::Event::Key(event) = event {
+                    match (event.code, event.kind) {
+                        (KeyCode::Char(c), KeyEventKind::Press) if c.is_ascii_digit() => {
+                            let num = c.to_digit(10).unwrap();
+                            if (1..=Hotel::count() as u32).contains(&num) {
+                                let hotel = Hotel::from(num as usize - 1);
+                                let response = AcquireResponse::DefunctChainToResolve(hotel);
+
+                                let res = self.submit_response(response);
+                                if let Err(e) = res {
+                                    self.error_message_per_player[self.current_player] =
+                                        e.to_string();
+                                } else {
+                                    self.error_message_per_player[self.current_player] =
+                                        String::new();
                                 }
+                                return Ok(());
                             }
-                            _ => {}
-                        },
+                        }
                         _ => {}
                     }
                 }
-                AcquireRequest::ChooseDefunctChainToResolve(player)
-                    if *player == self.current_player =>
-                {
-                    match event {
-                        event::Event::Key(event) => match (event.code, event.kind) {
-                            (KeyCode::Char(c), KeyEventKind::Press) if c.is_digit(10) => {
-                                let num = c.to_digit(10).unwrap();
-                                if num <= Hotel::count() as u32 {
-                                    let hotel = Hotel::from(num as usize - 1);
-                                    let response = AcquireResponse::DefunctChainToResolve(hotel);
-
-                                    let res =
-                                        self.acquire_game.handle_player_response(response.into());
-                                    if let Err(e) = res {
-                                        self.error_message_per_player[self.current_player] =
-                                            e.to_string();
-                                    } else {
-                                        self.error_message_per_player[self.current_player] =
-                                            String::new();
-                                    }
-                                    return Ok(());
-                                }
-                            }
-                            _ => {}
-                        },
-                        _ => {}
+            }
+            AcquireRequest::DisposeStock(player) if *player == self.current_player => {
+                if let event::Event::Key(event) = event {
+                    if let (KeyCode::Backspace, KeyEventKind::Press) = (event.code, event.kind) {
+                        self.dispose_count.pop();
+                        return Ok(());
                     }
-                }
-                AcquireRequest::DisposeStock => match event {
-                    event::Event::Key(event) => match (event.code, event.kind) {
-                        (KeyCode::Char(c), KeyEventKind::Press) => {
-                            let choice = match c {
-                                'k' => AcquireResponse::DisposeStock(
-                                    self.current_player,
-                                    DisposeStockChoice::Keep,
-                                ),
-                                's' => AcquireResponse::DisposeStock(
-                                    self.current_player,
-                                    DisposeStockChoice::Sell,
-                                ),
-                                't' => AcquireResponse::DisposeStock(
-                                    self.current_player,
-                                    DisposeStockChoice::Trade,
-                                ),
-                                'K' => AcquireResponse::DisposeStock(
-                                    self.current_player,
-                                    DisposeStockChoice::KeepAll,
-                                ),
-                                'S' => AcquireResponse::DisposeStock(
-                                    self.current_player,
-                                    DisposeStockChoice::SellAll,
-                                ),
-                                'T' => AcquireResponse::DisposeStock(
-                                    self.current_player,
-                                    DisposeStockChoice::TradeAll,
-                                ),
-                                _ => return Ok(()),
-                            };
 
-                            let res = self.acquire_game.handle_player_response(choice);
-                            if let Err(e) = res {
-                                self.error_message_per_player[self.current_player] = e.to_string();
-                            } else {
-                                self.error_message_per_player[self.current_player] = String::new();
-                            }
+                    if let (KeyCode::Enter, KeyEventKind::Press) = (event.code, event.kind) {
+                        let counts: Vec<u32> = self
+                            .dispose_count
+                            .split(',')
+                            .map(|count| count.parse::<u32>().unwrap_or(0))
+                            .collect();
+                        let [keep, sell, trade] = counts[..] else {
+                            return Ok(());
+                        };
+                        self.dispose_count.clear();
+
+                        let response = AcquireResponse::DisposeStock(
+                            self.current_player,
+                            DisposeStockChoice::Split { keep, sell, trade },
+                        );
+                        let res = self.submit_response(response);
+                        if let Err(e) = res {
+                            self.error_message_per_player[self.current_player] = e.to_string();
+                        } else {
+                            self.error_message_per_player[self.current_player] = String::new();
+                        }
+                        return Ok(());
+                    }
 
+                    if let (KeyCode::Char(c), KeyEventKind::Press) = (event.code, event.kind) {
+                        // each count is at most two digits and a split has three counts
+                        let typing = self.dispose_count.split(',').next_back().unwrap_or("");
+                        if c.is_ascii_digit() {
+                            if typing.len() < 2 {
+                                self.dispose_count.push(c);
+                            }
                             return Ok(());
                         }
-                        _ => {}
-                    },
-                    _ => {}
-                },
-                AcquireRequest::BuyStock(player) if *player == self.current_player => match event {
-                    event::Event::Key(event) => match (event.code, event.kind) {
-                        (KeyCode::Char(c), KeyEventKind::Press) if c.is_digit(10) => {
+                        if c == ',' {
+                            if self.dispose_count.matches(',').count() < 2 {
+                                self.dispose_count.push(c);
+                            }
+                            return Ok(());
+                        }
+                    }
+
+                    // a split in progress is only finished with Enter
+                    if event.kind == KeyEventKind::Press && !self.dispose_count.contains(',') {
+                        let keys = &self.keys;
+                        let code = event.code;
+                        let count = self.dispose_count.parse::<u32>().ok();
+                        let choice = match count {
+                            Some(count) if code == keys.sell => DisposeStockChoice::SellN(count),
+                            Some(count) if code == keys.trade => DisposeStockChoice::TradeN(count),
+                            _ if code == keys.keep => DisposeStockChoice::Keep,
+                            _ if code == keys.sell => DisposeStockChoice::Sell,
+                            _ if code == keys.trade => DisposeStockChoice::Trade,
+                            _ if code == keys.keep_all => DisposeStockChoice::KeepAll,
+                            _ if code == keys.sell_all => DisposeStockChoice::SellAll,
+                            _ if code == keys.trade_all => DisposeStockChoice::TradeAll,
+                            _ => return Ok(()),
+                        };
+                        self.dispose_count.clear();
+
+                        let response = AcquireResponse::DisposeStock(self.current_player, choice);
+                        let res = self.submit_response(response);
+                        if let Err(e) = res {
+                            self.error_message_per_player[self.current_player] = e.to_string();
+                        } else {
+                            self.error_message_per_player[self.current_player] = String::new();
+                        }
+
+                        return Ok(());
+                    }
+                }
+            }
+            AcquireRequest::BuyStock(player) if *player == self.current_player => {
+                if let event::Event::Key(event) = event {
+                    match (event.code, event.kind) {
+                        (KeyCode::Char(c), KeyEventKind::Press) if c.is_ascii_digit() => {
                             let num = c.to_digit(10).unwrap();
-                            if num <= Hotel::count() as u32 {
+                            if (1..=Hotel::count() as u32).contains(&num) {
                                 let hotel = Hotel::from(num as usize - 1);
 
-                                let response = AcquireResponse::BuyStock(
-                                    BuyStockChoice::Buy(hotel),
-                                );
+                                let response =
+                                    AcquireResponse::BuyStock(BuyStockChoice::Buy(hotel));
 
-                                let res = self.acquire_game.handle_player_response(response.into());
+                                let res = self.submit_response(response);
                                 if let Err(e) = res {
                                     self.error_message_per_player[self.current_player] =
                                         e.to_string();
@@ -306,10 +1032,34 @@ impl TuiApp {
                                 }
                                 return Ok(());
                             }
-                        },
-                        (KeyCode::Char('S'), KeyEventKind::Press) => {
+                        }
+                        // shift and a hotel's digit buys as many of it as allowed
+                        (KeyCode::Char(c), KeyEventKind::Press) if SHIFTED_DIGITS.contains(&c) => {
+                            let index = SHIFTED_DIGITS.iter().position(|d| *d == c).unwrap();
+                            let hotel = Hotel::from(index);
+                            let buys_remaining = self.acquire_game.buys_remaining().unwrap_or(0);
+
+                            let response = AcquireResponse::BuyStock(BuyStockChoice::BuyN(
+                                hotel,
+                                buys_remaining,
+                            ));
+                            let res = self.submit_response(response);
+                            if let Err(e) = res {
+                                self.error_message_per_player[self.current_player] = e.to_string();
+                            } else {
+                                self.error_message_per_player[self.current_player] = String::new();
+                            }
+                            return Ok(());
+                        }
+                        (code, KeyEventKind::Press) if code == self.keys.auto_buy => {
+                            self.auto_buy();
+                            return Ok(());
+                        }
+                        (code, KeyEventKind::Press)
+                            if code == self.keys.pass || code == KeyCode::Esc =>
+                        {
                             let response = AcquireResponse::BuyStock(BuyStockChoice::Pass);
-                            let res = self.acquire_game.handle_player_response(response.into());
+                            let res = self.submit_response(response);
                             if let Err(e) = res {
                                 self.error_message_per_player[self.current_player] = e.to_string();
                             } else {
@@ -318,26 +1068,53 @@ impl TuiApp {
                             return Ok(());
                         }
                         _ => {}
-                    },
-                    _ => {}
-                },
-                AcquireRequest::EndGame(player) if *player == self.current_player => {}
-
-                _ => {}
+                    }
+                }
+            }
+            AcquireRequest::EndGame(player) if *player == self.current_player => {
+                if let event::Event::Key(event) = event {
+                    let quit = match (event.code, event.kind) {
+                        (KeyCode::Char(' '), KeyEventKind::Press) => true,
+                        (KeyCode::Esc, KeyEventKind::Press) => false,
+                        _ => return Ok(()),
+                    };
+
+                    let response = AcquireResponse::EndGame(quit);
+                    let res = self.submit_response(response);
+                    if let Err(e) = res {
+                        self.error_message_per_player[self.current_player] = e.to_string();
+                    } else {
+                        self.error_message_per_player[self.current_player] = String::new();
+                    }
+                    return Ok(());
+                }
             }
 
-            // Global controls
-            match event {
-                event::Event::Key(event) => match (event.code, event.kind) {
-                    (KeyCode::Char(c), KeyEventKind::Press) if c.is_digit(10) => {
-                        let num = c.to_digit(10).unwrap();
-                        if num <= self.acquire_game.players.len() as u32 {
-                            self.current_player = num as usize - 1;
-                        }
+            _ => {}
+        }
+
+        // Global controls
+        if let event::Event::Key(event) = event {
+            match (event.code, event.kind) {
+                (KeyCode::Char(c), KeyEventKind::Press)
+                    if c.is_ascii_digit() && !self.follow_active_player =>
+                {
+                    let num = c.to_digit(10).unwrap();
+                    if (1..=self.acquire_game.players.len() as u32).contains(&num) {
+                        self.current_player = num as usize - 1;
                     }
-                    (KeyCode::Char('q'), KeyEventKind::Press) => self.exit = true,
-                    _ => {}
-                },
+                }
+                (code, KeyEventKind::Press) if code == self.keys.follow => {
+                    self.follow_active_player = !self.follow_active_player;
+                    self.follow_active_player();
+                }
+                (code, KeyEventKind::Press) if code == self.keys.undo => self.undo(),
+                (code, KeyEventKind::Press) if code == self.keys.quit => self.confirm_quit = true,
+                (code, KeyEventKind::Press)
+                    if code == self.keys.resign && !self.acquire_game.is_game_over() =>
+                {
+                    self.confirm_resign = true
+                }
                 _ => {}
             }
         }
@@ -345,11 +1122,214 @@ impl TuiApp {
         Ok(())
     }
 
+    // whether the key plays a tile or disposes of stock for the viewed player
+    fn answers_request(&self, code: KeyCode) -> bool {
+        if self.spectate {
+            return false;
+        }
+        let keys = &self.keys;
+        match *self.acquire_game.get_current_request() {
+            AcquireRequest::PlayTile(player) if player == self.current_player => {
+                keys.tile_slots.contains(&code)
+            }
+            AcquireRequest::DisposeStock(player) if player == self.current_player => [
+                keys.keep,
+                keys.sell,
+                keys.trade,
+                keys.keep_all,
+                keys.sell_all,
+                keys.trade_all,
+            ]
+            .contains(&code),
+            _ => false,
+        }
+    }
+
+    // the turn clock starts over once the break is over, rather than counting the break
+    fn resume(&mut self) {
+        self.paused = false;
+        self.turn_started = Instant::now();
+    }
+
+    // buys the cheapest stock the viewed player can afford a share at a time,
+    // until the turn's buys, their cash or the bank's stock run out
+    fn auto_buy(&mut self) {
+        let player = self.current_player;
+        let buys_remaining = self.acquire_game.buys_remaining().unwrap_or(0);
+        if self.acquire_game.buyable_hotels(player).is_empty() {
+            self.error_message_per_player[player] = "There is no stock you can buy".to_string();
+            return;
+        }
+
+        // the last buy of the turn ends the phase, after which nothing more is bought
+        for _ in 0..buys_remaining {
+            if *self.acquire_game.get_current_request() != AcquireRequest::BuyStock(player) {
+                break;
+            }
+            let Some((hotel, _)) = self
+                .acquire_game
+                .buyable_hotels(player)
+                .into_iter()
+                .min_by_key(|(_, price)| *price)
+            else {
+                break;
+            };
+
+            let response = AcquireResponse::BuyStock(BuyStockChoice::Buy(hotel));
+            if let Err(e) = self.submit_response(response) {
+                self.error_message_per_player[player] = e;
+                return;
+            }
+        }
+        self.error_message_per_player[player] = String::new();
+    }
+
+    // only the player whose turn it is may take back their last action
+    fn undo(&mut self) {
+        if self.acquire_game.get_current_request().get_player() != self.current_player {
+            return;
+        }
+        #[cfg(feature = "serde")]
+        if self.remote.is_some() {
+            self.error_message_per_player[self.current_player] =
+                "Undo is not available in network games".to_string();
+            return;
+        }
+
+        let res = self.acquire_game.undo();
+        if let Err(e) = res {
+            self.error_message_per_player[self.current_player] = e.to_string();
+        } else {
+            self.error_message_per_player[self.current_player] = String::new();
+        }
+    }
+
+    fn resign(&mut self) {
+        #[cfg(feature = "serde")]
+        if self.remote.is_some() {
+            self.error_message_per_player[self.current_player] =
+                "Resigning is not available in network games".to_string();
+            return;
+        }
+
+        if let Err(e) = self.acquire_game.resign_player(self.current_player) {
+            self.error_message_per_player[self.current_player] = e.to_string();
+        }
+    }
+
+    // network games are never offered a concession, the server could not follow it.
+    // nor is a game with hidden cash, as the offer would give away who is leading
+    fn offers_concession(&self) -> bool {
+        #[cfg(feature = "serde")]
+        if self.remote.is_some() {
+            return false;
+        }
+        !self.concession_declined
+            && !self.spectate
+            && !self.acquire_game.is_game_over()
+            && !self.acquire_game.players[self.current_player].resigned
+            && (0..self.acquire_game.players.len()).all(|player| self.is_cash_visible(player))
+            && matches!(
+                self.acquire_game.is_mathematically_decided(),
+                Some(leader) if leader != self.current_player
+            )
+    }
+
+    fn concede(&mut self) {
+        if let Err(e) = self.acquire_game.concede_to_leader(self.current_player) {
+            self.error_message_per_player[self.current_player] = e.to_string();
+        }
+    }
+
+    fn handle_click(&mut self, column: u16, row: u16) {
+        let contains = |rect: &Rect| {
+            column >= rect.x && column < rect.right() && row >= rect.y && row < rect.bottom()
+        };
+
+        if contains(&self.board_rect.borrow()) {
+            self.error_message_per_player[self.current_player] =
+                "Click a tile in your hand to play it".to_string();
+            return;
+        }
+
+        let clicked_tile = self.hand_tile_rects.borrow().iter().position(contains);
+        let Some(index) = clicked_tile else {
+            return;
+        };
+
+        if *self.acquire_game.get_current_request() != AcquireRequest::PlayTile(self.current_player)
+        {
+            return;
+        }
+
+        let Some(&tile) = self.acquire_game.players[self.current_player]
+            .tiles
+            .get(index)
+        else {
+            return;
+        };
+        let res = self.submit_response(AcquireResponse::Tile(tile));
+        if let Err(e) = res {
+            self.error_message_per_player[self.current_player] = e.to_string();
+        } else {
+            self.error_message_per_player[self.current_player] = String::new();
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn save_game(&self) {
+        let message = match self.acquire_game.save_to_path(Path::new(SAVE_FILE_PATH)) {
+            Ok(()) => format!("Game saved to {}", SAVE_FILE_PATH),
+            Err(e) => format!("Failed to save game: {}", e),
+        };
+        self.acquire_messages.send_message(&message);
+    }
+
+    #[cfg(feature = "serde")]
+    fn load_game(&mut self) {
+        if self.remote.is_some() {
+            self.acquire_messages
+                .send_message("Games cannot be loaded in a network game");
+            return;
+        }
+
+        let message = match AcquireGame::load_from_path(
+            Path::new(SAVE_FILE_PATH),
+            self.acquire_messages.clone(),
+        ) {
+            Ok(acquire_game) => {
+                let number_of_players = acquire_game.players.len();
+                self.acquire_messages.inner().set_player_names(
+                    acquire_game
+                        .players
+                        .iter()
+                        .map(|player| player.name.clone())
+                        .collect(),
+                );
+                self.acquire_game = acquire_game;
+                self.error_message_per_player = vec![String::new(); number_of_players];
+                let number_of_bots = self.bots.iter().filter(|bot| bot.is_some()).count();
+                self.bots = create_bots(number_of_players, number_of_bots);
+                self.current_player = self.current_player.min(number_of_players - 1);
+                format!("Game loaded from {}", SAVE_FILE_PATH)
+            }
+            Err(e) => format!("Failed to load game: {}", e),
+        };
+        self.acquire_messages.send_message(&message);
+    }
+
+    // the smallest terminal that fits the board with the panels around it
+    fn get_needed_size(&self) -> (u16, u16) {
+        let board = &self.acquire_game.board;
+        let needed_width =
+            self.cell_width * board.cols as u16 + self.cell_width * 10 + self.cell_width * 10;
+        // one extra row for the status bar
+        let needed_height = self.cell_height * board.rows as u16 + self.cell_height * 6 + 1;
+        (needed_width, needed_height)
+    }
+
     fn split_rects(&self, rect: Rect) -> Option<InnerRects> {
-        let needed_width = self.cell_width * game_board::BOARD_COLS as u16
-            + self.cell_width * 10
-            + self.cell_width * 10;
-        let needed_height = self.cell_height * game_board::BOARD_ROWS as u16 + self.cell_height * 6;
+        let (needed_width, needed_height) = self.get_needed_size();
 
         if rect.width < needed_width || rect.height < needed_height {
             return None;
@@ -361,6 +1341,18 @@ impl TuiApp {
         let player_height = self.cell_height * 4;
         let prompt_height = self.cell_height * 2;
 
+        // the status bar takes the bottom row across the full width
+        let status_bar = Rect {
+            x: rect.x,
+            y: rect.bottom() - 1,
+            width: rect.width,
+            height: 1,
+        };
+        let rect = Rect {
+            height: rect.height - 1,
+            ..rect
+        };
+
         let game_board_width = rect.width - stock_width - message_width;
         let game_board_height = rect.height - player_height - prompt_height;
 
@@ -410,19 +1402,65 @@ impl TuiApp {
             stocks,
             prompt,
             player,
+            status_bar,
         })
     }
 
+    // the phase, whose turn it is, the tiles left and the viewing player's cash on one line,
+    // for when the panels are too cramped to find them at a glance
+    fn render_status_bar(&self, area: Rect, frame: &mut Frame) {
+        let game = &self.acquire_game;
+        let phase = match game.current_phase() {
+            GamePhase::GameStart { .. } => "Drawing starting tiles".to_string(),
+            GamePhase::PlayTile { .. } => "Playing a tile".to_string(),
+            GamePhase::Merger { survivor, .. } => format!("Merging into {}", survivor),
+            GamePhase::DisposeStock {
+                defunct, survivor, ..
+            } => format!("Disposing {} → {}", defunct, survivor),
+            GamePhase::BuyStock { buys_remaining, .. } => {
+                format!("Buying stock ({} left)", buys_remaining)
+            }
+            GamePhase::EndGame { .. } => "Deciding whether to end the game".to_string(),
+            GamePhase::GameOver => "Game over".to_string(),
+        };
+
+        let mut spans = vec![phase.bold()];
+        if !game.is_game_over() {
+            let player = game.get_current_request().get_player();
+            spans.push(" │ ".dark_gray());
+            spans.push(format!("{}'s turn", game.players[player].name).into());
+        }
+        spans.push(" │ ".dark_gray());
+        spans.push(format!("{} tiles left", game.get_number_of_tiles_left()).into());
+        spans.push(" │ ".dark_gray());
+        spans.push(match self.is_cash_visible(self.current_player) {
+            true => format!("Cash ${}", game.players[self.current_player].cash).into(),
+            false => "Cash $???".into(),
+        });
+
+        frame.render_widget(Paragraph::new(Line::from(spans)).centered(), area);
+    }
+
     fn render_game_board(&self, area: Rect, frame: &mut Frame) -> Rect {
         let title = Title::from(" acTUIre ".bold());
 
+        // the seed lets players compare results or replay the same draws
+        let seed_text = match &self.daily_date {
+            Some(date) => format!(" Daily {} ", date),
+            None => format!(" Seed {} ", self.acquire_game.seed()),
+        };
+        let seed_title = Title::from(seed_text)
+            .position(Position::Bottom)
+            .alignment(Alignment::Right);
+
         let block = Block::default()
             .title(title.alignment(Alignment::Center))
+            .title(seed_title)
             .borders(Borders::ALL)
             .border_set(border::THICK)
             .border_type(BorderType::Rounded);
 
-        let inner = block.inner(area).clone();
+        let inner = block.inner(area);
         frame.render_widget(block, area);
 
         let cell_width = self.cell_width;
@@ -430,20 +1468,15 @@ impl TuiApp {
 
         let padding = 1;
 
-        let grid_width =
-            u16::try_from(cell_width * game_board::BOARD_COLS as u16 + 2 * padding).unwrap();
-        let grid_height =
-            u16::try_from(cell_height * game_board::BOARD_ROWS as u16 + 2 * padding).unwrap();
+        let (rows, cols) = (self.acquire_game.board.rows, self.acquire_game.board.cols);
+        let grid_width = cell_width * cols as u16 + 2 * padding;
+        let grid_height = cell_height * rows as u16 + 2 * padding;
 
         let row_constraints =
-            std::iter::repeat(Constraint::Length(u16::try_from(cell_height).unwrap()))
-                .take(game_board::BOARD_ROWS)
-                .collect::<Vec<_>>();
+            std::iter::repeat_n(Constraint::Length(cell_height), rows).collect::<Vec<_>>();
 
         let col_constraints =
-            std::iter::repeat(Constraint::Length(u16::try_from(cell_width).unwrap()))
-                .take(game_board::BOARD_COLS)
-                .collect::<Vec<_>>();
+            std::iter::repeat_n(Constraint::Length(cell_width), cols).collect::<Vec<_>>();
 
         let (center_x, center_y) = (inner.x + inner.width / 2, inner.y + inner.height / 2);
         let board_rect = Rect {
@@ -458,6 +1491,7 @@ impl TuiApp {
             .border_type(BorderType::Rounded);
 
         frame.render_widget(board_block, board_rect);
+        *self.board_rect.borrow_mut() = board_rect;
 
         let row_rects = Layout::default()
             .direction(Direction::Vertical)
@@ -475,10 +1509,9 @@ impl TuiApp {
                 .split(*row_rect);
 
             for (c, cell_rect) in col_rects.iter().enumerate() {
-                let tile: Tile = (r, c).into();
                 let single_row_text = format!(
                     "{:^length$}",
-                    tile.to_string(),
+                    self.get_cell_text(r, c),
                     length = (cell_width - 2).into()
                 );
 
@@ -493,10 +1526,9 @@ impl TuiApp {
                 //   the interesting text
                 //   pad with half the pad lines budget
                 //   join with newlines
-                let text = std::iter::repeat(pad_line.clone())
-                    .take(num_pad_lines / 2)
+                let text = std::iter::repeat_n(pad_line.clone(), num_pad_lines / 2)
                     .chain(std::iter::once(single_row_text))
-                    .chain(std::iter::repeat(pad_line).take(num_pad_lines / 2))
+                    .chain(std::iter::repeat_n(pad_line, num_pad_lines / 2))
                     .collect::<Vec<_>>()
                     .join("\n");
 
@@ -510,20 +1542,66 @@ impl TuiApp {
         inner
     }
 
-    fn get_cell_block(&self, row: usize, col: usize) -> Block {
+    // hotel cells show the chain's abbreviation and size, unresolved placements a marker,
+    // and everything else its coordinate
+    fn get_cell_text(&self, row: usize, col: usize) -> String {
+        match self.acquire_game.board.get_cell_state(row, col) {
+            game_board::Cell::Hotel(hotel) => format!(
+                "{}{}",
+                &hotel.to_string()[..2],
+                self.acquire_game.get_current_chain_size(hotel)
+            ),
+            game_board::Cell::Conflict(_) => "!".to_string(),
+            game_board::Cell::Empty | game_board::Cell::Independent => {
+                Tile::from((row, col)).to_string()
+            }
+        }
+    }
+
+    // the tile suggested to the viewed player while it is their turn to play one
+    fn hinted_tile(&self) -> Option<Tile> {
+        match *self.acquire_game.get_current_request() {
+            AcquireRequest::PlayTile(player) if self.show_hint && player == self.current_player => {
+                self.acquire_game.suggest_tile(player)
+            }
+            _ => None,
+        }
+    }
+
+    fn get_cell_block(&self, row: usize, col: usize) -> Block<'_> {
         let cell_state = self.acquire_game.board.get_cell_state(row, col);
 
         let (bg_color, fg_color) = match cell_state {
             game_board::Cell::Empty => (Color::Black, Color::White),
-            game_board::Cell::Hotel(hotel) => (HOTEL_COLORS[hotel as usize], Color::Black),
+            game_board::Cell::Hotel(hotel) => (self.palette.hotel_color(hotel), Color::Black),
             game_board::Cell::Independent => (Color::White, Color::Black),
             game_board::Cell::Conflict(_) => (Color::Gray, Color::Black),
         };
 
-        Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .style(Style::default().bg(bg_color).fg(fg_color))
-            .border_type(BorderType::Rounded)
+            .border_type(BorderType::Rounded);
+        if self.hinted_tile() == Some(Tile::from((row, col))) {
+            block = block.border_style(Style::default().fg(Color::Yellow).bold());
+        } else if self.is_drawn_tile_marked(row, col) {
+            block = block.border_style(Style::default().fg(Color::Magenta));
+        }
+
+        match cell_state {
+            game_board::Cell::Hotel(hotel) => match self.palette.hotel_symbol(hotel) {
+                Some(symbol) => block.title(symbol.bold()),
+                None => block,
+            },
+            _ => block,
+        }
+    }
+
+    // the tiles of these cells are in players' hands and are likely to be played soon
+    fn is_drawn_tile_marked(&self, row: usize, col: usize) -> bool {
+        self.show_drawn_tiles
+            && self.acquire_game.board.get_cell_state(row, col) == game_board::Cell::Empty
+            && !self.acquire_game.is_tile_in_bag(Tile::from((row, col)))
     }
 
     fn get_cell_text_style(&self, row: usize, col: usize) -> Style {
@@ -531,7 +1609,7 @@ impl TuiApp {
 
         let (bg_color, fg_color) = match cell_state {
             game_board::Cell::Empty => (Color::Black, Color::White),
-            game_board::Cell::Hotel(hotel) => (HOTEL_COLORS[hotel as usize], Color::Black),
+            game_board::Cell::Hotel(hotel) => (self.palette.hotel_color(hotel), Color::Black),
             game_board::Cell::Independent => (Color::White, Color::Black),
             game_board::Cell::Conflict(_) => (Color::Gray, Color::Black),
         };
@@ -539,6 +1617,22 @@ impl TuiApp {
         Style::default().bg(bg_color).fg(fg_color)
     }
 
+    // green when the tile can be played now, gray when it may become playable later
+    // and red when it would merge two safe chains and is dead for good
+    fn get_hand_tile_border_style(&self, tile: &Tile) -> Style {
+        if self.hinted_tile() == Some(*tile) {
+            return Style::default().fg(Color::Yellow).bold();
+        }
+
+        let color = match self.acquire_game.board.is_cell_playable(tile.row, tile.col) {
+            Ok(_) => Color::Green,
+            Err(reason) if reason.is_permanent() => Color::Red,
+            Err(_) => Color::DarkGray,
+        };
+
+        Style::default().fg(color)
+    }
+
     fn render_stocks(&self, area: Rect, frame: &mut Frame) -> Rect {
         let title = Title::from(" Stocks ".bold());
 
@@ -547,17 +1641,39 @@ impl TuiApp {
             .borders(Borders::ALL)
             .border_set(border::THICK)
             .border_type(BorderType::Rounded);
-        let inner = block.inner(area).clone();
+        let inner = block.inner(area);
 
+        // at the minimum terminal size a band only has room for a line or two,
+        // so the details are packed into two lines unless all five fit
         let stock_rect_height = inner.height / 7;
 
         for hotel in Hotel::iter() {
-            let color = HOTEL_COLORS[hotel as usize];
+            let color = self.palette.hotel_color(hotel);
             let hotel_name = format!("{:?}", hotel);
             let stock_count = self.acquire_game.get_current_stock_availability(hotel);
             let current_price = self.acquire_game.get_current_stock_price(hotel);
             let chain_size = self.acquire_game.get_current_chain_size(hotel);
 
+            // bonuses only mean something for chains on the board
+            let (majority_bonus, minority_bonus) = if chain_size > 0 {
+                (
+                    format!(
+                        "${}.00",
+                        self.acquire_game
+                            .board
+                            .get_hotel_majority_stock_bonus(hotel)
+                    ),
+                    format!(
+                        "${}.00",
+                        self.acquire_game
+                            .board
+                            .get_hotel_minority_stock_bonus(hotel)
+                    ),
+                )
+            } else {
+                ("-".to_string(), "-".to_string())
+            };
+
             let stock_rect = Rect {
                 x: inner.x,
                 y: inner.y + (stock_rect_height * hotel as u16),
@@ -565,25 +1681,41 @@ impl TuiApp {
                 height: stock_rect_height,
             };
 
+            // safe chains can no longer be merged away
+            let title = match self.acquire_game.board.is_chain_safe(hotel) {
+                true => Line::from(vec![hotel_name.bold(), " SAFE ".black().on_white()]),
+                false => Line::from(vec![hotel_name.bold()]),
+            };
             let stock_block = Block::default()
-                .title(hotel_name.bold())
+                .title(title)
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
                 .style(Style::default().fg(color).bg(Color::Black));
-            let stock_text = Text::from(vec![
-                Line::from(vec![Span::styled(
+            let lines = if stock_rect_height >= 7 {
+                vec![
                     format!("Available Stock: {}", stock_count),
-                    Style::default().fg(Color::White),
-                )]),
-                Line::from(vec![Span::styled(
                     format!("Price: ${}.00", current_price),
-                    Style::default().fg(Color::White),
-                )]),
-                Line::from(vec![Span::styled(
                     format!("Chain Size: {}", chain_size),
-                    Style::default().fg(Color::White),
-                )]),
-            ]);
+                    format!("Majority: {}", majority_bonus),
+                    format!("Minority: {}", minority_bonus),
+                ]
+            } else {
+                vec![
+                    format!(
+                        "Stock: {} ${}.00 Size: {}",
+                        stock_count, current_price, chain_size
+                    ),
+                    format!("Bonus: {} / {}", majority_bonus, minority_bonus),
+                ]
+            };
+            let stock_text = Text::from(
+                lines
+                    .into_iter()
+                    .map(|line| {
+                        Line::from(vec![Span::styled(line, Style::default().fg(Color::White))])
+                    })
+                    .collect::<Vec<_>>(),
+            );
 
             frame.render_widget(Paragraph::new(stock_text).block(stock_block), stock_rect);
         }
@@ -592,7 +1724,14 @@ impl TuiApp {
     }
 
     fn render_messages(&self, area: Rect, frame: &mut Frame) -> Rect {
-        let title = Title::from(" Messages ".bold());
+        let log_name = match self.show_history {
+            true => "Moves",
+            false => "Messages",
+        };
+        let title = match self.message_scroll {
+            0 => Title::from(format!(" {} ", log_name).bold()),
+            _ => Title::from(format!(" {} (scrolled) ", log_name).bold()),
+        };
 
         let block = Block::default()
             .title(title.alignment(Alignment::Center))
@@ -600,11 +1739,11 @@ impl TuiApp {
             .border_set(border::THICK)
             .border_type(BorderType::Rounded);
 
-        let inner_area = block.inner(area).clone();
+        let inner_area = block.inner(area);
         frame.render_widget(block, area);
 
-        let messages = self.acquire_messages.get_messages();
-        for (i, message) in messages.iter().rev().enumerate() {
+        let messages = self.get_log_lines();
+        for (i, message) in messages.iter().rev().skip(self.message_scroll).enumerate() {
             if i >= inner_area.height as usize {
                 break;
             }
@@ -621,11 +1760,97 @@ impl TuiApp {
         inner_area
     }
 
+    // the local user controls the seat they joined in a network game, or every
+    // seat the computer is not playing in a local one
+    fn is_cash_visible(&self, player: usize) -> bool {
+        if !self.hidden_cash || self.acquire_game.is_game_over() {
+            return true;
+        }
+        #[cfg(feature = "serde")]
+        if let Some(remote) = &self.remote {
+            return remote.player == player;
+        }
+        !self.spectate && self.bots[player].is_none()
+    }
+
+    // the leaders by net worth, none while that would give away hidden cash
+    fn visible_leaders(&self) -> Vec<usize> {
+        match (0..self.acquire_game.players.len()).all(|player| self.is_cash_visible(player)) {
+            true => self.acquire_game.current_leader(),
+            false => Vec::new(),
+        }
+    }
+
+    // one line per player with their cash, net worth, shares and hand, for spectators
+    fn render_all_players(&self, area: Rect, frame: &mut Frame) -> Rect {
+        let title = Title::from(" Players ".bold());
+
+        let block = Block::default()
+            .title(title.alignment(Alignment::Center))
+            .borders(Borders::ALL)
+            .border_set(border::THICK)
+            .border_type(BorderType::Rounded);
+
+        let mut header = vec![format!("{:<12}{:>7}{:>7}", "", "Cash", "Net").bold()];
+        for hotel in Hotel::iter() {
+            header.push(
+                format!("{:>3}", &hotel.to_string()[..2])
+                    .bold()
+                    .fg(self.palette.hotel_color(hotel)),
+            );
+        }
+        header.push("  Tiles".bold());
+
+        let leaders = self.visible_leaders();
+        let active_player = self.acquire_game.get_players_to_respond().first().copied();
+        let mut lines = vec![Line::from(header)];
+        for (index, player) in self.acquire_game.players.iter().enumerate() {
+            let mut name = player.name.clone();
+            if leaders.contains(&index) {
+                name = format!("♛ {}", name);
+            }
+            let name = format!("{:<12.12}", name);
+            let mut spans = vec![match active_player == Some(index) {
+                true => name.bold().yellow(),
+                false => name.into(),
+            }];
+            match self.is_cash_visible(index) {
+                true => spans.extend([
+                    format!("{:>7}", player.cash).into(),
+                    format!("{:>7}", self.acquire_game.net_worth(index)).into(),
+                ]),
+                false => spans.push(format!("{:>7}{:>7}", "???", "???").into()),
+            }
+            for hotel in Hotel::iter() {
+                spans.push(format!("{:>3}", player.stocks[hotel as usize]).into());
+            }
+            let tiles = match player.resigned {
+                true => "resigned".to_string(),
+                false => player
+                    .tiles
+                    .iter()
+                    .map(|tile| tile.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            };
+            spans.push(format!("  {}", tiles).into());
+            lines.push(Line::from(spans));
+        }
+
+        let inner = block.inner(area);
+        frame.render_widget(Paragraph::new(Text::from(lines)).block(block), area);
+
+        inner
+    }
+
     fn render_player(&self, area: Rect, frame: &mut Frame) -> Rect {
-        let player_name = self.acquire_game.players[self.current_player].name.clone();
+        let mut player_name = self.acquire_game.players[self.current_player].name.clone();
+        // the leaders by net worth are marked with a crown
+        if self.visible_leaders().contains(&self.current_player) {
+            player_name = format!("♛ {}", player_name);
+        }
 
         let title = Title::from(player_name.bold());
-        let tiles_title = Title::from(" Tiles ".bold());
         let cash_title = Title::from(" Cash ".bold());
 
         let parent_block = Block::default()
@@ -634,7 +1859,7 @@ impl TuiApp {
             .border_set(border::THICK)
             .border_type(BorderType::Rounded);
 
-        let inner = parent_block.inner(area).clone();
+        let inner = parent_block.inner(area);
 
         let cash_width = inner.width / 3;
         let half_height = inner.height / 2;
@@ -660,12 +1885,6 @@ impl TuiApp {
             height: half_height,
         };
 
-        let tiles_block = Block::default()
-            .title(tiles_title.alignment(Alignment::Center))
-            .borders(Borders::ALL)
-            .border_set(border::THICK)
-            .border_type(BorderType::Rounded);
-
         let cash_block = Block::default()
             .title(cash_title.alignment(Alignment::Center))
             .borders(Borders::ALL)
@@ -675,8 +1894,20 @@ impl TuiApp {
         frame.render_widget(parent_block, area);
 
         // render the cash
-        let cash = self.acquire_game.players[self.current_player].cash;
-        let cash_text = Text::from(vec![Line::from(vec![format!("${}.00", cash).into()])]);
+        let cash_text = match self.is_cash_visible(self.current_player) {
+            true => {
+                let cash = self.acquire_game.players[self.current_player].cash;
+                let net_worth = self.acquire_game.net_worth(self.current_player);
+                Text::from(vec![
+                    Line::from(vec![format!("${}.00", cash).into()]),
+                    Line::from(vec![format!("Net: ${}.00", net_worth).into()]),
+                ])
+            }
+            false => Text::from(vec![
+                Line::from(vec!["$???".into()]),
+                Line::from(vec!["Net: $???".into()]),
+            ]),
+        };
         frame.render_widget(
             Paragraph::new(cash_text).centered().block(cash_block),
             cash_rect,
@@ -685,10 +1916,8 @@ impl TuiApp {
         // render the tiles
         let tiles = self.acquire_game.players[self.current_player].tiles.clone();
 
-        let col_constraints =
-            std::iter::repeat(Constraint::Length(u16::try_from(self.cell_width).unwrap()))
-                .take(tiles.len() as usize)
-                .collect::<Vec<_>>();
+        let col_constraints = std::iter::repeat_n(Constraint::Length(self.cell_width), tiles.len())
+            .collect::<Vec<_>>();
 
         let col_rects = Layout::default()
             .direction(Direction::Horizontal)
@@ -696,6 +1925,7 @@ impl TuiApp {
             .horizontal_margin(1)
             .constraints(col_constraints.clone())
             .split(tiles_rect);
+        *self.hand_tile_rects.borrow_mut() = col_rects.to_vec();
 
         for (i, tile_rect) in col_rects.iter().enumerate() {
             let tile: Tile = tiles[i];
@@ -716,16 +1946,20 @@ impl TuiApp {
             //   the interesting text
             //   pad with half the pad lines budget
             //   join with newlines
-            let text = std::iter::repeat(pad_line.clone())
-                .take(num_pad_lines / 2)
+            let text = std::iter::repeat_n(pad_line.clone(), num_pad_lines / 2)
                 .chain(std::iter::once(single_row_text))
-                .chain(std::iter::repeat(pad_line).take(num_pad_lines / 2))
+                .chain(std::iter::repeat_n(pad_line, num_pad_lines / 2))
                 .collect::<Vec<_>>()
                 .join("\n");
 
+            let tile_block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(self.get_hand_tile_border_style(&tile));
+
             let cell_text = Paragraph::new(text)
-                .block(self.get_cell_block(0, 0))
-                .style(self.get_cell_text_style(0, 0))
+                .block(tile_block)
+                .style(Style::default().bg(Color::Black).fg(Color::White))
                 .alignment(Alignment::Center);
             frame.render_widget(cell_text, *tile_rect);
         }
@@ -734,7 +1968,7 @@ impl TuiApp {
         let stock_rect_width = holdings_rect.width / 7;
         for hotel in Hotel::iter() {
             let hotel_name = format!("{:?}", hotel);
-            let color = HOTEL_COLORS[hotel as usize];
+            let color = self.palette.hotel_color(hotel);
             let num_shares = self.acquire_game.players[self.current_player].stocks[hotel as usize];
 
             let hotel_rect = Rect {
@@ -770,18 +2004,467 @@ impl TuiApp {
         inner
     }
 
+    // the published price chart with a column for each price group, the rows the
+    // chains on the board are in are highlighted
+    fn render_price_table(&self, area: Rect, frame: &mut Frame) -> Rect {
+        let title = Title::from(" Price Chart ".bold());
+
+        let block = Block::default()
+            .title(title.alignment(Alignment::Center))
+            .borders(Borders::ALL)
+            .border_set(border::THICK)
+            .border_type(BorderType::Rounded);
+
+        // the cheapest hotel of each price group stands in for the whole group
+        let groups = [Hotel::Tower, Hotel::American, Hotel::Imperial];
+        let active_prices: Vec<(Hotel, u32)> = self
+            .acquire_game
+            .board
+            .get_active_hotels()
+            .into_iter()
+            .map(|hotel| (hotel, self.acquire_game.get_current_stock_price(hotel)))
+            .collect();
+
+        let mut group_names = vec![format!("{:6}", "").into()];
+        let mut column_names = vec![format!("{:6}", "Tiles").bold()];
+        for group in groups {
+            let names = Hotel::iter()
+                .filter(|hotel| hotel.get_price_group() == group.get_price_group())
+                .map(|hotel| hotel.to_string()[..2].to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            group_names.push(format!("  {:^18}", names).bold());
+            column_names.push(format!("  {:>5} {:>6} {:>5}", "Price", "Major", "Minor").bold());
+        }
+
+        let mut lines = vec![Line::from(group_names), Line::from(column_names)];
+        let tables: Vec<_> = groups
+            .iter()
+            .map(|group| self.acquire_game.price_table(*group))
+            .collect();
+        for bucket in 0..tables[0].len() {
+            let mut spans = vec![format!("{:6}", tables[0][bucket].0).into()];
+            for (group, table) in groups.iter().zip(&tables) {
+                let (_, price, majority, minority) = table[bucket];
+                let cell = format!("  {:>5} {:>6} {:>5}", price, majority, minority);
+                let is_on_board = active_prices.iter().any(|(hotel, active_price)| {
+                    hotel.get_price_group() == group.get_price_group() && *active_price == price
+                });
+                spans.push(match is_on_board {
+                    true => cell.bold().yellow(),
+                    false => cell.into(),
+                });
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let width = area.width.min(70);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let table_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let inner = block.inner(table_area);
+        frame.render_widget(Clear, table_area);
+        frame.render_widget(Paragraph::new(Text::from(lines)).block(block), table_area);
+
+        inner
+    }
+
+    // everyone's shares in the chains on the board, players with the most shares
+    // first, with the players in line for each bonus marked
+    fn render_ownership(&self, area: Rect, frame: &mut Frame) -> Rect {
+        let title = Title::from(" Ownership ".bold());
+
+        let block = Block::default()
+            .title(title.alignment(Alignment::Center))
+            .borders(Borders::ALL)
+            .border_set(border::THICK)
+            .border_type(BorderType::Rounded);
+
+        let game = &self.acquire_game;
+        let hotels = game.board.get_active_hotels();
+
+        let mut header = vec![format!("{:<12}", "").into()];
+        for hotel in &hotels {
+            header.push(
+                format!("{:>6}", &hotel.to_string()[..2])
+                    .bold()
+                    .fg(self.palette.hotel_color(*hotel)),
+            );
+        }
+        let mut lines = vec![Line::from(header)];
+
+        let mut players: Vec<usize> = (0..game.players.len()).collect();
+        let total_shares =
+            |player: usize| -> u32 { hotels.iter().map(|h| game.player_shares(player, *h)).sum() };
+        players.sort_by_key(|player| std::cmp::Reverse(total_shares(*player)));
+
+        for player in players {
+            let mut spans = vec![format!("{:<12.12}", game.players[player].name).into()];
+            for hotel in &hotels {
+                let shares = game.player_shares(player, *hotel);
+                let (marker, is_majority) = if game.majority_holders(*hotel).contains(&player) {
+                    ("M", true)
+                } else if game.minority_holders(*hotel).contains(&player) {
+                    ("m", false)
+                } else {
+                    (" ", false)
+                };
+                let cell = format!("{:>5}{}", shares, marker);
+                spans.push(match (is_majority, marker) {
+                    (true, _) => cell.bold().yellow(),
+                    (false, "m") => cell.yellow(),
+                    _ => cell.into(),
+                });
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(match hotels.is_empty() {
+            true => "No chains are on the board yet".into(),
+            false => "M majority, m minority bonus if merged now".italic(),
+        }));
+
+        let width = area
+            .width
+            .min(12 + 6 * hotels.len() as u16 + 4)
+            .max(46.min(area.width));
+        let height = area.height.min(lines.len() as u16 + 2);
+        let ownership_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let inner = block.inner(ownership_area);
+        frame.render_widget(Clear, ownership_area);
+        frame.render_widget(
+            Paragraph::new(Text::from(lines)).block(block),
+            ownership_area,
+        );
+
+        inner
+    }
+
+    // final cash and net worth, biggest payouts and what was left on the board
+    fn render_stats(&self, area: Rect, frame: &mut Frame) -> Rect {
+        let title = Title::from(" Game Over ".bold());
+
+        let block = Block::default()
+            .title(title.alignment(Alignment::Center))
+            .borders(Borders::ALL)
+            .border_set(border::THICK)
+            .border_type(BorderType::Rounded);
+
+        let names: Vec<String> = self
+            .acquire_game
+            .players
+            .iter()
+            .map(|player| player.name.clone())
+            .collect();
+        let lines: Vec<Line> = self
+            .acquire_game
+            .game_stats()
+            .describe(&names)
+            .into_iter()
+            .map(Line::from)
+            .collect();
+
+        let width = area.width.min(64);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let stats_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let inner = block.inner(stats_area);
+        frame.render_widget(Clear, stats_area);
+        frame.render_widget(Paragraph::new(Text::from(lines)).block(block), stats_area);
+
+        inner
+    }
+
+    fn render_paused(&self, area: Rect, frame: &mut Frame) -> Rect {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::THICK)
+            .border_type(BorderType::Rounded);
+
+        let lines = vec![
+            Line::from("PAUSED".bold()),
+            Line::from(format!("Press {} to resume", key_name(self.keys.pause))),
+        ];
+
+        let width = area.width.min(30);
+        let height = area.height.min(lines.len() as u16 + 2);
+        let paused_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let inner = block.inner(paused_area);
+        frame.render_widget(Clear, paused_area);
+        frame.render_widget(
+            Paragraph::new(Text::from(lines)).centered().block(block),
+            paused_area,
+        );
+
+        inner
+    }
+
+    fn render_help(&self, area: Rect, frame: &mut Frame) -> Rect {
+        let title = Title::from(" Help ".bold());
+
+        let block = Block::default()
+            .title(title.alignment(Alignment::Center))
+            .borders(Borders::ALL)
+            .border_set(border::THICK)
+            .border_type(BorderType::Rounded);
+
+        let keys = &self.keys;
+        let slots = keys
+            .tile_slots
+            .iter()
+            .map(|key| key_name(*key))
+            .collect::<Vec<_>>()
+            .join("");
+        let controls = [
+            (slots, "play a tile from your hand"),
+            ("1-7".to_string(), "choose a hotel or buy its stock"),
+            ("1-6".to_string(), "view a player when not choosing"),
+            (key_name(keys.follow), "toggle following the active player"),
+            ("Space".to_string(), "draw a starting tile or end the game"),
+            ("Esc".to_string(), "keep playing instead of ending"),
+            (
+                format!(
+                    "{} / {} / {}",
+                    key_name(keys.keep),
+                    key_name(keys.sell),
+                    key_name(keys.trade)
+                ),
+                "keep, sell or trade defunct stock",
+            ),
+            (
+                format!(
+                    "{} / {} / {}",
+                    key_name(keys.keep_all),
+                    key_name(keys.sell_all),
+                    key_name(keys.trade_all)
+                ),
+                "keep, sell or trade all of it",
+            ),
+            (
+                format!(
+                    "0-9 then {} / {}",
+                    key_name(keys.sell),
+                    key_name(keys.trade)
+                ),
+                "sell or trade that many shares",
+            ),
+            (
+                "k,s,t then Enter".to_string(),
+                "keep, sell and trade shares at once",
+            ),
+            ("Shift+1-7".to_string(), "buy as much of a hotel as you can"),
+            (
+                key_name(keys.auto_buy),
+                "buy the cheapest stock you can afford",
+            ),
+            (
+                format!("{} / Esc", key_name(keys.pass)),
+                "stop buying stock this turn",
+            ),
+            (key_name(keys.undo), "undo your last tile or stock purchase"),
+            (
+                "any key".to_string(),
+                "skip the pause before the computer moves",
+            ),
+            ("PgUp / PgDn".to_string(), "scroll the messages"),
+            (
+                key_name(keys.switch_view),
+                "switch between messages and moves",
+            ),
+            (key_name(keys.price_table), "show or hide the price chart"),
+            (
+                key_name(keys.ownership),
+                "show or hide who holds each hotel",
+            ),
+            (key_name(keys.hint), "hint at a good tile to play"),
+            (
+                key_name(keys.drawn_tiles),
+                "mark empty cells whose tile has been drawn",
+            ),
+            (
+                key_name(keys.stats),
+                "show or hide the recap once the game is over",
+            ),
+            ("Ctrl+S / Ctrl+L".to_string(), "save or load the game"),
+            (key_name(keys.resign), "resign the player you are viewing"),
+            (key_name(keys.pause), "pause the game for a break"),
+            (key_name(keys.quit), "quit"),
+            (key_name(keys.help), "toggle this help"),
+        ];
+        let help_text = Text::from(
+            controls
+                .iter()
+                .map(|(key, action)| {
+                    Line::from(vec![
+                        format!("{:>16}", key).bold(),
+                        format!("  {}", action).into(),
+                    ])
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let width = area.width.min(60);
+        let height = area.height.min(controls.len() as u16 + 2);
+        let help_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let inner = block.inner(help_area);
+        frame.render_widget(Clear, help_area);
+        frame.render_widget(Paragraph::new(help_text).block(block), help_area);
+
+        inner
+    }
+
+    // the hotels the current request accepts next to the digit that picks them,
+    // hotels that cannot be chosen are grayed out
+    fn get_hotel_choices_line(&self) -> Line<'_> {
+        let acceptable_hotels = self.acquire_game.get_acceptable_hotels_for_response();
+
+        let spans = Hotel::iter()
+            .map(|hotel| {
+                let number = hotel as usize + 1;
+                match acceptable_hotels.contains(&hotel) {
+                    true => format!("{} {} ", number, hotel)
+                        .bold()
+                        .fg(self.palette.hotel_color(hotel)),
+                    false => format!("{} {} ", number, &hotel.to_string()[..2]).dark_gray(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Line::from(spans)
+    }
+
+    // the bonuses each hotel the current request accepts would pay if it went defunct,
+    // so the choice of survivor or which chain to resolve first is an informed one
+    fn get_merger_preview_lines(&self) -> Vec<Line<'_>> {
+        self.acquire_game
+            .get_acceptable_hotels_for_response()
+            .into_iter()
+            .map(|hotel| {
+                let preview = self.acquire_game.preview_merger_payout(hotel);
+                let payouts = match preview.payouts.is_empty() {
+                    true => "no bonuses".to_string(),
+                    false => preview
+                        .payouts
+                        .iter()
+                        .map(|(player, amount, _)| {
+                            format!("{} ${}", self.acquire_game.players[*player].name, amount)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                };
+                Line::from(vec![
+                    format!("{} defunct: ", hotel).fg(self.palette.hotel_color(hotel)),
+                    payouts.into(),
+                ])
+            })
+            .collect()
+    }
+
     fn render_prompt(&self, area: Rect, frame: &mut Frame) -> Rect {
         let title = Title::from(" Prompt ".bold());
 
-        let block = Block::default()
+        // how many tiles are left in the bag matters for timing the end of the game
+        let tiles_left = self.acquire_game.get_number_of_tiles_left();
+        let tiles_left_title = Title::from(match tiles_left {
+            0..=10 => format!(" Tiles left: {} ", tiles_left).bold().yellow(),
+            _ => format!(" Tiles left: {} ", tiles_left).into(),
+        })
+        .position(Position::Bottom)
+        .alignment(Alignment::Right);
+
+        let mut block = Block::default()
             .title(title.alignment(Alignment::Center))
+            .title(tiles_left_title)
             .borders(Borders::ALL)
             .border_set(border::THICK)
             .border_type(BorderType::Rounded);
 
+        if let Some(turn_seconds) = self.turn_seconds {
+            if !self.acquire_game.is_game_over() {
+                let seconds_left =
+                    turn_seconds.saturating_sub(self.turn_started.elapsed().as_secs());
+                let clock = format!(" {}s left ", seconds_left);
+                block = block.title(
+                    Title::from(match seconds_left {
+                        0..=5 => clock.bold().red(),
+                        _ => clock.into(),
+                    })
+                    .position(Position::Bottom)
+                    .alignment(Alignment::Left),
+                );
+            }
+        }
+
         let current_acquire_request = self.acquire_game.get_current_request();
 
-        let prompt_text = match current_acquire_request {
+        let mut prompt_text = match current_acquire_request {
+            _ if self.confirm_quit => Text::from(vec![
+                Line::from(vec!["Quit? (y/n)".into()]),
+                Line::from(vec!["Unsaved progress will be lost".into()]),
+            ]),
+            _ if self.spectate && !self.acquire_game.is_game_over() => {
+                Text::from(vec![Line::from(vec![format!(
+                    "Spectating, waiting on {}",
+                    self.acquire_game.players[current_acquire_request.get_player()].name
+                )
+                .into()])])
+            }
+            _ if self.confirm_resign => Text::from(vec![
+                Line::from(vec![format!(
+                    "Resign {}? (y/n)",
+                    self.acquire_game.players[self.current_player].name
+                )
+                .into()]),
+                Line::from(vec![
+                    "Their stock is sold and their turns are skipped".into()
+                ]),
+            ]),
+            _ if self.offers_concession() => {
+                let leader = self
+                    .acquire_game
+                    .is_mathematically_decided()
+                    .unwrap_or_default();
+                Text::from(vec![
+                    Line::from(vec![format!(
+                        "No one can catch {} any more",
+                        self.acquire_game.players[leader].name
+                    )
+                    .into()]),
+                    Line::from(vec!["Concede the game? (y/n)".into()]),
+                ])
+            }
+            _ if self.acquire_game.is_game_over() => Text::from(vec![
+                Line::from(vec!["The game is over!".into()]),
+                Line::from(format!("Press {} to quit", key_name(self.keys.quit))),
+            ]),
             AcquireRequest::PlayStartingTile(player) if *player == self.current_player => {
                 Text::from(vec![
                     Line::from(vec!["It's your turn!".into()]),
@@ -801,46 +2484,106 @@ impl TuiApp {
             AcquireRequest::ChooseNewChain(player) if *player == self.current_player => {
                 Text::from(vec![
                     Line::from(vec!["You started a new chain!".into()]),
-                    Line::from(vec!["Press 1-7 to choose a hotel".into()]),
+                    self.get_hotel_choices_line(),
                     Line::from(vec![self.error_message_per_player[self.current_player]
                         .clone()
                         .into()]),
                 ])
             }
             AcquireRequest::ChooseMergerSurvivor(player) if *player == self.current_player => {
-                Text::from(vec![
+                let mut lines = vec![
                     Line::from(vec!["Choose a surviving chain!".into()]),
-                    Line::from(vec!["Press 1-7 to choose a hotel".into()]),
-                    Line::from(vec![self.error_message_per_player[self.current_player]
-                        .clone()
-                        .into()]),
-                ])
+                    self.get_hotel_choices_line(),
+                ];
+                lines.extend(self.get_merger_preview_lines());
+                lines.push(Line::from(vec![self.error_message_per_player
+                    [self.current_player]
+                    .clone()
+                    .into()]));
+                Text::from(lines)
             }
             AcquireRequest::ChooseDefunctChainToResolve(player)
                 if *player == self.current_player =>
             {
-                Text::from(vec![
+                let mut lines = vec![
                     Line::from(vec!["Choose a defunct chain to resolve".into()]),
-                    Line::from(vec!["Press 1-7 to choose a hotel".into()]),
+                    self.get_hotel_choices_line(),
+                ];
+                lines.extend(self.get_merger_preview_lines());
+                lines.push(Line::from(vec![self.error_message_per_player
+                    [self.current_player]
+                    .clone()
+                    .into()]));
+                Text::from(lines)
+            }
+            AcquireRequest::DisposeStock(player) if *player == self.current_player => {
+                let heading = match self.acquire_game.current_phase() {
+                    GamePhase::DisposeStock {
+                        defunct,
+                        survivor,
+                        merge_maker,
+                        ..
+                    } => format!(
+                        "Disposing {} → {} ({}'s merger)",
+                        defunct, survivor, self.acquire_game.players[merge_maker].name
+                    ),
+                    _ => "Dispose of stock".to_string(),
+                };
+                let shares = self
+                    .acquire_game
+                    .get_remaining_shares_to_dispose(self.current_player);
+
+                let instructions = match (shares, self.dispose_count.as_str()) {
+                    (0, _) => "You have no shares left to dispose of".to_string(),
+                    (_, "") => format!(
+                        "You hold {} shares. (s)ell/(k)eep/(t)rade, capitals for all.",
+                        shares
+                    ),
+                    (_, split) if split.contains(',') => format!(
+                        "You hold {} shares. Keep,sell,trade {} then Enter.",
+                        shares, split
+                    ),
+                    (_, count) => format!(
+                        "You hold {} shares. (s)ell or (t)rade {} of them.",
+                        shares, count
+                    ),
+                };
+
+                Text::from(vec![
+                    Line::from(vec![heading.into()]),
+                    Line::from(vec![instructions.into()]),
                     Line::from(vec![self.error_message_per_player[self.current_player]
                         .clone()
                         .into()]),
                 ])
             }
-            AcquireRequest::DisposeStock => Text::from(vec![
-                Line::from(vec!["Dispose of stock".into()]),
-                Line::from(vec!["Press 1-7 to choose a hotel".into()]),
-                Line::from(vec![self.error_message_per_player[self.current_player]
-                    .clone()
-                    .into()]),
-            ]),
-            AcquireRequest::BuyStock(player) if *player == self.current_player => Text::from(vec![
-                Line::from(vec!["Buy stock".into()]),
-                Line::from(vec!["Press 1-7 to choose a hotel".into()]),
-                Line::from(vec![self.error_message_per_player[self.current_player]
-                    .clone()
+            AcquireRequest::BuyStock(player) if *player == self.current_player => {
+                let buyable_hotels = self.acquire_game.buyable_hotels(*player);
+                let choices = match buyable_hotels.is_empty() {
+                    true => "There is no stock you can buy".to_string(),
+                    false => buyable_hotels
+                        .iter()
+                        .map(|(hotel, price)| {
+                            format!("{} {} ${}", *hotel as usize + 1, hotel, price)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                };
+
+                let buys_remaining = self.acquire_game.buys_remaining().unwrap_or(0);
+
+                Text::from(vec![
+                    Line::from(vec![format!(
+                        "Buy stock, buys remaining: {} (S or Esc to pass)",
+                        buys_remaining
+                    )
                     .into()]),
-            ]),
+                    Line::from(vec![choices.into()]),
+                    Line::from(vec![self.error_message_per_player[self.current_player]
+                        .clone()
+                        .into()]),
+                ])
+            }
             AcquireRequest::EndGame(player) if *player == self.current_player => Text::from(vec![
                 Line::from(vec!["End the game?".into()]),
                 Line::from(vec!["Press <SPACE> to end the game, <ESC> to cancel".into()]),
@@ -881,6 +2624,22 @@ impl TuiApp {
                 ])])
             }
 
+            AcquireRequest::DisposeStock(player) => {
+                let mut lines = vec![Line::from(vec![
+                    "Waiting for ".into(),
+                    self.acquire_game.players[*player].name.clone().into(),
+                    " to dispose of their stock".into(),
+                ])];
+                if let Some(merge_maker) = self.acquire_game.merge_maker() {
+                    lines.push(Line::from(vec![format!(
+                        "{} triggered this merger",
+                        self.acquire_game.players[merge_maker].name
+                    )
+                    .into()]));
+                }
+                Text::from(lines)
+            }
+
             AcquireRequest::BuyStock(player) => Text::from(vec![Line::from(vec![
                 "Waiting for ".into(),
                 self.acquire_game.players[*player].name.clone().into(),
@@ -893,10 +2652,326 @@ impl TuiApp {
                 " to decide wether or not to end the game".into(),
             ])]),
         };
+        if self.is_bot_move_pending() && !self.spectate {
+            prompt_text
+                .lines
+                .push(Line::from("Press any key to skip ahead"));
+        }
 
-        let inner = block.inner(area).clone();
+        let inner = block.inner(area);
         frame.render_widget(Paragraph::new(prompt_text).centered().block(block), area);
 
         inner
     }
 }
+
+// the last players in turn order are controlled by bots
+fn create_bots(
+    number_of_players: usize,
+    number_of_bots: usize,
+) -> Vec<Option<Box<dyn AcquirePlayerStrategy<TuiMessages>>>> {
+    (0..number_of_players)
+        .map(|player| {
+            if player + number_of_bots >= number_of_players {
+                Some(Box::new(GreedyBot::new(player)) as Box<dyn AcquirePlayerStrategy<_>>)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use actuire::logic::acquire_constants::TILES_PER_PLAYER;
+    use crossterm::event::KeyEvent;
+
+    use super::*;
+
+    // an app for a new two player game with player 1 answering the given request
+    fn app_with_request(request: AcquireRequest) -> TuiApp {
+        let messages = Arc::new(TuiMessages::default());
+        let game = AcquireGame::new_with_first_player(2, messages.clone(), 0);
+        let mut snapshot = game.snapshot();
+        snapshot.current_request = request;
+        TuiApp::builder(AcquireGame::from_snapshot(snapshot, messages).unwrap()).build()
+    }
+
+    fn press(app: &mut TuiApp, c: char) {
+        let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        app.handle_event(event::Event::Key(key)).unwrap();
+    }
+
+    #[test]
+    fn test_zero_key_is_ignored() {
+        for request in [
+            AcquireRequest::PlayTile(0),
+            AcquireRequest::ChooseNewChain(0),
+            AcquireRequest::BuyStock(0),
+        ] {
+            let mut app = app_with_request(request);
+            press(&mut app, '0');
+            assert_eq!(*app.acquire_game.get_current_request(), request);
+            assert_eq!(app.current_player, 0);
+        }
+
+        // merger choices can't be restored from a snapshot, so player 1 plays a tile
+        // between Luxor and Tower, tied at two tiles, with or without a larger
+        // Continental underneath
+        for (with_continental, request) in [
+            (false, AcquireRequest::ChooseMergerSurvivor(0)),
+            (true, AcquireRequest::ChooseDefunctChainToResolve(0)),
+        ] {
+            let messages = Arc::new(TuiMessages::default());
+            let game = AcquireGame::new_with_first_player(2, messages.clone(), 0);
+            let mut snapshot = game.snapshot();
+            let mut chains = vec![
+                ((0, 0), Hotel::Luxor),
+                ((0, 1), Hotel::Luxor),
+                ((0, 3), Hotel::Tower),
+                ((0, 4), Hotel::Tower),
+            ];
+            if with_continental {
+                chains.extend((1..4).map(|row| ((row, 2), Hotel::Continental)));
+            }
+            for &((row, col), hotel) in &chains {
+                snapshot.cells[row][col] = game_board::Cell::Hotel(hotel);
+                snapshot.chain_sizes[hotel as usize] += 1;
+            }
+            // the bag is worked out from the board and the hands, so no one may hold
+            // a tile that is now on the board or the tile player 1 is given
+            let tile = Tile::from((0, 2));
+            for player in &mut snapshot.players {
+                player.tiles.retain(|held| {
+                    *held != tile && !chains.iter().any(|(cell, _)| Tile::from(*cell) == *held)
+                });
+            }
+            snapshot.players[0].tiles.truncate(TILES_PER_PLAYER - 1);
+            snapshot.players[0].tiles.push(tile);
+            let mut game = AcquireGame::from_snapshot(snapshot, messages).unwrap();
+            game.handle_player_response(AcquirePlayerResponse::new(AcquireResponse::Tile(tile), 0))
+                .unwrap();
+            assert_eq!(*game.get_current_request(), request);
+
+            let mut app = TuiApp::builder(game).build();
+            press(&mut app, '0');
+            assert_eq!(*app.acquire_game.get_current_request(), request);
+            assert_eq!(app.current_player, 0);
+        }
+    }
+
+    // player 1 is buying stock with Luxor at $200 and American at $300 a share
+    fn app_buying_stock(cash: u32) -> TuiApp {
+        let messages = Arc::new(TuiMessages::default());
+        let game = AcquireGame::new_with_first_player(2, messages.clone(), 0);
+        let mut snapshot = game.snapshot();
+        let chains = [(Hotel::Luxor, 0), (Hotel::American, 2)];
+        for (hotel, row) in chains {
+            for col in 0..2 {
+                snapshot.cells[row][col] = game_board::Cell::Hotel(hotel);
+                for player in &mut snapshot.players {
+                    player.tiles.retain(|tile| *tile != Tile::from((row, col)));
+                }
+            }
+            snapshot.chain_sizes[hotel as usize] = 2;
+        }
+        snapshot.players[0].cash = cash;
+        snapshot.current_request = AcquireRequest::BuyStock(0);
+        TuiApp::builder(AcquireGame::from_snapshot(snapshot, messages).unwrap()).build()
+    }
+
+    #[test]
+    fn test_auto_buy_buys_the_cheapest_stock() {
+        let mut app = app_buying_stock(6000);
+        press(&mut app, 'a');
+        assert_eq!(app.acquire_game.player_shares(0, Hotel::Luxor), 3);
+        assert_eq!(app.acquire_game.players[0].cash, 5400);
+        assert_ne!(
+            *app.acquire_game.get_current_request(),
+            AcquireRequest::BuyStock(0)
+        );
+    }
+
+    #[test]
+    fn test_auto_buy_stops_when_the_cash_runs_out() {
+        let mut app = app_buying_stock(500);
+        press(&mut app, 'a');
+        assert_eq!(app.acquire_game.player_shares(0, Hotel::Luxor), 2);
+        assert_eq!(app.acquire_game.players[0].cash, 100);
+        assert_eq!(
+            *app.acquire_game.get_current_request(),
+            AcquireRequest::BuyStock(0)
+        );
+        assert_eq!(app.error_message_per_player[0], "");
+    }
+
+    #[test]
+    fn test_buy_error_survives_switching_views() {
+        let mut app = app_buying_stock(6000);
+        let tower = char::from_digit(Hotel::Tower as u32 + 1, 10).unwrap();
+        press(&mut app, tower);
+        let error = "Tower has not been founded, its stock cannot be bought";
+        assert_eq!(app.error_message_per_player[0], error);
+
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        app.handle_event(event::Event::Key(tab)).unwrap();
+        assert_eq!(app.error_message_per_player[0], error);
+
+        let luxor = char::from_digit(Hotel::Luxor as u32 + 1, 10).unwrap();
+        press(&mut app, luxor);
+        assert_eq!(app.error_message_per_player[0], "");
+    }
+
+    #[test]
+    fn test_empty_tile_slot_is_ignored() {
+        let messages = Arc::new(TuiMessages::default());
+        let game = AcquireGame::new_with_first_player(2, messages.clone(), 0);
+        let mut snapshot = game.snapshot();
+        snapshot.players[0].tiles.truncate(3);
+        let mut app =
+            TuiApp::builder(AcquireGame::from_snapshot(snapshot, messages).unwrap()).build();
+
+        press(&mut app, '5');
+        assert_eq!(
+            *app.acquire_game.get_current_request(),
+            AcquireRequest::PlayTile(0)
+        );
+        assert_eq!(app.acquire_game.players[0].tiles.len(), 3);
+        assert_eq!(app.current_player, 0);
+    }
+
+    #[test]
+    fn test_every_tile_slot_key_plays_its_tile() {
+        let letters = KeyBindings::from_toml(
+            "tile_slots = [\"a\", \"s\", \"d\", \"j\", \"k\", \"l\"]\nhint = \"H\"",
+        )
+        .unwrap();
+        for keys in [KeyBindings::default(), letters] {
+            for (slot, key) in keys.tile_slots.clone().into_iter().enumerate() {
+                let messages = Arc::new(TuiMessages::default());
+                let game = AcquireGame::new_with_first_player(2, messages, 0);
+                let tile = game.players[0].tiles[slot];
+                let mut app = TuiApp::builder(game).key_bindings(keys.clone()).build();
+
+                let KeyCode::Char(c) = key else {
+                    panic!("{:?} is not a character", key);
+                };
+                press(&mut app, c);
+                assert!(!app.acquire_game.players[0].tiles.contains(&tile));
+                assert_ne!(
+                    *app.acquire_game.get_current_request(),
+                    AcquireRequest::PlayTile(0)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_status_bar_takes_the_bottom_row() {
+        let app = app_with_request(AcquireRequest::PlayTile(0));
+        let (width, height) = app.get_needed_size();
+        let rects = app.split_rects(Rect::new(0, 0, width, height)).unwrap();
+
+        assert_eq!(rects.status_bar, Rect::new(0, height - 1, width, 1));
+        for panel in [rects.messages, rects.player, rects.stocks] {
+            assert!(!panel.intersects(rects.status_bar));
+        }
+        assert!(app
+            .split_rects(Rect::new(0, 0, width, height - 1))
+            .is_none());
+    }
+
+    #[test]
+    fn test_a_key_press_skips_the_pause_before_a_paced_bot_moves() {
+        let messages = Arc::new(TuiMessages::default());
+        let game = AcquireGame::new_with_first_player(2, messages, 1);
+        let mut app = TuiApp::builder(game).bots(1).pace_bots(true).build();
+        let request = *app.acquire_game.get_current_request();
+        assert_eq!(request.get_player(), 1);
+
+        app.last_bot_response = Instant::now();
+        app.advance_bots();
+        assert_eq!(*app.acquire_game.get_current_request(), request);
+        assert!(app.is_bot_move_pending());
+
+        // the key only hurries the bot, it is not read as a move for the viewed player
+        press(&mut app, '1');
+        assert!(app.error_message_per_player[0].is_empty());
+        app.advance_bots();
+        assert_ne!(*app.acquire_game.get_current_request(), request);
+        assert!(!app.hurry_bots);
+    }
+
+    #[test]
+    fn test_pausing_ignores_game_input_until_resumed() {
+        let mut app = app_with_request(AcquireRequest::PlayTile(0));
+        let hand = app.acquire_game.players[0].tiles.clone();
+
+        press(&mut app, 'P');
+        assert!(app.paused);
+        press(&mut app, '1');
+        press(&mut app, '?');
+        assert_eq!(app.acquire_game.players[0].tiles, hand);
+        assert!(!app.show_help);
+
+        // quitting is still confirmed as usual, declining leaves the game paused
+        press(&mut app, 'q');
+        assert!(app.confirm_quit);
+        press(&mut app, 'n');
+        assert!(!app.exit && app.paused);
+
+        press(&mut app, 'P');
+        assert!(!app.paused);
+        press(&mut app, '1');
+        assert_ne!(app.acquire_game.players[0].tiles, hand);
+    }
+
+    #[test]
+    fn test_hidden_cash_is_shown_for_controlled_players() {
+        let messages = Arc::new(TuiMessages::default());
+        let game = AcquireGame::new_with_first_player(3, messages, 0);
+        let mut app = TuiApp::builder(game).bots(1).hidden_cash(true).build();
+        assert!(app.is_cash_visible(0));
+        assert!(app.is_cash_visible(1));
+        assert!(!app.is_cash_visible(2));
+        assert!(app.visible_leaders().is_empty());
+
+        app.hidden_cash = false;
+        assert!(app.is_cash_visible(2));
+        assert!(!app.visible_leaders().is_empty());
+    }
+
+    #[test]
+    fn test_concession_is_offered_once() {
+        let mut app = app_with_request(AcquireRequest::PlayTile(0));
+        assert!(!app.offers_concession());
+
+        // enough to buy out the bank at the highest prices
+        app.acquire_game.players[1].cash = 200_000;
+        assert!(app.offers_concession());
+
+        // the leader is never asked to concede
+        app.current_player = 1;
+        assert!(!app.offers_concession());
+        app.current_player = 0;
+        press(&mut app, 'n');
+        assert!(!app.offers_concession());
+        assert!(!app.acquire_game.is_game_over());
+
+        app.concession_declined = false;
+        press(&mut app, 'y');
+        assert!(app.acquire_game.is_game_over());
+    }
+
+    #[test]
+    fn test_concession_keeps_hidden_cash_hidden() {
+        let messages = Arc::new(TuiMessages::default());
+        let game = AcquireGame::new_with_first_player(2, messages, 0);
+        let mut app = TuiApp::builder(game).bots(1).hidden_cash(true).build();
+        app.acquire_game.players[1].cash = 200_000;
+        assert!(!app.offers_concession());
+
+        app.hidden_cash = false;
+        assert!(app.offers_concession());
+    }
+}
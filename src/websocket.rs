@@ -0,0 +1,321 @@
+use std::{
+    io,
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use actuire::logic::{
+    acquire_game::AcquireGame,
+    acquire_player_strategy::{AcquirePlayerStrategy, GreedyBot},
+    acquire_response::{AcquirePlayerResponse, AcquireResponse},
+    game_setup::GameSetup,
+    game_snapshot::GameSnapshot,
+};
+use serde::{Deserialize, Serialize};
+use tungstenite::{Message, WebSocket};
+
+use crate::headless::StdoutMessages;
+
+// how long a connection waits for the browser before checking for snapshots to send
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// sent to every browser as a JSON text message, browsers post an AcquirePlayerResponse back
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum BridgeMessage {
+    Snapshot(GameSnapshot),
+    // the connection's last response was rejected
+    Error { message: String },
+}
+
+// what the connection threads tell the game loop
+enum BridgeEvent {
+    Connected(usize, Sender<BridgeMessage>),
+    Response(usize, AcquirePlayerResponse),
+    Invalid(usize, String),
+    Left(usize),
+}
+
+// exposes the game at addr over WebSockets, browsers can still view the
+// final snapshot once the game is over
+pub fn serve(addr: impl ToSocketAddrs, setup: GameSetup, number_of_bots: usize) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    println!(
+        "Serving Acquire over WebSockets on {}",
+        listener.local_addr()?
+    );
+    run_bridge(listener, setup, number_of_bots)
+}
+
+fn run_bridge(listener: TcpListener, setup: GameSetup, number_of_bots: usize) -> io::Result<()> {
+    let messages = Arc::new(StdoutMessages::default());
+    let game = setup
+        .build(messages.clone())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    messages.set_player_names(game.players.iter().map(|p| p.name.clone()).collect());
+
+    let number_of_players = setup.number_of_players;
+    let mut bridge = Bridge {
+        game,
+        bots: (0..number_of_players)
+            .map(|player| {
+                (player + number_of_bots >= number_of_players).then(|| GreedyBot::new(player))
+            })
+            .collect(),
+        subscribers: Vec::new(),
+    };
+    bridge.advance_bots();
+
+    let (events, received_events) = mpsc::channel();
+    thread::spawn(move || {
+        for (connection, stream) in listener.incoming().enumerate() {
+            let Ok(stream) = stream else { continue };
+            let events = events.clone();
+            thread::spawn(move || run_connection(connection, stream, events));
+        }
+    });
+
+    // the game itself only ever runs on this thread, the connections just marshal
+    for event in received_events {
+        match event {
+            BridgeEvent::Connected(connection, sender) => {
+                let _ = sender.send(BridgeMessage::Snapshot(bridge.game.snapshot()));
+                bridge.subscribers.push((connection, sender));
+            }
+            BridgeEvent::Response(connection, response) => {
+                // browsers are not bound to a seat, so none of them may resign one or
+                // concede for it, either would end the game for everyone else
+                let result = match response.response {
+                    AcquireResponse::Resign => {
+                        Err("Resigning is not available in network games".to_string())
+                    }
+                    AcquireResponse::Concede => {
+                        Err("Conceding is not available in network games".to_string())
+                    }
+                    _ => bridge
+                        .game
+                        .handle_player_response(response)
+                        .map_err(|e| e.to_string()),
+                };
+                match result {
+                    Ok(()) => {
+                        bridge.broadcast_snapshot();
+                        bridge.advance_bots();
+                    }
+                    Err(message) => bridge.send_to(connection, BridgeMessage::Error { message }),
+                }
+            }
+            BridgeEvent::Invalid(connection, message) => {
+                bridge.send_to(connection, BridgeMessage::Error { message })
+            }
+            BridgeEvent::Left(connection) => bridge
+                .subscribers
+                .retain(|(subscriber, _)| *subscriber != connection),
+        }
+    }
+    Ok(())
+}
+
+struct Bridge {
+    game: AcquireGame<StdoutMessages>,
+    bots: Vec<Option<GreedyBot>>,
+    subscribers: Vec<(usize, Sender<BridgeMessage>)>,
+}
+
+impl Bridge {
+    fn send_to(&self, connection: usize, message: BridgeMessage) {
+        if let Some((_, sender)) = self
+            .subscribers
+            .iter()
+            .find(|(subscriber, _)| *subscriber == connection)
+        {
+            let _ = sender.send(message);
+        }
+    }
+
+    fn broadcast_snapshot(&self) {
+        let snapshot = self.game.snapshot();
+        for (_, sender) in &self.subscribers {
+            let _ = sender.send(BridgeMessage::Snapshot(snapshot.clone()));
+        }
+    }
+
+    fn advance_bots(&mut self) {
+        while !self.game.is_game_over() {
+            let Some((player, bot)) = self
+                .game
+                .get_players_to_respond()
+                .into_iter()
+                .find_map(|player| self.bots[player].as_ref().map(|bot| (player, bot)))
+            else {
+                return;
+            };
+            let request = *self.game.get_current_request();
+            let response = AcquirePlayerResponse::new(bot.decide(&self.game, &request), player);
+            if let Err(error) = self.game.handle_player_response(response) {
+                println!("Error: {}", error);
+                return;
+            }
+            self.broadcast_snapshot();
+        }
+    }
+}
+
+// passes the browser's responses to the game loop and the game's messages back,
+// reads time out so messages to send are never stuck behind a quiet browser
+fn run_connection(connection: usize, stream: TcpStream, events: Sender<BridgeEvent>) {
+    let Ok(mut socket) = tungstenite::accept(stream) else {
+        return;
+    };
+    if socket
+        .get_ref()
+        .set_read_timeout(Some(POLL_INTERVAL))
+        .is_err()
+    {
+        return;
+    }
+
+    let (sender, outgoing) = mpsc::channel();
+    if events
+        .send(BridgeEvent::Connected(connection, sender))
+        .is_err()
+    {
+        return;
+    }
+
+    while send_outgoing(&mut socket, &outgoing).is_ok() {
+        let event = match socket.read() {
+            Ok(Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(response) => BridgeEvent::Response(connection, response),
+                Err(e) => BridgeEvent::Invalid(connection, format!("Invalid response: {}", e)),
+            },
+            Ok(Message::Close(_)) => break,
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                continue
+            }
+            Err(_) => break,
+        };
+        if events.send(event).is_err() {
+            break;
+        }
+    }
+
+    let _ = socket.close(None);
+    let _ = events.send(BridgeEvent::Left(connection));
+}
+
+// Err once the socket fails or the game loop has finished with this connection
+fn send_outgoing(
+    socket: &mut WebSocket<TcpStream>,
+    outgoing: &Receiver<BridgeMessage>,
+) -> Result<(), ()> {
+    loop {
+        match outgoing.try_recv() {
+            Ok(message) => {
+                let text = serde_json::to_string(&message).map_err(|_| ())?;
+                socket.send(Message::Text(text)).map_err(|_| ())?;
+            }
+            Err(mpsc::TryRecvError::Empty) => return Ok(()),
+            Err(mpsc::TryRecvError::Disconnected) => return Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actuire::logic::{acquire_request::AcquireRequest, hotel_data::Hotel};
+
+    use super::*;
+
+    fn next_message(
+        socket: &mut WebSocket<tungstenite::stream::MaybeTlsStream<TcpStream>>,
+    ) -> BridgeMessage {
+        loop {
+            if let Message::Text(text) = socket.read().unwrap() {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    fn next_request(
+        socket: &mut WebSocket<tungstenite::stream::MaybeTlsStream<TcpStream>>,
+    ) -> AcquireRequest {
+        match next_message(socket) {
+            BridgeMessage::Snapshot(snapshot) => snapshot.current_request,
+            message => panic!("expected a snapshot, got {:?}", message),
+        }
+    }
+
+    #[test]
+    fn test_browsers_receive_snapshots() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let setup = GameSetup::new(7, 2);
+        thread::spawn(move || run_bridge(listener, setup, 0));
+
+        let (mut socket, _) = tungstenite::connect(format!("ws://{}", addr)).unwrap();
+        assert_eq!(
+            next_request(&mut socket),
+            AcquireRequest::PlayStartingTile(0)
+        );
+
+        let post = |socket: &mut WebSocket<_>, player: usize, response: AcquireResponse| {
+            let response = AcquirePlayerResponse::new(response, player);
+            socket
+                .send(Message::Text(serde_json::to_string(&response).unwrap()))
+                .unwrap()
+        };
+
+        post(&mut socket, 0, AcquireResponse::StartingTile);
+        assert_eq!(
+            next_request(&mut socket),
+            AcquireRequest::PlayStartingTile(1)
+        );
+
+        post(&mut socket, 0, AcquireResponse::StartingTile);
+        assert_eq!(
+            next_message(&mut socket),
+            BridgeMessage::Error {
+                message: "It is not your turn".to_string()
+            }
+        );
+
+        post(&mut socket, 1, AcquireResponse::NewChain(Hotel::Luxor));
+        assert!(matches!(
+            next_message(&mut socket),
+            BridgeMessage::Error { .. }
+        ));
+
+        post(&mut socket, 1, AcquireResponse::Resign);
+        assert_eq!(
+            next_message(&mut socket),
+            BridgeMessage::Error {
+                message: "Resigning is not available in network games".to_string()
+            }
+        );
+
+        post(&mut socket, 1, AcquireResponse::Concede);
+        assert_eq!(
+            next_message(&mut socket),
+            BridgeMessage::Error {
+                message: "Conceding is not available in network games".to_string()
+            }
+        );
+
+        socket.send(Message::Text("dance".to_string())).unwrap();
+        assert!(matches!(
+            next_message(&mut socket),
+            BridgeMessage::Error { .. }
+        ));
+    }
+}
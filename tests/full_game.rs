@@ -0,0 +1,248 @@
+// drives whole games through the public API, from the starting tiles through
+// founding chains, buying stock and resolving a merger
+use std::sync::{Arc, Mutex};
+
+use actuire::logic::{
+    acquire_constants::{MAX_STOCK_PER_HOTEL, STARTING_CASH, TILES_PER_PLAYER},
+    acquire_game::{AcquireGame, AcquireGameCallback},
+    acquire_request::AcquireRequest,
+    acquire_response::{
+        AcquirePlayerResponse, AcquireResponse, BuyStockChoice, DisposeStockChoice,
+    },
+    game_board::{Cell, GameBoard},
+    game_snapshot::GameSnapshot,
+    hotel_data::Hotel,
+    tile::Tile,
+};
+
+#[derive(Default)]
+struct CollectedMessages {
+    messages: Mutex<Vec<String>>,
+}
+
+impl AcquireGameCallback for CollectedMessages {
+    fn send_message(&self, message: &str) {
+        self.messages.lock().unwrap().push(message.to_string());
+    }
+}
+
+impl CollectedMessages {
+    fn contains(&self, message: &str) -> bool {
+        self.messages.lock().unwrap().iter().any(|m| m == message)
+    }
+}
+
+// plays the starting tiles for three players and returns the game along with
+// the players in turn order
+fn started_game() -> (
+    AcquireGame<CollectedMessages>,
+    Arc<CollectedMessages>,
+    [usize; 3],
+) {
+    let messages = Arc::new(CollectedMessages::default());
+    let mut game = AcquireGame::with_seed(3, messages.clone(), 42);
+
+    for player in 0..3 {
+        assert_eq!(
+            *game.get_current_request(),
+            AcquireRequest::PlayStartingTile(player)
+        );
+        respond(&mut game, player, AcquireResponse::StartingTile);
+    }
+
+    let first = match *game.get_current_request() {
+        AcquireRequest::PlayTile(player) => player,
+        request => panic!(
+            "Expected the first player to play a tile, not {:?}",
+            request
+        ),
+    };
+    (game, messages, [first, (first + 1) % 3, (first + 2) % 3])
+}
+
+fn cash(game: &AcquireGame<CollectedMessages>) -> Vec<u32> {
+    game.players.iter().map(|player| player.cash).collect()
+}
+
+// every share is either held by a player or still available to buy
+fn assert_stock_is_conserved(game: &AcquireGame<CollectedMessages>) {
+    for hotel in Hotel::iter() {
+        let held: u32 = (0..game.players.len())
+            .map(|player| game.player_shares(player, hotel))
+            .sum();
+        assert_eq!(
+            held + game.get_current_stock_availability(hotel),
+            MAX_STOCK_PER_HOTEL,
+            "{} stock is not conserved",
+            hotel
+        );
+    }
+}
+
+// rebuilds the game from a snapshot changed by edit, the game works out which tiles
+// are left in the bag from the board and the hands
+fn edit_game(game: &mut AcquireGame<CollectedMessages>, edit: impl FnOnce(&mut GameSnapshot)) {
+    let mut snapshot = game.snapshot();
+    edit(&mut snapshot);
+    *game = AcquireGame::from_snapshot(snapshot, game.message_callback()).unwrap();
+}
+
+// hands the player the tile, in place of one of theirs if their hand is full, and plays it
+fn play_tile(game: &mut AcquireGame<CollectedMessages>, player: usize, tile: (usize, usize)) {
+    let tile = Tile::from(tile);
+    edit_game(game, |snapshot| {
+        for holder in &mut snapshot.players {
+            holder.tiles.retain(|held| *held != tile);
+        }
+        let hand = &mut snapshot.players[player].tiles;
+        if hand.len() == TILES_PER_PLAYER {
+            hand.remove(0);
+        }
+        hand.push(tile);
+    });
+    respond(game, player, AcquireResponse::Tile(tile));
+}
+
+fn respond(game: &mut AcquireGame<CollectedMessages>, player: usize, response: AcquireResponse) {
+    game.handle_player_response(AcquirePlayerResponse::new(response, player))
+        .unwrap();
+}
+
+#[test]
+fn test_starting_tile_phase() {
+    let (game, messages, _) = started_game();
+
+    assert_eq!(game.tiles_drawn(), 3 + 3 * TILES_PER_PLAYER);
+    for player in &game.players {
+        assert_eq!(player.tiles.len(), TILES_PER_PLAYER);
+        assert_eq!(player.cash, STARTING_CASH);
+    }
+
+    let independent_tiles = Tile::all()
+        .filter(|tile| game.board.get_cell_state(tile.row, tile.col) == Cell::Independent)
+        .count();
+    assert_eq!(independent_tiles, 3);
+    assert!(messages.contains("All players have placed their starting tiles."));
+    assert!(game.board.get_active_hotels().is_empty());
+    assert_stock_is_conserved(&game);
+}
+
+#[test]
+fn test_found_buy_merge_and_dispose() {
+    let (mut game, messages, [p, q, r]) = started_game();
+
+    // lay out a row where two chains can be founded and then merged
+    edit_game(&mut game, |snapshot| {
+        snapshot.cells = GameBoard::new().cells;
+        for col in [0, 4] {
+            snapshot.cells[0][col] = Cell::Independent;
+            for player in &mut snapshot.players {
+                player.tiles.retain(|held| *held != Tile::from((0, col)));
+            }
+        }
+    });
+
+    // the first player founds Luxor and buys three more shares
+    play_tile(&mut game, p, (0, 1));
+    assert_eq!(
+        *game.get_current_request(),
+        AcquireRequest::ChooseNewChain(p)
+    );
+    respond(&mut game, p, AcquireResponse::NewChain(Hotel::Luxor));
+    respond(
+        &mut game,
+        p,
+        AcquireResponse::BuyStock(BuyStockChoice::BuyN(Hotel::Luxor, 3)),
+    );
+    assert_eq!(game.player_shares(p, Hotel::Luxor), 4);
+    assert_eq!(game.players[p].cash, 5400);
+    assert_stock_is_conserved(&game);
+
+    // the second player founds Tower, buys two shares and passes
+    assert_eq!(*game.get_current_request(), AcquireRequest::PlayTile(q));
+    play_tile(&mut game, q, (0, 5));
+    respond(&mut game, q, AcquireResponse::NewChain(Hotel::Tower));
+    respond(
+        &mut game,
+        q,
+        AcquireResponse::BuyStock(BuyStockChoice::BuyN(Hotel::Tower, 2)),
+    );
+    respond(
+        &mut game,
+        q,
+        AcquireResponse::BuyStock(BuyStockChoice::Pass),
+    );
+    assert_eq!(game.player_shares(q, Hotel::Tower), 3);
+    assert_eq!(game.players[q].cash, 5600);
+    assert_stock_is_conserved(&game);
+
+    // the third player grows Luxor and buys a share of each chain
+    assert_eq!(*game.get_current_request(), AcquireRequest::PlayTile(r));
+    play_tile(&mut game, r, (0, 2));
+    assert_eq!(game.board.get_hotel_chain_size(Hotel::Luxor), 3);
+    respond(
+        &mut game,
+        r,
+        AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Tower)),
+    );
+    respond(
+        &mut game,
+        r,
+        AcquireResponse::BuyStock(BuyStockChoice::Buy(Hotel::Luxor)),
+    );
+    respond(
+        &mut game,
+        r,
+        AcquireResponse::BuyStock(BuyStockChoice::Pass),
+    );
+    assert_eq!(game.players[r].cash, 5500);
+    assert_stock_is_conserved(&game);
+
+    // joining the chains merges the two tile Tower chain into Luxor, the
+    // second player holds the majority and the third the minority
+    assert_eq!(*game.get_current_request(), AcquireRequest::PlayTile(p));
+    play_tile(&mut game, p, (0, 3));
+    assert_eq!(game.players[q].cash, 5600 + 2000);
+    assert_eq!(game.players[r].cash, 5500 + 1000);
+
+    // stock is disposed of in turn order after the player who merged
+    assert_eq!(*game.get_current_request(), AcquireRequest::DisposeStock(q));
+
+    // sell one share, trade two for a Luxor share and keep the last one
+    respond(
+        &mut game,
+        q,
+        AcquireResponse::DisposeStock(q, DisposeStockChoice::SellN(1)),
+    );
+    assert_eq!(game.players[q].cash, 7800);
+    respond(
+        &mut game,
+        q,
+        AcquireResponse::DisposeStock(q, DisposeStockChoice::TradeN(2)),
+    );
+    assert_eq!(game.player_shares(q, Hotel::Tower), 0);
+    assert_eq!(game.player_shares(q, Hotel::Luxor), 1);
+    assert_stock_is_conserved(&game);
+    assert_eq!(*game.get_current_request(), AcquireRequest::DisposeStock(r));
+
+    respond(
+        &mut game,
+        r,
+        AcquireResponse::DisposeStock(r, DisposeStockChoice::KeepAll),
+    );
+    assert_eq!(game.player_shares(r, Hotel::Tower), 1);
+
+    // the merger is over and the player who caused it can buy stock
+    assert!(messages.contains("The merger into Luxor is complete!"));
+    assert_eq!(*game.get_current_request(), AcquireRequest::BuyStock(p));
+    assert_eq!(game.board.get_active_hotels(), vec![Hotel::Luxor]);
+    assert_eq!(game.board.get_hotel_chain_size(Hotel::Luxor), 6);
+    assert_eq!(game.get_current_stock_availability(Hotel::Tower), 24);
+    assert_eq!(game.get_current_stock_availability(Hotel::Luxor), 19);
+    assert_eq!(
+        cash(&game).iter().sum::<u32>(),
+        5400 + 7800 + 6500,
+        "only the bank's bonuses and sales change the cash in play"
+    );
+    assert_stock_is_conserved(&game);
+}